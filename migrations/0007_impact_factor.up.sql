@@ -0,0 +1,12 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS impact_factor (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        product_id UUID NOT NULL UNIQUE,
+        co2e_per_kg DECIMAL(10, 4) NOT NULL DEFAULT 0,
+        landfill_m3_per_kg DECIMAL(10, 6) NOT NULL DEFAULT 0,
+        energy_kwh_per_kg DECIMAL(10, 4) NOT NULL DEFAULT 0,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (product_id) REFERENCES product (id) ON DELETE CASCADE
+    );
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS epr_report;
+DROP TABLE IF EXISTS epr_product_stream;
+DROP TABLE IF EXISTS epr_stream;
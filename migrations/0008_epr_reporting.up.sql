@@ -0,0 +1,37 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS epr_stream (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        code VARCHAR(255) NOT NULL UNIQUE,
+        name VARCHAR(255) NOT NULL,
+        description TEXT NOT NULL DEFAULT '',
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE TABLE
+    IF NOT EXISTS epr_product_stream (
+        product_id UUID PRIMARY KEY NOT NULL,
+        stream_id UUID NOT NULL,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (product_id) REFERENCES product (id) ON DELETE CASCADE,
+        FOREIGN KEY (stream_id) REFERENCES epr_stream (id)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS epr_report (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        year INTEGER NOT NULL,
+        quarter INTEGER NOT NULL CHECK (quarter BETWEEN 1 AND 4),
+        period_start TIMESTAMP NOT NULL,
+        period_end TIMESTAMP NOT NULL,
+        rows JSONB NOT NULL,
+        csv TEXT NOT NULL,
+        checksum VARCHAR(255) NOT NULL,
+        generated_by UUID,
+        submitted_by UUID,
+        submitted_at TIMESTAMP,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (generated_by) REFERENCES users (id) ON DELETE SET NULL,
+        FOREIGN KEY (submitted_by) REFERENCES users (id) ON DELETE SET NULL
+    );
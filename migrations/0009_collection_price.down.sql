@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS collection_set_price ON collection;
+
+DROP FUNCTION IF EXISTS set_collection_price;
+
+ALTER TABLE collection
+DROP COLUMN IF EXISTS price;
@@ -0,0 +1,43 @@
+-- Add up migration script here
+-- The price per kilogram a collection was bought at, so statements and reports
+-- keep their amounts when prices change later on.
+ALTER TABLE collection
+ADD COLUMN IF NOT EXISTS price DECIMAL(10, 2);
+
+UPDATE collection
+SET
+    price = COALESCE(
+        (
+            SELECT
+                price
+            FROM
+                product
+            WHERE
+                product.id = collection.product_id
+        ),
+        0
+    );
+
+ALTER TABLE collection
+ALTER COLUMN price
+SET NOT NULL;
+
+-- Collections are priced at the product price when they are captured, and
+-- again when they are moved to another product.
+CREATE OR REPLACE FUNCTION set_collection_price () RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_OP = 'INSERT' AND NEW.price IS NULL)
+        OR (TG_OP = 'UPDATE' AND NEW.product_id <> OLD.product_id) THEN
+        SELECT price INTO NEW.price FROM product WHERE id = NEW.product_id;
+
+        NEW.price := COALESCE(NEW.price, 0);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER collection_set_price
+BEFORE INSERT
+OR
+UPDATE OF product_id ON collection FOR EACH ROW
+EXECUTE FUNCTION set_collection_price ();
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS report_job_run;
+DROP TABLE IF EXISTS report_job;
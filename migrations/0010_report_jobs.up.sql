@@ -0,0 +1,36 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS report_job (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        name VARCHAR(255) NOT NULL,
+        report_type VARCHAR(255) NOT NULL,
+        business_id UUID,
+        cron_expression VARCHAR(255) NOT NULL,
+        utc_offset_minutes INTEGER NOT NULL DEFAULT 120,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_by UUID,
+        last_run_at TIMESTAMP,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE TABLE
+    IF NOT EXISTS report_job_run (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        report_job_id UUID NOT NULL,
+        status VARCHAR(255) NOT NULL DEFAULT 'Running',
+        output_path VARCHAR(255),
+        output_size BIGINT,
+        error TEXT,
+        -- The period the run covers, from the end of the previous successful
+        -- run of the job until the run started. Open-ended for the first run.
+        period_start TIMESTAMP,
+        period_end TIMESTAMP,
+        started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        finished_at TIMESTAMP,
+        FOREIGN KEY (report_job_id) REFERENCES report_job (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS report_job_run_report_job_id_idx ON report_job_run (report_job_id, started_at DESC);
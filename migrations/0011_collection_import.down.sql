@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS collection_import_row;
+
+DROP INDEX IF EXISTS product_business_id_code_idx;
+
+ALTER TABLE product DROP COLUMN IF EXISTS code;
+
+ALTER TABLE collector_profile DROP COLUMN IF EXISTS collector_code;
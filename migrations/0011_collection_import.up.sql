@@ -0,0 +1,35 @@
+-- Add up migration script here
+ALTER TABLE collector_profile ADD COLUMN IF NOT EXISTS collector_code VARCHAR(255) UNIQUE;
+
+ALTER TABLE product ADD COLUMN IF NOT EXISTS code VARCHAR(255);
+
+CREATE UNIQUE INDEX IF NOT EXISTS product_business_id_code_idx ON product (business_id, code);
+
+CREATE TABLE
+    IF NOT EXISTS collection_import_row (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        -- Weighings without a ticket number or time can not be told apart
+        -- from another weighing of the same weight, so they have no hash and
+        -- never count as duplicates.
+        row_hash VARCHAR(32),
+        source VARCHAR(255) NOT NULL,
+        row_number INTEGER NOT NULL,
+        reference VARCHAR(255),
+        collector_reference VARCHAR(255) NOT NULL,
+        product_reference VARCHAR(255) NOT NULL,
+        weight VARCHAR(255) NOT NULL,
+        weighed_at VARCHAR(255),
+        status VARCHAR(255) NOT NULL DEFAULT 'Pending',
+        reason TEXT,
+        collection_id UUID,
+        created_by UUID,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE (business_id, row_hash),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (collection_id) REFERENCES collection (id) ON DELETE SET NULL,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS collection_import_row_status_idx ON collection_import_row (business_id, status);
@@ -0,0 +1,14 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS scale (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        name VARCHAR(255) NOT NULL,
+        connection_type VARCHAR(255) NOT NULL,
+        host VARCHAR(255),
+        port INTEGER,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS webhook_delivery_attempt;
+DROP TABLE IF EXISTS webhook_delivery;
+DROP TABLE IF EXISTS webhook_subscription;
+
+ALTER TABLE collection DROP COLUMN IF EXISTS paid_at;
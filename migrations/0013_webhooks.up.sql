@@ -0,0 +1,52 @@
+-- Add up migration script here
+ALTER TABLE collection ADD COLUMN IF NOT EXISTS paid_at TIMESTAMP;
+
+CREATE TABLE
+    IF NOT EXISTS webhook_subscription (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        name VARCHAR(255) NOT NULL,
+        url VARCHAR(2048) NOT NULL,
+        secret VARCHAR(255) NOT NULL,
+        event_types TEXT[] NOT NULL DEFAULT '{}',
+        business_id UUID,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_by UUID,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE TABLE
+    IF NOT EXISTS webhook_delivery (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        subscription_id UUID NOT NULL,
+        event_id UUID NOT NULL,
+        event_type VARCHAR(255) NOT NULL,
+        payload JSONB NOT NULL,
+        status VARCHAR(255) NOT NULL DEFAULT 'Pending',
+        attempts INTEGER NOT NULL DEFAULT 0,
+        next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        last_status_code INTEGER,
+        last_error TEXT,
+        delivered_at TIMESTAMP,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (subscription_id) REFERENCES webhook_subscription (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_due_idx ON webhook_delivery (status, next_attempt_at);
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_subscription_id_idx ON webhook_delivery (subscription_id, created_at DESC);
+
+CREATE TABLE
+    IF NOT EXISTS webhook_delivery_attempt (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        delivery_id UUID NOT NULL,
+        attempt INTEGER NOT NULL,
+        status_code INTEGER,
+        error TEXT,
+        duration_ms BIGINT NOT NULL,
+        created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (delivery_id) REFERENCES webhook_delivery (id) ON DELETE CASCADE
+    );
@@ -0,0 +1,15 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS collection_event (
+        id BIGSERIAL PRIMARY KEY NOT NULL,
+        event_type VARCHAR(255) NOT NULL,
+        collection_id UUID NOT NULL,
+        business_id UUID NOT NULL,
+        collector_id UUID NOT NULL,
+        collection JSONB NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX IF NOT EXISTS collection_event_business_id_idx ON collection_event (business_id, id);
+
+CREATE INDEX IF NOT EXISTS collection_event_created_at_idx ON collection_event (created_at);
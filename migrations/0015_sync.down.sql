@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS collector_profile_tombstone ON collector_profile;
+DROP TRIGGER IF EXISTS product_tombstone ON product;
+DROP FUNCTION IF EXISTS record_collector_tombstone;
+DROP FUNCTION IF EXISTS record_product_tombstone;
+DROP TABLE IF EXISTS sync_tombstone;
+DROP TRIGGER IF EXISTS collector_profile_touch_updated_at ON collector_profile;
+DROP TRIGGER IF EXISTS product_touch_updated_at ON product;
+DROP FUNCTION IF EXISTS touch_updated_at;
@@ -0,0 +1,48 @@
+-- Add up migration script here
+CREATE OR REPLACE FUNCTION touch_updated_at () RETURNS TRIGGER AS $$
+BEGIN
+    NEW.updated_at = clock_timestamp();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER product_touch_updated_at BEFORE
+UPDATE ON product FOR EACH ROW
+EXECUTE FUNCTION touch_updated_at ();
+
+CREATE TRIGGER collector_profile_touch_updated_at BEFORE
+UPDATE ON collector_profile FOR EACH ROW
+EXECUTE FUNCTION touch_updated_at ();
+
+CREATE TABLE
+    IF NOT EXISTS sync_tombstone (
+        id BIGSERIAL PRIMARY KEY NOT NULL,
+        entity VARCHAR(255) NOT NULL,
+        record_id UUID NOT NULL,
+        business_id UUID,
+        deleted_at TIMESTAMP NOT NULL DEFAULT clock_timestamp()
+    );
+
+CREATE INDEX IF NOT EXISTS sync_tombstone_deleted_at_idx ON sync_tombstone (deleted_at);
+
+CREATE OR REPLACE FUNCTION record_product_tombstone () RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO sync_tombstone (entity, record_id, business_id) VALUES ('product', OLD.id, OLD.business_id);
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION record_collector_tombstone () RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO sync_tombstone (entity, record_id) VALUES ('collector', OLD.id);
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER product_tombstone
+AFTER DELETE ON product FOR EACH ROW
+EXECUTE FUNCTION record_product_tombstone ();
+
+CREATE TRIGGER collector_profile_tombstone
+AFTER DELETE ON collector_profile FOR EACH ROW
+EXECUTE FUNCTION record_collector_tombstone ();
@@ -0,0 +1,20 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS idempotency_key (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        user_id UUID NOT NULL,
+        key VARCHAR(255) NOT NULL,
+        request_hash VARCHAR(255) NOT NULL,
+        status_code INTEGER,
+        content_type VARCHAR(255),
+        response_body BYTEA,
+        -- When the request was taken up. A request that never finished, e.g.
+        -- because the client went away, can be taken up again after a while.
+        claimed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        expires_at TIMESTAMP NOT NULL,
+        UNIQUE (user_id, key),
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS idempotency_key_expires_at_idx ON idempotency_key (user_id, expires_at);
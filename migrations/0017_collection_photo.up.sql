@@ -0,0 +1,19 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS collection_photo (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        collection_id UUID NOT NULL,
+        storage_key VARCHAR(255) NOT NULL,
+        thumbnail_key VARCHAR(255) NOT NULL,
+        file_name VARCHAR(255) NOT NULL,
+        content_type VARCHAR(255) NOT NULL,
+        size_bytes BIGINT NOT NULL,
+        width INTEGER NOT NULL,
+        height INTEGER NOT NULL,
+        uploaded_by UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (collection_id) REFERENCES collection (id) ON DELETE CASCADE,
+        FOREIGN KEY (uploaded_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS collection_photo_collection_id_idx ON collection_photo (collection_id);
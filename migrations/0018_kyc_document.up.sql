@@ -0,0 +1,32 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS kyc_document (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        collector_id UUID,
+        business_id UUID,
+        document_type VARCHAR(255) NOT NULL,
+        storage_key VARCHAR(255) NOT NULL,
+        file_name VARCHAR(255) NOT NULL,
+        content_type VARCHAR(255) NOT NULL,
+        size_bytes BIGINT NOT NULL,
+        expires_on DATE,
+        status VARCHAR(255) NOT NULL DEFAULT 'Pending',
+        rejection_reason TEXT,
+        reviewed_by UUID,
+        reviewed_at TIMESTAMP,
+        uploaded_by UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK ((collector_id IS NULL) <> (business_id IS NULL)),
+        CHECK (status IN ('Pending', 'Verified', 'Rejected')),
+        FOREIGN KEY (collector_id) REFERENCES collector_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (reviewed_by) REFERENCES users (id) ON DELETE SET NULL,
+        FOREIGN KEY (uploaded_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS kyc_document_collector_id_idx ON kyc_document (collector_id);
+
+CREATE INDEX IF NOT EXISTS kyc_document_business_id_idx ON kyc_document (business_id);
+
+CREATE INDEX IF NOT EXISTS kyc_document_status_idx ON kyc_document (status);
@@ -0,0 +1,12 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS business_profile_latitude_idx;
+
+ALTER TABLE collection
+DROP CONSTRAINT IF EXISTS collection_coordinates_check,
+DROP COLUMN IF EXISTS latitude,
+DROP COLUMN IF EXISTS longitude;
+
+ALTER TABLE business_profile
+DROP CONSTRAINT IF EXISTS business_profile_coordinates_check,
+DROP COLUMN IF EXISTS latitude,
+DROP COLUMN IF EXISTS longitude;
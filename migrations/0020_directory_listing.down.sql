@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS operating_hours;
+
+DROP TABLE IF EXISTS directory_listing;
@@ -0,0 +1,28 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS directory_listing (
+        business_id UUID PRIMARY KEY NOT NULL,
+        listed BOOLEAN NOT NULL DEFAULT FALSE,
+        show_phone_number BOOLEAN NOT NULL DEFAULT TRUE,
+        show_address BOOLEAN NOT NULL DEFAULT TRUE,
+        show_operating_hours BOOLEAN NOT NULL DEFAULT TRUE,
+        show_prices BOOLEAN NOT NULL DEFAULT TRUE,
+        contact_email VARCHAR(255),
+        website VARCHAR(255),
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    IF NOT EXISTS operating_hours (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        day_of_week SMALLINT NOT NULL,
+        opens_at TIME NOT NULL,
+        closes_at TIME NOT NULL,
+        UNIQUE (business_id, day_of_week),
+        CHECK (day_of_week BETWEEN 1 AND 7),
+        CHECK (opens_at < closes_at),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS pickup_request;
+
+DROP TABLE IF EXISTS driver;
+
+DROP TABLE IF EXISTS vehicle;
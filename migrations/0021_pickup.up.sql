@@ -0,0 +1,73 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS vehicle (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        registration VARCHAR(255) NOT NULL,
+        description VARCHAR(255) NOT NULL DEFAULT '',
+        capacity_kg NUMERIC,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE (business_id, registration),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    IF NOT EXISTS driver (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        first_name VARCHAR(255) NOT NULL,
+        last_name VARCHAR(255) NOT NULL,
+        phone_number VARCHAR(255) NOT NULL,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    IF NOT EXISTS pickup_request (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        collector_id UUID NOT NULL,
+        product_id UUID NOT NULL,
+        estimated_weight NUMERIC NOT NULL,
+        address VARCHAR(255) NOT NULL,
+        city VARCHAR(255) NOT NULL,
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        preferred_date DATE NOT NULL,
+        window_start TIME,
+        window_end TIME,
+        notes TEXT NOT NULL DEFAULT '',
+        status VARCHAR(255) NOT NULL DEFAULT 'Requested',
+        driver_id UUID,
+        vehicle_id UUID,
+        scheduled_date DATE,
+        stop_order INTEGER,
+        collection_id UUID,
+        completed_at TIMESTAMP,
+        created_by UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK (status IN ('Requested', 'Scheduled', 'Completed', 'Cancelled')),
+        CHECK (estimated_weight > 0),
+        CHECK (window_start IS NULL OR window_end IS NULL OR window_start < window_end),
+        CHECK (
+            (latitude IS NULL) = (longitude IS NULL)
+            AND latitude BETWEEN -90 AND 90
+            AND longitude BETWEEN -180 AND 180
+        ),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (collector_id) REFERENCES collector_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (product_id) REFERENCES product (id) ON DELETE CASCADE,
+        FOREIGN KEY (driver_id) REFERENCES driver (id) ON DELETE SET NULL,
+        FOREIGN KEY (vehicle_id) REFERENCES vehicle (id) ON DELETE SET NULL,
+        FOREIGN KEY (collection_id) REFERENCES collection (id) ON DELETE SET NULL,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS pickup_request_business_id_status_idx ON pickup_request (business_id, status);
+
+CREATE INDEX IF NOT EXISTS pickup_request_driver_id_scheduled_date_idx ON pickup_request (driver_id, scheduled_date);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS collection_record_stock ON collection;
+
+DROP FUNCTION IF EXISTS record_collection_stock ();
+
+DROP TABLE IF EXISTS stock_movement;
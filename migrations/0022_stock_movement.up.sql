@@ -0,0 +1,71 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS stock_movement (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        product_id UUID NOT NULL,
+        movement_type VARCHAR(255) NOT NULL,
+        -- Positive into stock, negative out of stock.
+        quantity NUMERIC NOT NULL,
+        collection_id UUID UNIQUE,
+        -- The other side of a baling or transfer.
+        linked_movement_id UUID,
+        reference VARCHAR(255) NOT NULL DEFAULT '',
+        notes TEXT NOT NULL DEFAULT '',
+        created_by UUID,
+        occurred_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK (
+            movement_type IN ('Collection', 'Sale', 'Baling', 'Transfer', 'Write Off')
+        ),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (product_id) REFERENCES product (id) ON DELETE CASCADE,
+        FOREIGN KEY (collection_id) REFERENCES collection (id) ON DELETE CASCADE,
+        FOREIGN KEY (linked_movement_id) REFERENCES stock_movement (id) ON DELETE SET NULL,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS stock_movement_business_id_product_id_idx ON stock_movement (business_id, product_id, occurred_at);
+
+-- Every collection is an inbound movement of its product, kept in step with
+-- the collection.
+CREATE OR REPLACE FUNCTION record_collection_stock () RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO stock_movement (business_id, product_id, movement_type, quantity, collection_id, occurred_at)
+        VALUES (NEW.business_id, NEW.product_id, 'Collection', NEW.weight, NEW.id, COALESCE(NEW.created_at, CURRENT_TIMESTAMP));
+    ELSE
+        UPDATE stock_movement
+        SET business_id = NEW.business_id, product_id = NEW.product_id, quantity = NEW.weight
+        WHERE collection_id = NEW.id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER collection_record_stock
+AFTER INSERT
+OR
+UPDATE OF business_id,
+product_id,
+weight ON collection FOR EACH ROW
+EXECUTE FUNCTION record_collection_stock ();
+
+INSERT INTO
+    stock_movement (
+        business_id,
+        product_id,
+        movement_type,
+        quantity,
+        collection_id,
+        occurred_at
+    )
+SELECT
+    business_id,
+    product_id,
+    'Collection',
+    weight,
+    id,
+    COALESCE(created_at, CURRENT_TIMESTAMP)
+FROM
+    collection ON CONFLICT (collection_id) DO NOTHING;
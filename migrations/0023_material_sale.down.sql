@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS material_sale;
+
+DROP TABLE IF EXISTS offtaker;
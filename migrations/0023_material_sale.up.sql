@@ -0,0 +1,46 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS offtaker (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        name VARCHAR(255) NOT NULL,
+        contact_name VARCHAR(255) NOT NULL DEFAULT '',
+        email VARCHAR(255) NOT NULL DEFAULT '',
+        phone_number VARCHAR(255) NOT NULL DEFAULT '',
+        address VARCHAR(255) NOT NULL DEFAULT '',
+        registration_number VARCHAR(255) NOT NULL DEFAULT '',
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    IF NOT EXISTS material_sale (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        offtaker_id UUID NOT NULL,
+        product_id UUID NOT NULL,
+        weight NUMERIC NOT NULL,
+        price_per_kg NUMERIC NOT NULL,
+        amount NUMERIC GENERATED ALWAYS AS (ROUND(weight * price_per_kg, 2)) STORED,
+        weighbridge_ticket VARCHAR(255) NOT NULL DEFAULT '',
+        invoice_number VARCHAR(255) NOT NULL DEFAULT '',
+        stock_movement_id UUID,
+        sold_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        created_by UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK (weight > 0),
+        CHECK (price_per_kg >= 0),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (offtaker_id) REFERENCES offtaker (id),
+        FOREIGN KEY (product_id) REFERENCES product (id),
+        FOREIGN KEY (stock_movement_id) REFERENCES stock_movement (id) ON DELETE SET NULL,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE UNIQUE INDEX IF NOT EXISTS material_sale_invoice_number_idx ON material_sale (business_id, invoice_number)
+WHERE
+    invoice_number <> '';
+
+CREATE INDEX IF NOT EXISTS material_sale_business_id_sold_at_idx ON material_sale (business_id, sold_at);
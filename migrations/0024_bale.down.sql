@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS bale_collection;
+
+DROP TABLE IF EXISTS bale;
@@ -0,0 +1,36 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS bale (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        product_id UUID NOT NULL,
+        code VARCHAR(255) NOT NULL UNIQUE,
+        status VARCHAR(255) NOT NULL DEFAULT 'Open',
+        -- The weighed bale, set when it is closed.
+        weight NUMERIC,
+        sale_id UUID,
+        created_by UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        closed_at TIMESTAMP,
+        CHECK (status IN ('Open', 'Closed', 'Sold')),
+        CHECK (weight IS NULL OR weight > 0),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (product_id) REFERENCES product (id),
+        FOREIGN KEY (sale_id) REFERENCES material_sale (id) ON DELETE SET NULL,
+        FOREIGN KEY (created_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS bale_business_id_status_idx ON bale (business_id, status);
+
+CREATE INDEX IF NOT EXISTS bale_sale_id_idx ON bale (sale_id);
+
+-- A collection goes into at most one bale.
+CREATE TABLE
+    IF NOT EXISTS bale_collection (
+        bale_id UUID NOT NULL,
+        collection_id UUID NOT NULL UNIQUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (bale_id, collection_id),
+        FOREIGN KEY (bale_id) REFERENCES bale (id) ON DELETE CASCADE,
+        FOREIGN KEY (collection_id) REFERENCES collection (id) ON DELETE CASCADE
+    );
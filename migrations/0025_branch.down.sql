@@ -0,0 +1,54 @@
+-- Add down migration script here
+-- Collections are priced at the product price again.
+CREATE OR REPLACE FUNCTION set_collection_price () RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_OP = 'INSERT' AND NEW.price IS NULL)
+        OR (TG_OP = 'UPDATE' AND NEW.product_id <> OLD.product_id) THEN
+        SELECT price INTO NEW.price FROM product WHERE id = NEW.product_id;
+
+        NEW.price := COALESCE(NEW.price, 0);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS collection_set_price ON collection;
+
+CREATE TRIGGER collection_set_price
+BEFORE INSERT
+OR
+UPDATE OF product_id ON collection FOR EACH ROW
+EXECUTE FUNCTION set_collection_price ();
+
+DROP TRIGGER IF EXISTS collection_assign_branch ON collection;
+
+DROP FUNCTION IF EXISTS assign_collection_branch;
+
+ALTER TABLE collection
+DROP CONSTRAINT IF EXISTS collection_branch_id_fkey;
+
+DROP INDEX IF EXISTS collection_branch_id_idx;
+
+ALTER TABLE collection
+DROP COLUMN IF EXISTS branch_id;
+
+DROP TRIGGER IF EXISTS business_profile_default_branch ON business_profile;
+
+DROP FUNCTION IF EXISTS create_default_branch;
+
+DROP TABLE IF EXISTS branch_staff;
+
+DROP TRIGGER IF EXISTS branch_price_tombstone ON branch_price;
+
+DROP FUNCTION IF EXISTS record_branch_price_tombstone;
+
+DELETE FROM sync_tombstone
+WHERE
+    entity = 'branch_price';
+
+ALTER TABLE sync_tombstone
+DROP COLUMN IF EXISTS branch_id;
+
+DROP TABLE IF EXISTS branch_price;
+
+DROP TABLE IF EXISTS branch;
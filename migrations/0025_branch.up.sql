@@ -0,0 +1,192 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS branch (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        name VARCHAR(255) NOT NULL,
+        phone_number VARCHAR(255) NOT NULL DEFAULT '',
+        address VARCHAR(255) NOT NULL DEFAULT '',
+        city VARCHAR(255) NOT NULL DEFAULT '',
+        state VARCHAR(255) NOT NULL DEFAULT '',
+        zip_code VARCHAR(255) NOT NULL DEFAULT '',
+        latitude DOUBLE PRECISION,
+        longitude DOUBLE PRECISION,
+        -- Collections without a branch go to the default branch.
+        is_default BOOLEAN NOT NULL DEFAULT FALSE,
+        active BOOLEAN NOT NULL DEFAULT TRUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE (business_id, name),
+        UNIQUE (id, business_id),
+        CHECK (
+            NOT is_default
+            OR active
+        ),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE UNIQUE INDEX IF NOT EXISTS branch_business_id_default_idx ON branch (business_id)
+WHERE
+    is_default;
+
+-- A price per kilogram for a product at one branch, in place of the product
+-- price.
+CREATE TABLE
+    IF NOT EXISTS branch_price (
+        branch_id UUID NOT NULL,
+        product_id UUID NOT NULL,
+        price NUMERIC NOT NULL,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (branch_id, product_id),
+        CHECK (price >= 0),
+        FOREIGN KEY (branch_id) REFERENCES branch (id) ON DELETE CASCADE,
+        FOREIGN KEY (product_id) REFERENCES product (id) ON DELETE CASCADE
+    );
+
+-- Branch prices are synced to devices like products, they are keyed by branch
+-- and product so their tombstones keep the branch as well.
+ALTER TABLE sync_tombstone
+ADD COLUMN IF NOT EXISTS branch_id UUID;
+
+CREATE TRIGGER branch_price_touch_updated_at BEFORE
+UPDATE ON branch_price FOR EACH ROW
+EXECUTE FUNCTION touch_updated_at ();
+
+CREATE OR REPLACE FUNCTION record_branch_price_tombstone () RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO sync_tombstone (entity, record_id, business_id, branch_id)
+    VALUES (
+        'branch_price',
+        OLD.product_id,
+        COALESCE(
+            (SELECT business_id FROM branch WHERE id = OLD.branch_id),
+            (SELECT business_id FROM product WHERE id = OLD.product_id)
+        ),
+        OLD.branch_id
+    );
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER branch_price_tombstone
+AFTER DELETE ON branch_price FOR EACH ROW
+EXECUTE FUNCTION record_branch_price_tombstone ();
+
+-- A user works at one branch.
+CREATE TABLE
+    IF NOT EXISTS branch_staff (
+        branch_id UUID NOT NULL,
+        user_id UUID NOT NULL UNIQUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (branch_id, user_id),
+        FOREIGN KEY (branch_id) REFERENCES branch (id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+    );
+
+-- Every business has a default branch at its own address.
+CREATE OR REPLACE FUNCTION create_default_branch () RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO branch (business_id, name, phone_number, address, city, state, zip_code, latitude, longitude, is_default)
+    VALUES (NEW.id, 'Main', NEW.phone_number, NEW.address, NEW.city, NEW.state, NEW.zip_code, NEW.latitude, NEW.longitude, TRUE);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER business_profile_default_branch
+AFTER INSERT ON business_profile FOR EACH ROW
+EXECUTE FUNCTION create_default_branch ();
+
+INSERT INTO
+    branch (
+        business_id,
+        name,
+        phone_number,
+        address,
+        city,
+        state,
+        zip_code,
+        latitude,
+        longitude,
+        is_default
+    )
+SELECT
+    id,
+    'Main',
+    phone_number,
+    address,
+    city,
+    state,
+    zip_code,
+    latitude,
+    longitude,
+    TRUE
+FROM
+    business_profile;
+
+ALTER TABLE collection
+ADD COLUMN IF NOT EXISTS branch_id UUID;
+
+UPDATE collection
+SET
+    branch_id = branch.id
+FROM
+    branch
+WHERE
+    branch.business_id = collection.business_id
+    AND branch.is_default;
+
+-- Collections captured without a branch, or moved to another business
+-- without one, go to the default branch of their business.
+CREATE OR REPLACE FUNCTION assign_collection_branch () RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.branch_id IS NULL
+        OR (TG_OP = 'UPDATE' AND NEW.business_id <> OLD.business_id AND NEW.branch_id = OLD.branch_id) THEN
+        SELECT id INTO NEW.branch_id FROM branch WHERE business_id = NEW.business_id AND is_default;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER collection_assign_branch
+BEFORE INSERT
+OR
+UPDATE OF business_id,
+branch_id ON collection FOR EACH ROW
+EXECUTE FUNCTION assign_collection_branch ();
+
+ALTER TABLE collection
+ALTER COLUMN branch_id
+SET NOT NULL;
+
+ALTER TABLE collection
+ADD CONSTRAINT collection_branch_id_fkey FOREIGN KEY (branch_id, business_id) REFERENCES branch (id, business_id);
+
+CREATE INDEX IF NOT EXISTS collection_branch_id_idx ON collection (branch_id);
+
+-- Collections are priced at the branch price, or else the product price, when
+-- they are captured, and again when they are moved to another product or
+-- branch. Runs after collection_assign_branch, triggers fire by name.
+CREATE OR REPLACE FUNCTION set_collection_price () RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_OP = 'INSERT' AND NEW.price IS NULL)
+        OR (TG_OP = 'UPDATE' AND (NEW.product_id <> OLD.product_id OR NEW.branch_id <> OLD.branch_id)) THEN
+        SELECT COALESCE(branch_price.price, product.price, 0) INTO NEW.price
+        FROM product
+        LEFT JOIN branch_price ON branch_price.product_id = product.id AND branch_price.branch_id = NEW.branch_id
+        WHERE product.id = NEW.product_id;
+
+        NEW.price := COALESCE(NEW.price, 0);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS collection_set_price ON collection;
+
+CREATE TRIGGER collection_set_price
+BEFORE INSERT
+OR
+UPDATE OF business_id,
+branch_id,
+product_id ON collection FOR EACH ROW
+EXECUTE FUNCTION set_collection_price ();
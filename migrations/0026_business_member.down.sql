@@ -0,0 +1,31 @@
+-- Add down migration script here
+ALTER TABLE IF EXISTS branch_staff_unmigrated RENAME TO branch_staff;
+
+CREATE TABLE
+    IF NOT EXISTS branch_staff (
+        branch_id UUID NOT NULL,
+        user_id UUID NOT NULL UNIQUE,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        PRIMARY KEY (branch_id, user_id),
+        FOREIGN KEY (branch_id) REFERENCES branch (id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+    );
+
+INSERT INTO
+    branch_staff (branch_id, user_id, created_at)
+SELECT
+    branch_id,
+    user_id,
+    created_at
+FROM
+    business_member
+WHERE
+    branch_id IS NOT NULL ON CONFLICT DO NOTHING;
+
+DROP TRIGGER IF EXISTS business_profile_owner_membership ON business_profile;
+
+DROP FUNCTION IF EXISTS create_owner_membership;
+
+DROP TABLE IF EXISTS business_invitation;
+
+DROP TABLE IF EXISTS business_member;
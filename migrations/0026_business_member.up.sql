@@ -0,0 +1,100 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS business_member (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        -- A user belongs to one business.
+        user_id UUID NOT NULL UNIQUE,
+        role VARCHAR(255) NOT NULL,
+        -- The branch the member works at, if any.
+        branch_id UUID,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK (role IN ('Owner', 'Manager', 'Cashier', 'Read Only')),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+        FOREIGN KEY (branch_id, business_id) REFERENCES branch (id, business_id) ON DELETE SET NULL (branch_id)
+    );
+
+CREATE INDEX IF NOT EXISTS business_member_business_id_idx ON business_member (business_id);
+
+CREATE TABLE
+    IF NOT EXISTS business_invitation (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        business_id UUID NOT NULL,
+        email VARCHAR(255) NOT NULL,
+        role VARCHAR(255) NOT NULL,
+        branch_id UUID,
+        -- Only a hash of the token is kept.
+        token_hash VARCHAR(255) NOT NULL UNIQUE,
+        invited_by UUID NOT NULL,
+        expires_at TIMESTAMP NOT NULL,
+        accepted_by UUID,
+        accepted_at TIMESTAMP,
+        revoked_at TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        CHECK (role IN ('Owner', 'Manager', 'Cashier', 'Read Only')),
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE,
+        FOREIGN KEY (branch_id, business_id) REFERENCES branch (id, business_id) ON DELETE SET NULL (branch_id),
+        FOREIGN KEY (invited_by) REFERENCES users (id) ON DELETE CASCADE,
+        FOREIGN KEY (accepted_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS business_invitation_business_id_idx ON business_invitation (business_id);
+
+-- The user a business is created for owns it.
+CREATE OR REPLACE FUNCTION create_owner_membership () RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO business_member (business_id, user_id, role)
+    VALUES (NEW.id, NEW.user_id, 'Owner');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER business_profile_owner_membership
+AFTER INSERT ON business_profile FOR EACH ROW
+EXECUTE FUNCTION create_owner_membership ();
+
+INSERT INTO
+    business_member (business_id, user_id, role)
+SELECT
+    id,
+    user_id,
+    'Owner'
+FROM
+    business_profile ON CONFLICT (user_id) DO NOTHING;
+
+-- Branch staff become cashiers at their branch. Staff who already are a
+-- member of the business keep their role and get the branch.
+INSERT INTO
+    business_member (business_id, user_id, role, branch_id)
+SELECT
+    branch.business_id,
+    branch_staff.user_id,
+    'Cashier',
+    branch_staff.branch_id
+FROM
+    branch_staff
+    INNER JOIN branch ON branch.id = branch_staff.branch_id ON CONFLICT (user_id) DO
+UPDATE
+SET
+    branch_id = EXCLUDED.branch_id
+WHERE
+    business_member.business_id = EXCLUDED.business_id
+    AND business_member.branch_id IS NULL;
+
+DELETE FROM branch_staff USING business_member
+WHERE
+    business_member.user_id = branch_staff.user_id
+    AND business_member.branch_id = branch_staff.branch_id;
+
+-- Staff of a branch of another business than the one they own can not be
+-- members of both, they are kept aside to be sorted out by hand.
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM branch_staff) THEN
+        ALTER TABLE branch_staff RENAME TO branch_staff_unmigrated;
+    ELSE
+        DROP TABLE branch_staff;
+    END IF;
+END $$;
@@ -0,0 +1,18 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS user_activation (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        -- The invited user, who stays inactive until the link is used.
+        user_id UUID NOT NULL,
+        -- Only a hash of the token is kept.
+        token_hash VARCHAR(255) NOT NULL UNIQUE,
+        invited_by UUID,
+        expires_at TIMESTAMP NOT NULL,
+        used_at TIMESTAMP,
+        revoked_at TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+        FOREIGN KEY (invited_by) REFERENCES users (id) ON DELETE SET NULL
+    );
+
+CREATE INDEX IF NOT EXISTS user_activation_user_id_idx ON user_activation (user_id);
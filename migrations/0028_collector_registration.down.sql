@@ -0,0 +1,20 @@
+-- Add down migration script here
+DROP TRIGGER IF EXISTS collection_require_approved_collector ON collection;
+
+DROP FUNCTION IF EXISTS require_approved_collector;
+
+DROP TABLE IF EXISTS phone_verification;
+
+DROP INDEX IF EXISTS collector_profile_review_business_id_idx;
+
+DROP INDEX IF EXISTS collector_profile_status_idx;
+
+ALTER TABLE collector_profile
+DROP CONSTRAINT IF EXISTS collector_profile_reviewed_by_fkey,
+DROP CONSTRAINT IF EXISTS collector_profile_review_business_id_fkey,
+DROP CONSTRAINT IF EXISTS collector_profile_status_check,
+DROP COLUMN IF EXISTS reviewed_at,
+DROP COLUMN IF EXISTS reviewed_by,
+DROP COLUMN IF EXISTS rejection_reason,
+DROP COLUMN IF EXISTS review_business_id,
+DROP COLUMN IF EXISTS status;
@@ -0,0 +1,54 @@
+-- Add up migration script here
+-- Collectors added by staff and businesses are approved straight away, self
+-- registered collectors wait for a review.
+ALTER TABLE collector_profile
+ADD COLUMN IF NOT EXISTS status VARCHAR(255) NOT NULL DEFAULT 'Approved',
+-- The business that may review the registration, the nearest one by default.
+ADD COLUMN IF NOT EXISTS review_business_id UUID,
+ADD COLUMN IF NOT EXISTS rejection_reason TEXT,
+ADD COLUMN IF NOT EXISTS reviewed_by UUID,
+ADD COLUMN IF NOT EXISTS reviewed_at TIMESTAMP;
+
+ALTER TABLE collector_profile
+ADD CONSTRAINT collector_profile_status_check CHECK (status IN ('Pending', 'Approved', 'Rejected')),
+ADD CONSTRAINT collector_profile_review_business_id_fkey FOREIGN KEY (review_business_id) REFERENCES business_profile (id) ON DELETE SET NULL,
+ADD CONSTRAINT collector_profile_reviewed_by_fkey FOREIGN KEY (reviewed_by) REFERENCES users (id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS collector_profile_status_idx ON collector_profile (status);
+
+CREATE INDEX IF NOT EXISTS collector_profile_review_business_id_idx ON collector_profile (review_business_id);
+
+CREATE TABLE
+    IF NOT EXISTS phone_verification (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        phone_number VARCHAR(255) NOT NULL,
+        -- Only a hash of the code is kept.
+        code_hash VARCHAR(255) NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        expires_at TIMESTAMP NOT NULL,
+        used_at TIMESTAMP,
+        -- The address the code was requested from, to limit requests per
+        -- address.
+        ip_address VARCHAR(45),
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE INDEX IF NOT EXISTS phone_verification_phone_number_idx ON phone_verification (phone_number);
+
+CREATE INDEX IF NOT EXISTS phone_verification_created_at_idx ON phone_verification (created_at);
+
+-- Only approved collectors can have collections recorded against them.
+CREATE OR REPLACE FUNCTION require_approved_collector () RETURNS TRIGGER AS $$
+BEGIN
+    IF (TG_OP = 'INSERT' OR NEW.collector_id <> OLD.collector_id)
+        AND NOT EXISTS (SELECT 1 FROM collector_profile WHERE id = NEW.collector_id AND status = 'Approved') THEN
+        RAISE EXCEPTION 'Collector % is not approved.', NEW.collector_id
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER collection_require_approved_collector
+BEFORE INSERT OR UPDATE OF collector_id ON collection FOR EACH ROW
+EXECUTE FUNCTION require_approved_collector ();
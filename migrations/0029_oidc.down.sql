@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS user_identity;
+
+DROP TABLE IF EXISTS oidc_login;
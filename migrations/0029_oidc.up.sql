@@ -0,0 +1,27 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS oidc_login (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        -- A sign-in that was started but not yet completed. Only a hash of the
+        -- state is kept, the verifier is needed again for the code exchange.
+        state_hash VARCHAR(255) NOT NULL UNIQUE,
+        code_verifier VARCHAR(255) NOT NULL,
+        nonce VARCHAR(255) NOT NULL,
+        expires_at TIMESTAMP NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+
+CREATE TABLE
+    IF NOT EXISTS user_identity (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        user_id UUID NOT NULL,
+        -- The account of the user at an identity provider.
+        issuer VARCHAR(255) NOT NULL,
+        subject VARCHAR(255) NOT NULL,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        last_login_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        UNIQUE (issuer, subject),
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS user_identity_user_id_idx ON user_identity (user_id);
@@ -0,0 +1,27 @@
+-- Add up migration script here
+CREATE TABLE
+    IF NOT EXISTS api_key (
+        id UUID PRIMARY KEY NOT NULL DEFAULT uuid_generate_v4 (),
+        name VARCHAR(255) NOT NULL,
+        -- The start of the key, kept to tell keys apart. Only a hash of the
+        -- whole key is kept.
+        prefix VARCHAR(255) NOT NULL,
+        key_hash VARCHAR(255) NOT NULL UNIQUE,
+        -- The user the key acts as, who created it. Keys of a business can
+        -- also be managed by the managers of the business, and stop working
+        -- once the user is no longer a member of it.
+        user_id UUID NOT NULL,
+        business_id UUID,
+        -- The areas of the api the key may use, e.g. `collection:read`.
+        permissions TEXT[] NOT NULL DEFAULT '{}',
+        expires_at TIMESTAMP,
+        last_used_at TIMESTAMP,
+        revoked_at TIMESTAMP,
+        created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+        FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+        FOREIGN KEY (business_id) REFERENCES business_profile (id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS api_key_user_id_idx ON api_key (user_id);
+
+CREATE INDEX IF NOT EXISTS api_key_business_id_idx ON api_key (business_id);
@@ -0,0 +1,223 @@
+use axum::{
+    http::{Method, StatusCode},
+    Json,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    data::entities::{api_key::ApiKey, user::User},
+    AppState,
+};
+
+use super::token::{generate_token, hash_token};
+
+/// API keys start with this, which tells them apart from JWTs in the
+/// Authorization header.
+pub const KEY_PREFIX: &str = "3r_";
+
+/// The areas of the api a key can be given access to, by the first segment of
+/// the path. Keys can not manage keys or sign in, so those are left out.
+pub const AREAS: [&str; 21] = [
+    "bale",
+    "branch",
+    "business",
+    "collection",
+    "collector",
+    "epr",
+    "export",
+    "impact",
+    "import",
+    "kyc",
+    "pickup",
+    "product",
+    "report",
+    "sale",
+    "scale",
+    "statement",
+    "stock",
+    "trace",
+    "sync",
+    "users",
+    "webhook",
+];
+
+/// A new key and the prefix to show for it. The key itself is only ever
+/// returned once.
+pub fn generate_key() -> (String, String) {
+    let key = format!("{}{}", KEY_PREFIX, generate_token());
+    let prefix = key[..KEY_PREFIX.len() + 8].to_string();
+
+    (key, prefix)
+}
+
+/// Permissions are an area with `read` or `write`, where `write` includes
+/// `read`, e.g. `collection:write`.
+pub fn is_permission(permission: &str) -> bool {
+    match permission.split_once(':') {
+        Some((area, access)) => AREAS.contains(&area) && (access == "read" || access == "write"),
+        None => false,
+    }
+}
+
+/// Whether the permissions allow a request. Safe methods need `read`, anything
+/// else needs `write`.
+pub fn allows(permissions: &[String], method: &Method, path: &str) -> bool {
+    let area = path.trim_start_matches('/').split('/').next().unwrap_or("");
+
+    if !AREAS.contains(&area) {
+        return false;
+    }
+
+    let write = format!("{}:write", area);
+    let read = format!("{}:read", area);
+
+    permissions
+        .iter()
+        .any(|permission| *permission == write || (method.is_safe() && *permission == read))
+}
+
+/// Finds the user a key acts as and records its use. Keys act as the user who
+/// created them, so a key of a business can do no more than its creator, and
+/// stops working once they are no longer a member of the business.
+pub async fn authenticate(
+    key: &str,
+    method: &Method,
+    path: &str,
+    app_state: &AppState,
+) -> Result<User, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to get API key: {}", error);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({ "message": "Internal Server Error" })),
+        )
+    };
+
+    let unauthorized = |reason: &str| {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "message": "Unauthorized", "reason": reason })),
+        )
+    };
+
+    let api_key = sqlx::query_as!(
+        ApiKey,
+        r#"
+            UPDATE api_key SET last_used_at = CURRENT_TIMESTAMP
+            WHERE key_hash = $1
+                AND revoked_at IS NULL
+                AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+            RETURNING *
+        "#,
+        hash_token(key)
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or_else(|| unauthorized("Invalid API key."))?;
+
+    if !allows(&api_key.permissions, method, path) {
+        return Err(unauthorized(
+            "The API key does not have permission for this request.",
+        ));
+    }
+
+    let user = sqlx::query_as!(
+        User,
+        r#"
+            SELECT * FROM users
+            WHERE id = $1
+                AND (
+                    $2::uuid IS NULL
+                    OR EXISTS (
+                        SELECT 1 FROM business_member
+                        WHERE business_member.user_id = users.id AND business_member.business_id = $2
+                    )
+                )
+        "#,
+        api_key.user_id,
+        api_key.business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or_else(|| unauthorized("Invalid API key."))?;
+
+    if !user.active {
+        return Err(unauthorized("Account deactivated."));
+    }
+
+    tracing::info!(
+        "🔐 User {} authenticated with API key {}.",
+        user.email,
+        api_key.prefix
+    );
+
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permissions(permissions: &[&str]) -> Vec<String> {
+        permissions
+            .iter()
+            .map(|permission| permission.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn recognises_permissions() {
+        assert!(is_permission("collection:read"));
+        assert!(is_permission("sync:write"));
+        assert!(!is_permission("collection"));
+        assert!(!is_permission("collection:delete"));
+        assert!(!is_permission("api_key:write"));
+        assert!(!is_permission("unknown:read"));
+    }
+
+    #[test]
+    fn read_allows_only_safe_methods() {
+        let read = permissions(&["collection:read"]);
+
+        assert!(allows(&read, &Method::GET, "/collection/list"));
+        assert!(allows(&read, &Method::HEAD, "/collection/list"));
+        assert!(!allows(&read, &Method::POST, "/collection/add"));
+        assert!(!allows(&read, &Method::DELETE, "/collection/1"));
+    }
+
+    #[test]
+    fn write_allows_every_method() {
+        let write = permissions(&["collection:write"]);
+
+        assert!(allows(&write, &Method::GET, "/collection/list"));
+        assert!(allows(&write, &Method::POST, "/collection/add"));
+        assert!(allows(&write, &Method::DELETE, "/collection/1"));
+    }
+
+    #[test]
+    fn refuses_other_areas() {
+        let write = permissions(&["collection:write"]);
+
+        assert!(!allows(&write, &Method::GET, "/scale/list"));
+        assert!(!allows(&write, &Method::GET, "/collections/list"));
+        assert!(!allows(&write, &Method::GET, "/unknown"));
+    }
+
+    #[test]
+    fn refuses_key_management() {
+        let write = permissions(&["api_key:write", "collection:write"]);
+
+        assert!(!allows(&write, &Method::GET, "/api_key"));
+        assert!(!allows(&write, &Method::POST, "/api_key/add"));
+    }
+
+    #[test]
+    fn refuses_paths_without_an_area() {
+        let write = permissions(&["collection:write"]);
+
+        assert!(!allows(&write, &Method::GET, "/"));
+        assert!(!allows(&write, &Method::GET, ""));
+    }
+}
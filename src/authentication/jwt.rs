@@ -15,9 +15,15 @@ use jsonwebtoken::{
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::{data::entities::user::User, AppState};
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    AppState,
+};
 
-use super::roles::Role;
+use super::{
+    api_key,
+    roles::{BusinessRole, Role},
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Claims {
@@ -87,27 +93,68 @@ pub async fn jwt_guard(
         })
         .replace("Bearer ", "");
 
-    let claims = validate_jwt(&token, app_state.clone()).await?;
-    let email = &claims.sub;
+    let user = if token.starts_with(api_key::KEY_PREFIX) {
+        api_key::authenticate(&token, request.method(), request.uri().path(), &app_state).await?
+    } else {
+        let claims = validate_jwt(&token, app_state.clone()).await?;
+        let email = &claims.sub;
+
+        sqlx::query_as!(
+            User,
+            r#"
+                SELECT * FROM users WHERE email = $1
+            "#,
+            email
+        )
+        .fetch_one(&app_state.pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to get user: {}", error);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "message": "Internal Server Error" })),
+            )
+        })?
+    };
 
-    let user = sqlx::query_as!(
-        User,
+    // The business the user is a member of, resolved once for the handlers.
+    let membership = sqlx::query_as!(
+        BusinessMember,
         r#"
-            SELECT * FROM users WHERE email = $1
+            SELECT * FROM business_member WHERE user_id = $1
         "#,
-        email
+        user.id
     )
-    .fetch_one(&app_state.pool)
+    .fetch_optional(&app_state.pool)
     .await
     .map_err(|error| {
-        tracing::error!("🔥 Failed to get user: {}", error);
+        tracing::error!("🔥 Failed to get business membership: {}", error);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({ "message": "Internal Server Error" })),
         )
     })?;
 
+    let read_only = membership
+        .as_ref()
+        .is_some_and(|membership| membership.role() == BusinessRole::ReadOnly);
+
+    if read_only
+        && !request.method().is_safe()
+        && !request.uri().path().starts_with("/authentication")
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "message": "Unauthorized", "reason": "Read-only members can not make changes." }),
+            ),
+        ));
+    }
+
     request.extensions_mut().insert::<User>(user);
+    request
+        .extensions_mut()
+        .insert::<Option<BusinessMember>>(membership);
 
     Ok(next.run(request).await)
 }
@@ -131,25 +178,30 @@ pub async fn jwt_guard_optional(
         Some(token) => {
             let token = token.replace("Bearer ", "");
 
-            let claims = validate_jwt(&token, app_state.clone()).await?;
-            let email = &claims.sub;
-
-            let user = sqlx::query_as!(
-                User,
-                r#"
-                    SELECT * FROM users WHERE email = $1
-                "#,
-                email
-            )
-            .fetch_one(&app_state.pool)
-            .await
-            .map_err(|error| {
-                tracing::error!("🔥 Failed to get user: {}", error);
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(json!({ "message": "Internal Server Error" })),
+            let user = if token.starts_with(api_key::KEY_PREFIX) {
+                api_key::authenticate(&token, request.method(), request.uri().path(), &app_state)
+                    .await?
+            } else {
+                let claims = validate_jwt(&token, app_state.clone()).await?;
+                let email = &claims.sub;
+
+                sqlx::query_as!(
+                    User,
+                    r#"
+                        SELECT * FROM users WHERE email = $1
+                    "#,
+                    email
                 )
-            })?;
+                .fetch_one(&app_state.pool)
+                .await
+                .map_err(|error| {
+                    tracing::error!("🔥 Failed to get user: {}", error);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({ "message": "Internal Server Error" })),
+                    )
+                })?
+            };
 
             request.extensions_mut().insert::<Option<User>>(Some(user));
 
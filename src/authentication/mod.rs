@@ -1,2 +1,5 @@
+pub mod api_key;
 pub mod jwt;
+pub mod oidc;
 pub mod roles;
+pub mod token;
@@ -0,0 +1,279 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{AlgorithmParameters, EllipticCurve, Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+use super::roles::Role;
+
+/// Single sign-on for staff through an OpenID Connect provider, using the
+/// authorization code flow with PKCE.
+#[derive(Clone)]
+pub struct Oidc {
+    client: reqwest::Client,
+    issuer: String,
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_url: String,
+    scopes: String,
+    groups_claim: String,
+    admin_groups: Vec<String>,
+    staff_groups: Vec<String>,
+}
+
+/// The endpoints of the provider, from its discovery document.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Provider {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// The verified claims of an ID token that are needed to sign someone in.
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    email: Option<String>,
+    email_verified: Option<bool>,
+    nonce: Option<String>,
+    #[serde(flatten)]
+    other: Map<String, Value>,
+}
+
+impl Oidc {
+    /// Single sign-on is only set up when an issuer and client are configured.
+    pub fn init(config: &Config) -> Option<Self> {
+        Some(Self {
+            client: reqwest::Client::new(),
+            issuer: config.oidc_issuer.clone()?,
+            client_id: config.oidc_client_id.clone()?,
+            client_secret: config.oidc_client_secret.clone(),
+            redirect_url: config.oidc_redirect_url.clone(),
+            scopes: config.oidc_scopes.clone(),
+            groups_claim: config.oidc_groups_claim.clone(),
+            admin_groups: config.oidc_admin_groups.clone(),
+            staff_groups: config.oidc_staff_groups.clone(),
+        })
+    }
+
+    pub async fn provider(&self) -> Result<Provider, Error> {
+        let provider: Provider = self
+            .client
+            .get(format!("{}/.well-known/openid-configuration", self.issuer))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if provider.issuer.trim_end_matches('/') != self.issuer {
+            return Err(anyhow!(
+                "The provider's issuer \"{}\" does not match \"{}\".",
+                provider.issuer,
+                self.issuer
+            ));
+        }
+
+        Ok(provider)
+    }
+
+    /// Where to send the browser to sign in.
+    pub fn authorization_url(
+        &self,
+        provider: &Provider,
+        state: &str,
+        nonce: &str,
+        code_verifier: &str,
+    ) -> Result<String, Error> {
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+        let url = Url::parse_with_params(
+            &provider.authorization_endpoint,
+            [
+                ("response_type", "code"),
+                ("client_id", self.client_id.as_str()),
+                ("redirect_uri", self.redirect_url.as_str()),
+                ("scope", self.scopes.as_str()),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )?;
+
+        Ok(url.to_string())
+    }
+
+    /// Exchanges the code from the redirect for the identity of the user.
+    pub async fn identity(
+        &self,
+        provider: &Provider,
+        code: &str,
+        code_verifier: &str,
+        nonce: &str,
+    ) -> Result<Identity, Error> {
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.redirect_url.as_str()),
+            ("client_id", self.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+
+        if let Some(client_secret) = &self.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        let response = self
+            .client
+            .post(&provider.token_endpoint)
+            .form(&form)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "The token endpoint responded with {}: {}",
+                response.status(),
+                response.text().await.unwrap_or_default()
+            ));
+        }
+
+        let tokens: TokenResponse = response.json().await?;
+
+        self.verify(provider, &tokens.id_token, nonce).await
+    }
+
+    /// Checks the signature of the ID token against the provider's keys, and
+    /// that it was issued by the provider to this client for this sign-in.
+    async fn verify(
+        &self,
+        provider: &Provider,
+        id_token: &str,
+        nonce: &str,
+    ) -> Result<Identity, Error> {
+        let header = decode_header(id_token)?;
+
+        let keys: JwkSet = self
+            .client
+            .get(&provider.jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let key = match &header.kid {
+            Some(kid) => keys.find(kid),
+            None if keys.keys.len() == 1 => keys.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| anyhow!("The ID token was signed with an unknown key."))?;
+
+        // The algorithm comes from the provider's key, never from the token,
+        // which could otherwise pick a weaker algorithm than the key is for.
+        let algorithm = key_algorithm(key)?;
+
+        if header.alg != algorithm {
+            return Err(anyhow!(
+                "The ID token was signed with {:?}, but the key is for {:?}.",
+                header.alg,
+                algorithm
+            ));
+        }
+
+        let mut validation = Validation::new(algorithm);
+        validation.set_audience(&[&self.client_id]);
+        validation.set_issuer(&[&provider.issuer]);
+
+        let claims =
+            decode::<IdTokenClaims>(id_token, &DecodingKey::from_jwk(key)?, &validation)?.claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(anyhow!("The ID token is not for this sign-in."));
+        }
+
+        // Accounts are linked by email, so an email the provider did not
+        // verify is not used.
+        let email = match claims.email_verified {
+            Some(true) => claims.email.map(|email| email.trim().to_lowercase()),
+            _ => None,
+        };
+
+        // Providers send groups as a list, or as a single group.
+        let groups = match claims.other.get(&self.groups_claim) {
+            Some(Value::Array(groups)) => groups
+                .iter()
+                .filter_map(|group| group.as_str().map(str::to_string))
+                .collect(),
+            Some(Value::String(group)) => vec![group.clone()],
+            _ => Vec::new(),
+        };
+
+        Ok(Identity {
+            issuer: self.issuer.clone(),
+            subject: claims.sub,
+            email,
+            groups,
+        })
+    }
+
+    /// The role of a user by their groups at the provider. Only staff sign in
+    /// this way, anyone else has no role.
+    pub fn role(&self, groups: &[String]) -> Option<Role> {
+        let in_any = |names: &[String]| groups.iter().any(|group| names.contains(group));
+
+        if in_any(&self.admin_groups) {
+            Some(Role::SystemAdmin)
+        } else if in_any(&self.staff_groups) {
+            Some(Role::Staff)
+        } else {
+            None
+        }
+    }
+}
+
+/// The signing algorithm of a provider key, by its `alg`, or by its type and
+/// curve when the provider leaves `alg` out. Symmetric keys are refused, an ID
+/// token must be signed with the provider's private key.
+fn key_algorithm(key: &Jwk) -> Result<Algorithm, Error> {
+    if let Some(key_algorithm) = key.common.key_algorithm {
+        return Algorithm::from_str(&key_algorithm.to_string())
+            .map_err(|_| anyhow!("The key is for {}, not for signing.", key_algorithm));
+    }
+
+    match &key.algorithm {
+        AlgorithmParameters::RSA(_) => Ok(Algorithm::RS256),
+        AlgorithmParameters::EllipticCurve(parameters) => match parameters.curve {
+            EllipticCurve::P256 => Ok(Algorithm::ES256),
+            EllipticCurve::P384 => Ok(Algorithm::ES384),
+            _ => Err(anyhow!("The key uses an unsupported curve.")),
+        },
+        AlgorithmParameters::OctetKeyPair(_) => Ok(Algorithm::EdDSA),
+        AlgorithmParameters::OctetKey(_) => Err(anyhow!("The key is not a public key.")),
+    }
+}
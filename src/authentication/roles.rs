@@ -32,3 +32,41 @@ impl Role {
         }
     }
 }
+
+/// The role of a member within their business.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub enum BusinessRole {
+    Owner,
+    Manager,
+    Cashier,
+    ReadOnly,
+}
+
+impl fmt::Display for BusinessRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusinessRole::Owner => write!(f, "Owner"),
+            BusinessRole::Manager => write!(f, "Manager"),
+            BusinessRole::Cashier => write!(f, "Cashier"),
+            BusinessRole::ReadOnly => write!(f, "Read Only"),
+        }
+    }
+}
+
+impl From<&str> for BusinessRole {
+    fn from(role: &str) -> Self {
+        match role {
+            "Owner" => BusinessRole::Owner,
+            "Manager" => BusinessRole::Manager,
+            "Cashier" => BusinessRole::Cashier,
+            _ => BusinessRole::ReadOnly,
+        }
+    }
+}
+
+impl BusinessRole {
+    /// Owners and managers run the business: its members, branches and prices.
+    pub fn can_manage(&self) -> bool {
+        matches!(self, BusinessRole::Owner | BusinessRole::Manager)
+    }
+}
@@ -0,0 +1,19 @@
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// A random single-use token to hand out, for example in an invitation link.
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Tokens are only stored hashed, so a leaked table can not be used to redeem
+/// them.
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.trim().as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
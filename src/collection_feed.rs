@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use serde_json::json;
+use sqlx::{Pool, Postgres};
+use tokio::sync::{broadcast, Mutex};
+
+use crate::data::entities::{collection::Collection, collection_event::CollectionEvent};
+
+/// Fans collection changes out to the connected dashboards. Every change is
+/// also stored with an increasing id, so that a dashboard that reconnects can
+/// resume from the last event it received.
+///
+/// Events are stored and broadcast one at a time, so they are committed and
+/// sent in the order of their ids and a dashboard never skips one by resuming
+/// after a later id.
+#[derive(Clone)]
+pub struct CollectionFeed {
+    sender: broadcast::Sender<CollectionEvent>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl Default for CollectionFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollectionFeed {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(1024);
+
+        Self {
+            sender,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<CollectionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Stores and broadcasts a change. Failing to do so is logged rather than
+    /// returned, the change itself has already been made.
+    pub async fn publish(&self, pool: &Pool<Postgres>, event_type: &str, collection: &Collection) {
+        let _guard = self.lock.lock().await;
+
+        let event = sqlx::query_as!(
+            CollectionEvent,
+            r#"
+            INSERT INTO collection_event (event_type, collection_id, business_id, collector_id, collection)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+            event_type,
+            collection.id,
+            collection.business_id,
+            collection.collector_id,
+            json!(collection)
+        )
+        .fetch_one(pool)
+        .await;
+
+        match event {
+            // Nobody may be connected, which is fine.
+            Ok(event) => {
+                let _ = self.sender.send(event);
+            }
+            Err(error) => tracing::error!("🔥 Failed to store {} event: {}", event_type, error),
+        }
+    }
+}
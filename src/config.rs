@@ -10,6 +10,33 @@ pub struct Config {
     pub admin_password: String,
     pub mfa_issuer: String,
     pub mfa_128_bit_secret: String,
+    pub reports_directory: String,
+    pub idempotency_key_ttl_hours: i64,
+    pub storage_backend: String,
+    pub storage_directory: String,
+    pub s3_endpoint: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_region: String,
+    pub s3_access_key: Option<String>,
+    pub s3_secret_key: Option<String>,
+    pub mail_backend: String,
+    pub mail_directory: String,
+    pub mail_from: String,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub app_url: String,
+    pub sms_backend: String,
+    pub sms_directory: String,
+    pub sms_gateway_url: Option<String>,
+    pub sms_gateway_token: Option<String>,
+    pub oidc_issuer: Option<String>,
+    pub oidc_client_id: Option<String>,
+    pub oidc_client_secret: Option<String>,
+    pub oidc_redirect_url: String,
+    pub oidc_scopes: String,
+    pub oidc_groups_claim: String,
+    pub oidc_admin_groups: Vec<String>,
+    pub oidc_staff_groups: Vec<String>,
 }
 
 impl Config {
@@ -99,6 +126,65 @@ impl Config {
         }
         .unwrap();
 
+        let reports_directory =
+            env::var("REPORTS_DIRECTORY").unwrap_or_else(|_| "./reports".to_string());
+
+        let idempotency_key_ttl_hours = env::var("IDEMPOTENCY_KEY_TTL_HOURS")
+            .ok()
+            .and_then(|hours| hours.parse().ok())
+            .unwrap_or(24);
+
+        let storage_backend = env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string());
+        let storage_directory =
+            env::var("STORAGE_DIRECTORY").unwrap_or_else(|_| "./storage".to_string());
+        let s3_endpoint = env::var("S3_ENDPOINT").ok();
+        let s3_bucket = env::var("S3_BUCKET").ok();
+        let s3_region = env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let s3_access_key = env::var("S3_ACCESS_KEY").ok();
+        let s3_secret_key = env::var("S3_SECRET_KEY").ok();
+
+        let mail_backend = env::var("MAIL_BACKEND").unwrap_or_else(|_| "file".to_string());
+        let mail_directory = env::var("MAIL_DIRECTORY").unwrap_or_else(|_| "./mail".to_string());
+        let mail_from =
+            env::var("MAIL_FROM").unwrap_or_else(|_| "3reco <no-reply@3reco.co.za>".to_string());
+        let smtp_host = env::var("SMTP_HOST").ok();
+        let smtp_port = env::var("SMTP_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(1025);
+        let app_url = env::var("APP_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        let sms_backend = env::var("SMS_BACKEND").unwrap_or_else(|_| "file".to_string());
+        let sms_directory = env::var("SMS_DIRECTORY").unwrap_or_else(|_| "./sms".to_string());
+        let sms_gateway_url = env::var("SMS_GATEWAY_URL").ok();
+        let sms_gateway_token = env::var("SMS_GATEWAY_TOKEN").ok();
+
+        // Single sign-on is off unless an issuer and client are configured.
+        let oidc_issuer = env::var("OIDC_ISSUER")
+            .ok()
+            .map(|issuer| issuer.trim_end_matches('/').to_string());
+        let oidc_client_id = env::var("OIDC_CLIENT_ID").ok();
+        let oidc_client_secret = env::var("OIDC_CLIENT_SECRET").ok();
+        let oidc_redirect_url = env::var("OIDC_REDIRECT_URL")
+            .unwrap_or_else(|_| format!("{}/oidc/callback", app_url));
+        let oidc_scopes =
+            env::var("OIDC_SCOPES").unwrap_or_else(|_| "openid email profile".to_string());
+        let oidc_groups_claim =
+            env::var("OIDC_GROUPS_CLAIM").unwrap_or_else(|_| "groups".to_string());
+        let groups = |name: &str| -> Vec<String> {
+            env::var(name)
+                .unwrap_or_default()
+                .split(',')
+                .map(|group| group.trim().to_string())
+                .filter(|group| !group.is_empty())
+                .collect()
+        };
+        let oidc_admin_groups = groups("OIDC_ADMIN_GROUPS");
+        let oidc_staff_groups = groups("OIDC_STAFF_GROUPS");
+
         Config {
             database_url,
             jwt_secret,
@@ -106,6 +192,33 @@ impl Config {
             admin_password,
             mfa_issuer,
             mfa_128_bit_secret,
+            reports_directory,
+            idempotency_key_ttl_hours,
+            storage_backend,
+            storage_directory,
+            s3_endpoint,
+            s3_bucket,
+            s3_region,
+            s3_access_key,
+            s3_secret_key,
+            mail_backend,
+            mail_directory,
+            mail_from,
+            smtp_host,
+            smtp_port,
+            app_url,
+            sms_backend,
+            sms_directory,
+            sms_gateway_url,
+            sms_gateway_token,
+            oidc_issuer,
+            oidc_client_id,
+            oidc_client_secret,
+            oidc_redirect_url,
+            oidc_scopes,
+            oidc_groups_claim,
+            oidc_admin_groups,
+            oidc_staff_groups,
         }
     }
 }
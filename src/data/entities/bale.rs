@@ -0,0 +1,20 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bale {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub product_id: Uuid,
+    /// The barcode on the bale.
+    pub code: String,
+    pub status: String,
+    /// The weighed bale, set when it is closed.
+    pub weight: Option<BigDecimal>,
+    pub sale_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub closed_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Branch {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub name: String,
+    pub phone_number: String,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Collections without a branch go to the default branch.
+    pub is_default: bool,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,12 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BranchPrice {
+    pub branch_id: Uuid,
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+    pub updated_at: NaiveDateTime,
+}
@@ -14,6 +14,8 @@ pub struct Business {
     pub city: String,
     pub state: String,
     pub zip_code: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
\ No newline at end of file
@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BusinessInvitation {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub branch_id: Option<Uuid>,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub invited_by: Uuid,
+    pub expires_at: NaiveDateTime,
+    pub accepted_by: Option<Uuid>,
+    pub accepted_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::authentication::roles::BusinessRole;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BusinessMember {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub user_id: Uuid,
+    pub role: String,
+    /// The branch the member works at, if any.
+    pub branch_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl BusinessMember {
+    pub fn role(&self) -> BusinessRole {
+        BusinessRole::from(self.role.as_str())
+    }
+}
@@ -10,6 +10,12 @@ pub struct Collection {
     pub collector_id: Uuid,
     pub product_id: Uuid,
     pub weight: BigDecimal,
+    pub paid_at: Option<NaiveDateTime>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub branch_id: Uuid,
+    /// The price per kilogram when the collection was captured.
+    pub price: BigDecimal,
 }
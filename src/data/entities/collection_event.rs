@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollectionEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub collection_id: Uuid,
+    pub business_id: Uuid,
+    pub collector_id: Uuid,
+    pub collection: Value,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A weighing from an imported scale export. Rows that could not be matched
+/// to a collector and product stay pending until they are resolved or
+/// discarded by hand.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollectionImportRow {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    /// Empty for weighings without a reference or time, which are never
+    /// duplicates.
+    pub row_hash: Option<String>,
+    pub source: String,
+    pub row_number: i32,
+    pub reference: Option<String>,
+    pub collector_reference: String,
+    pub product_reference: String,
+    pub weight: String,
+    pub weighed_at: Option<String>,
+    pub status: String,
+    pub reason: Option<String>,
+    pub collection_id: Option<Uuid>,
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,18 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CollectionPhoto {
+    pub id: Uuid,
+    pub collection_id: Uuid,
+    pub storage_key: String,
+    pub thumbnail_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: i32,
+    pub height: i32,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
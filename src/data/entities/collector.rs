@@ -17,6 +17,12 @@ pub struct Collector {
     pub bank_name: String,
     pub bank_account_holder: String,
     pub bank_account_number: String,
+    pub collector_code: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub review_business_id: Option<Uuid>,
+    pub rejection_reason: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<NaiveDateTime>,
 }
@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DirectoryListing {
+    pub business_id: Uuid,
+    pub listed: bool,
+    pub show_phone_number: bool,
+    pub show_address: bool,
+    pub show_operating_hours: bool,
+    pub show_prices: bool,
+    pub contact_email: Option<String>,
+    pub website: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,24 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// A snapshot of an EPR tonnage report. The rows and the rendered csv are
+/// stored as they were generated so that a submitted report can always be
+/// reproduced exactly, regardless of later changes to collections.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EprReport {
+    pub id: Uuid,
+    pub year: i32,
+    pub quarter: i32,
+    pub period_start: NaiveDateTime,
+    pub period_end: NaiveDateTime,
+    pub rows: Value,
+    #[serde(skip_serializing)]
+    pub csv: String,
+    pub checksum: String,
+    pub generated_by: Option<Uuid>,
+    pub submitted_by: Option<Uuid>,
+    pub submitted_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+}
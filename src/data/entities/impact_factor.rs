@@ -0,0 +1,17 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Conversion factors used to turn a collected weight of a product into
+/// environmental impact figures. All factors are expressed per kilogram.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImpactFactor {
+    pub id: Uuid,
+    pub product_id: Uuid,
+    pub co2e_per_kg: BigDecimal,
+    pub landfill_m3_per_kg: BigDecimal,
+    pub energy_kwh_per_kg: BigDecimal,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
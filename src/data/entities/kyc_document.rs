@@ -0,0 +1,23 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KycDocument {
+    pub id: Uuid,
+    pub collector_id: Option<Uuid>,
+    pub business_id: Option<Uuid>,
+    pub document_type: String,
+    pub storage_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub expires_on: Option<NaiveDate>,
+    pub status: String,
+    pub rejection_reason: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,21 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MaterialSale {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub offtaker_id: Uuid,
+    pub product_id: Uuid,
+    pub weight: BigDecimal,
+    pub price_per_kg: BigDecimal,
+    pub amount: Option<BigDecimal>,
+    pub weighbridge_ticket: String,
+    pub invoice_number: String,
+    pub stock_movement_id: Option<Uuid>,
+    pub sold_at: NaiveDateTime,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+}
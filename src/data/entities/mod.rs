@@ -3,3 +3,34 @@ pub mod product;
 pub mod collection;
 pub mod business;
 pub mod collector;
+pub mod impact_factor;
+pub mod epr_stream;
+pub mod epr_report;
+pub mod report_job;
+pub mod report_job_run;
+pub mod collection_import_row;
+pub mod scale;
+pub mod webhook_subscription;
+pub mod webhook_delivery;
+pub mod collection_event;
+pub mod sync_tombstone;
+pub mod collection_photo;
+pub mod kyc_document;
+pub mod directory_listing;
+pub mod operating_hours;
+pub mod vehicle;
+pub mod driver;
+pub mod pickup_request;
+pub mod stock_movement;
+pub mod offtaker;
+pub mod material_sale;
+pub mod bale;
+pub mod branch;
+pub mod branch_price;
+pub mod business_invitation;
+pub mod business_member;
+pub mod user_activation;
+pub mod phone_verification;
+pub mod oidc_login;
+pub mod user_identity;
+pub mod api_key;
@@ -0,0 +1,18 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Offtaker {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub name: String,
+    pub contact_name: String,
+    pub email: String,
+    pub phone_number: String,
+    pub address: String,
+    pub registration_number: String,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
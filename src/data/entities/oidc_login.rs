@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OidcLogin {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub state_hash: String,
+    #[serde(skip_serializing)]
+    pub code_verifier: String,
+    #[serde(skip_serializing)]
+    pub nonce: String,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,13 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OperatingHours {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    /// 1 is Monday and 7 is Sunday.
+    pub day_of_week: i16,
+    pub opens_at: NaiveTime,
+    pub closes_at: NaiveTime,
+}
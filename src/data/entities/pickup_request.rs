@@ -0,0 +1,31 @@
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PickupRequest {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub collector_id: Uuid,
+    pub product_id: Uuid,
+    pub estimated_weight: BigDecimal,
+    pub address: String,
+    pub city: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub preferred_date: NaiveDate,
+    pub window_start: Option<NaiveTime>,
+    pub window_end: Option<NaiveTime>,
+    pub notes: String,
+    pub status: String,
+    pub driver_id: Option<Uuid>,
+    pub vehicle_id: Option<Uuid>,
+    pub scheduled_date: Option<NaiveDate>,
+    pub stop_order: Option<i32>,
+    pub collection_id: Option<Uuid>,
+    pub completed_at: Option<NaiveDateTime>,
+    pub created_by: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
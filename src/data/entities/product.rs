@@ -10,6 +10,7 @@ pub struct Product {
     pub name: String,
     pub description: String,
     pub price: BigDecimal,
+    pub code: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
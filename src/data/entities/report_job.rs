@@ -0,0 +1,18 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportJob {
+    pub id: Uuid,
+    pub name: String,
+    pub report_type: String,
+    pub business_id: Option<Uuid>,
+    pub cron_expression: String,
+    pub utc_offset_minutes: i32,
+    pub active: bool,
+    pub created_by: Option<Uuid>,
+    pub last_run_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
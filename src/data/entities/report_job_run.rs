@@ -0,0 +1,19 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReportJobRun {
+    pub id: Uuid,
+    pub report_job_id: Uuid,
+    pub status: String,
+    #[serde(skip_serializing)]
+    pub output_path: Option<String>,
+    pub output_size: Option<i64>,
+    pub error: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+    /// Collections captured from here until `period_end` are in the report.
+    pub period_start: Option<NaiveDateTime>,
+    pub period_end: Option<NaiveDateTime>,
+}
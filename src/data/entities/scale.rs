@@ -0,0 +1,16 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Scale {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub name: String,
+    pub connection_type: String,
+    pub host: Option<String>,
+    pub port: Option<i32>,
+    pub active: bool,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,22 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StockMovement {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub product_id: Uuid,
+    pub movement_type: String,
+    /// Positive into stock, negative out of stock.
+    pub quantity: BigDecimal,
+    pub collection_id: Option<Uuid>,
+    /// The other side of a baling or transfer.
+    pub linked_movement_id: Option<Uuid>,
+    pub reference: String,
+    pub notes: String,
+    pub created_by: Option<Uuid>,
+    pub occurred_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncTombstone {
+    pub id: i64,
+    pub entity: String,
+    pub record_id: Uuid,
+    pub business_id: Option<Uuid>,
+    pub deleted_at: NaiveDateTime,
+    /// The branch of a deleted branch price, whose record is the product.
+    pub branch_id: Option<Uuid>,
+}
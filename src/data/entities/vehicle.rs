@@ -0,0 +1,16 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vehicle {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub registration: String,
+    pub description: String,
+    pub capacity_kg: Option<BigDecimal>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// The subscribed event types, all events when empty.
+    pub event_types: Vec<String>,
+    /// Only events of this business are delivered, all events when empty.
+    pub business_id: Option<Uuid>,
+    pub active: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
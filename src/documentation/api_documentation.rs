@@ -2,7 +2,11 @@ use utoipa::OpenApi;
 
 use crate::{
     documentation::api_security_addon::SecurityAddon,
-    routes::{authentication, business, collection, collector, export, product, users},
+    routes::{
+        api_key, authentication, bale, branch, business, collection, collector, epr, epr_stream,
+        export, impact, impact_factor, import, kyc, pickup, product, report_job, sale, scale,
+        statement, stock, sync, users, webhook,
+    },
 };
 
 #[derive(OpenApi)]
@@ -32,6 +36,12 @@ use crate::{
         business::add::business,
         business::update::business,
         business::delete::business,
+        business::location::nearest,
+        business::location::geojson,
+        business::listing::listing,
+        business::listing::update,
+        business::directory::directory,
+        business::directory::entry,
         collector::view::collectors,
         collector::view::collector,
         collector::add::collector,
@@ -48,7 +58,145 @@ use crate::{
         collection::add::collection,
         collection::update::collection,
         collection::delete::collection,
-        export::business::business
+        collection::paid::paid,
+        collection::feed::feed,
+        collection::photo::photos,
+        collection::photo::upload,
+        collection::photo::delete,
+        collection::photo::download,
+        export::business::business,
+        impact::collection::collection,
+        impact::collector::collector,
+        impact::business::business,
+        impact::period::period,
+        impact_factor::view::impact_factors,
+        impact_factor::view::impact_factor,
+        impact_factor::add::impact_factor,
+        impact_factor::update::impact_factor,
+        impact_factor::delete::impact_factor,
+        epr_stream::view::epr_streams,
+        epr_stream::view::epr_stream,
+        epr_stream::add::epr_stream,
+        epr_stream::update::epr_stream,
+        epr_stream::delete::epr_stream,
+        epr::product::products,
+        epr::product::assign,
+        epr::product::unassign,
+        epr::view::epr_reports,
+        epr::view::epr_report,
+        epr::preview::preview,
+        epr::generate::generate,
+        epr::export::export,
+        epr::submit::submit,
+        epr::delete::epr_report,
+        statement::collector::collector,
+        statement::collector::me,
+        statement::bulk::bulk,
+        report_job::view::report_jobs,
+        report_job::view::report_job,
+        report_job::view::report_job_runs,
+        report_job::add::report_job,
+        report_job::update::report_job,
+        report_job::delete::report_job,
+        report_job::run::run,
+        report_job::download::download,
+        import::collector::collector,
+        import::business::business,
+        import::collection::collection,
+        import::staged::staged,
+        import::staged::resolve,
+        import::staged::discard,
+        scale::view::scales,
+        scale::view::scale,
+        scale::add::scale,
+        scale::update::scale,
+        scale::delete::scale,
+        scale::reading::reading,
+        scale::reading::feed,
+        webhook::view::webhooks,
+        webhook::view::webhook,
+        webhook::add::webhook,
+        webhook::update::webhook,
+        webhook::delete::webhook,
+        webhook::delivery::deliveries,
+        webhook::delivery::delivery,
+        webhook::delivery::retry,
+        api_key::view::api_keys,
+        api_key::view::api_key,
+        api_key::add::api_key,
+        api_key::delete::api_key,
+        sync::apply::sync,
+        kyc::view::documents,
+        kyc::view::collector_documents,
+        kyc::view::business_documents,
+        kyc::view::download,
+        kyc::add::collector_document,
+        kyc::add::business_document,
+        kyc::review::review,
+        kyc::delete::document,
+        pickup::view::pickups,
+        pickup::view::pickup,
+        pickup::add::pickup,
+        pickup::update::assign,
+        pickup::update::cancel,
+        pickup::complete::complete,
+        pickup::route::route,
+        pickup::vehicle::vehicles,
+        pickup::vehicle::add,
+        pickup::vehicle::update,
+        pickup::driver::drivers,
+        pickup::driver::add,
+        pickup::driver::update,
+        stock::view::stock,
+        stock::view::movements,
+        stock::movement::record,
+        stock::balance::balance,
+        sale::view::sales,
+        sale::add::sale,
+        sale::delete::sale,
+        sale::margin::margin,
+        sale::offtaker::offtakers,
+        sale::offtaker::add,
+        sale::offtaker::update,
+        bale::view::bales,
+        bale::view::bale,
+        bale::view::code,
+        bale::add::bale,
+        bale::update::add,
+        bale::update::remove,
+        bale::update::close,
+        bale::update::sale,
+        bale::trace::collection,
+        bale::trace::sale,
+        branch::view::branches,
+        branch::view::branch,
+        branch::add::branch,
+        branch::update::branch,
+        branch::price::prices,
+        branch::price::set,
+        branch::price::remove,
+        branch::staff::staff,
+        branch::staff::add,
+        branch::staff::remove,
+        branch::report::report,
+        business::member::members,
+        business::member::update,
+        business::member::remove,
+        business::invitation::invite,
+        business::invitation::invitations,
+        business::invitation::revoke,
+        business::invitation::resend,
+        authentication::invitation::accept,
+        authentication::activate::user,
+        authentication::oidc::authorize,
+        authentication::oidc::callback,
+        users::invitation::invite,
+        users::invitation::invitations,
+        users::invitation::resend,
+        collector::registration::code,
+        collector::registration::register,
+        collector::registration::registrations,
+        collector::registration::review
     ),
     components(
         schemas(
@@ -58,12 +206,60 @@ use crate::{
             users::update::UpdateUserPayload,
             business::add::AddBusinessPayload,
             business::update::UpdateBusinessPayload,
+            business::listing::UpdateListingPayload,
+            business::listing::OperatingHoursPayload,
             collector::add::AddCollectorPayload,
             collector::update::UpdateCollectorPayload,
             product::add::AddProductPayload,
             product::update::UpdateProductPayload,
             collection::add::AddCollectionPayload,
             collection::update::UpdateCollectionPayload,
+            impact_factor::add::AddImpactFactorPayload,
+            impact_factor::update::UpdateImpactFactorPayload,
+            epr_stream::add::AddEprStreamPayload,
+            epr_stream::update::UpdateEprStreamPayload,
+            epr::product::AssignEprStreamPayload,
+            report_job::add::AddReportJobPayload,
+            report_job::update::UpdateReportJobPayload,
+            crate::scheduler::report_type::ReportType,
+            import::staged::ResolveStagedPayload,
+            scale::add::AddScalePayload,
+            scale::update::UpdateScalePayload,
+            webhook::add::AddWebhookPayload,
+            webhook::update::UpdateWebhookPayload,
+            api_key::add::AddApiKeyPayload,
+            sync::apply::SyncPayload,
+            sync::apply::SyncCollectionPayload,
+            kyc::review::ReviewDocumentPayload,
+            pickup::add::AddPickupPayload,
+            pickup::update::AssignPickupPayload,
+            pickup::complete::CompletePickupPayload,
+            pickup::vehicle::AddVehiclePayload,
+            pickup::vehicle::UpdateVehiclePayload,
+            pickup::driver::AddDriverPayload,
+            pickup::driver::UpdateDriverPayload,
+            stock::movement::RecordMovementPayload,
+            sale::add::AddSalePayload,
+            sale::offtaker::AddOfftakerPayload,
+            sale::offtaker::UpdateOfftakerPayload,
+            bale::add::AddBalePayload,
+            bale::update::BaleCollectionsPayload,
+            bale::update::CloseBalePayload,
+            bale::update::BaleSalePayload,
+            branch::add::AddBranchPayload,
+            branch::update::UpdateBranchPayload,
+            branch::price::SetBranchPricePayload,
+            branch::staff::AddBranchStaffPayload,
+            business::member::UpdateMemberPayload,
+            business::invitation::InviteMemberPayload,
+            authentication::invitation::AcceptInvitationPayload,
+            authentication::activate::ActivatePayload,
+            authentication::oidc::CallbackPayload,
+            users::invitation::InviteUserPayload,
+            collector::registration::RequestCodePayload,
+            collector::registration::RegisterCollectorPayload,
+            collector::registration::ReviewRegistrationPayload,
+            crate::authentication::roles::BusinessRole,
         )
     ),
     modifiers(&SecurityAddon),
@@ -72,6 +268,22 @@ use crate::{
         (name = "Business", description = "Business routes."),
         (name = "Collector", description = "Collector routes."),
         (name = "Collection", description = "Collection routes."),
+        (name = "Scale", description = "Scale routes."),
+        (name = "Impact", description = "Environmental impact routes."),
+        (name = "EPR", description = "Extended Producer Responsibility reporting routes."),
+        (name = "Statement", description = "Collector earnings statement routes."),
+        (name = "Report", description = "Scheduled report job routes."),
+        (name = "Import", description = "Bulk import routes."),
+        (name = "Webhook", description = "Outbound webhook routes."),
+        (name = "API Key", description = "API keys for machine clients and integrations."),
+        (name = "Sync", description = "Offline capture sync routes."),
+        (name = "KYC", description = "Collector and business document routes."),
+        (name = "Directory", description = "Public buy-back centre directory routes."),
+        (name = "Pickup", description = "Household pickup and route planning routes."),
+        (name = "Stock", description = "Material stock ledger routes."),
+        (name = "Sale", description = "Material sales to off-taker routes."),
+        (name = "Bale", description = "Bale and chain-of-custody traceability routes."),
+        (name = "Branch", description = "Business branch routes."),
         (name = "Product", description = "Product routes."),
         (name = "Users", description = "Users routes."),
     ),
@@ -0,0 +1,170 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+/// The length of one degree of latitude, used to narrow searches by radius.
+pub const KM_PER_DEGREE_LATITUDE: f64 = 111.2;
+/// Bounds the 2-opt improvement of routes with many stops.
+const MAX_TWO_OPT_PASSES: usize = 50;
+
+/// The great-circle distance between two coordinates, in kilometres.
+pub fn haversine_km(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (from_latitude, from_longitude) = (from.0.to_radians(), from.1.to_radians());
+    let (to_latitude, to_longitude) = (to.0.to_radians(), to.1.to_radians());
+
+    let a = ((to_latitude - from_latitude) / 2.0).sin().powi(2)
+        + from_latitude.cos()
+            * to_latitude.cos()
+            * ((to_longitude - from_longitude) / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Orders stops into a short route from the depot, returning the indexes of
+/// the stops in visiting order. The route starts with the nearest neighbour at
+/// every step and is then improved with 2-opt, reversing any part of the route
+/// that makes it shorter, until no reversal helps. The route ends at the last
+/// stop rather than returning to the depot.
+pub fn plan_route(depot: (f64, f64), stops: &[(f64, f64)]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..stops.len()).collect();
+    let mut order = Vec::with_capacity(stops.len());
+    let mut position = depot;
+
+    while !remaining.is_empty() {
+        let nearest = (0..remaining.len())
+            .min_by(|a, b| {
+                haversine_km(position, stops[remaining[*a]])
+                    .total_cmp(&haversine_km(position, stops[remaining[*b]]))
+            })
+            .unwrap_or_default();
+
+        let stop = remaining.swap_remove(nearest);
+        position = stops[stop];
+        order.push(stop);
+    }
+
+    // The route as points, the depot first.
+    let point = |order: &[usize], index: usize| {
+        if index == 0 {
+            depot
+        } else {
+            stops[order[index - 1]]
+        }
+    };
+
+    let length = order.len();
+
+    for _ in 0..MAX_TWO_OPT_PASSES {
+        let mut improved = false;
+
+        for i in 1..length {
+            for j in (i + 1)..=length {
+                let mut before = haversine_km(point(&order, i - 1), point(&order, i));
+                let mut after = haversine_km(point(&order, i - 1), point(&order, j));
+
+                if j < length {
+                    before += haversine_km(point(&order, j), point(&order, j + 1));
+                    after += haversine_km(point(&order, i), point(&order, j + 1));
+                }
+
+                if after + 1e-9 < before {
+                    order[(i - 1)..j].reverse();
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    order
+}
+
+/// Checks that a latitude and longitude are given together and are in range.
+pub fn validate_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let reason = match (latitude, longitude) {
+        (None, None) => return Ok(()),
+        (Some(latitude), Some(longitude)) => {
+            if !(-90.0..=90.0).contains(&latitude) {
+                "The latitude must be between -90 and 90."
+            } else if !(-180.0..=180.0).contains(&longitude) {
+                "The longitude must be between -180 and 180."
+            } else {
+                return Ok(());
+            }
+        }
+        _ => "The latitude and longitude must be given together.",
+    };
+
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Bad Request",
+            "reason": reason
+        })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route_km(depot: (f64, f64), stops: &[(f64, f64)], order: &[usize]) -> f64 {
+        let mut position = depot;
+        let mut length = 0.0;
+
+        for stop in order {
+            length += haversine_km(position, stops[*stop]);
+            position = stops[*stop];
+        }
+
+        length
+    }
+
+    #[test]
+    fn measures_known_distances() {
+        assert_eq!(haversine_km((52.52, 13.405), (52.52, 13.405)), 0.0);
+        // One degree along a meridian.
+        assert!((haversine_km((0.0, 0.0), (1.0, 0.0)) - 111.195).abs() < 0.01);
+        // A quarter of the equator.
+        assert!((haversine_km((0.0, 0.0), (0.0, 90.0)) - 10_007.543).abs() < 0.01);
+    }
+
+    #[test]
+    fn plans_no_stops() {
+        assert!(plan_route((0.0, 0.0), &[]).is_empty());
+    }
+
+    #[test]
+    fn plans_a_single_stop() {
+        assert_eq!(plan_route((0.0, 0.0), &[(1.0, 1.0)]), vec![0]);
+    }
+
+    #[test]
+    fn visits_the_nearest_stop_first() {
+        let stops = [(0.0, 0.03), (0.0, 0.01), (0.0, 0.02)];
+
+        assert_eq!(plan_route((0.0, 0.0), &stops), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn uncrosses_the_nearest_neighbour_route() {
+        let depot = (0.0, 0.0);
+        let stops = [(0.03, 0.0), (0.0, 0.02), (0.01, 0.01), (0.01, 0.02)];
+        // The nearest neighbour goes 2, 3, 1 and then back across the route
+        // to 0.
+        let nearest_neighbour = [2, 3, 1, 0];
+
+        let order = plan_route(depot, &stops);
+
+        assert_eq!(order, vec![1, 3, 2, 0]);
+        assert!(
+            route_km(depot, &stops, &order) + 0.5 < route_km(depot, &stops, &nearest_neighbour)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{self, Request},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{data::entities::user::User, AppState};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Keeps a response out of the idempotency store. Handlers add it as a
+/// response extension when the response carries a secret that is only ever
+/// returned once, such as a new API key. A retry runs the handler again.
+#[derive(Clone, Copy, Debug)]
+pub struct NoStore;
+
+/// Matches the body limit of the router.
+const MAX_BODY_SIZE: usize = 100_000_000;
+const MAX_KEY_LENGTH: usize = 255;
+/// How long a request may run before its key counts as abandoned. Handlers
+/// stop when the client goes away, which leaves the key claimed without a
+/// response.
+const CLAIM_TIMEOUT_SECONDS: f64 = 300.0;
+
+/// Makes mutating requests with an `Idempotency-Key` header safe to retry.
+///
+/// The first request with a key runs as usual and its response is stored for
+/// the configured window. A retry with the same key and payload gets the stored
+/// response back without running the handler again, while a retry with another
+/// payload, or one that arrives while the first is still running, is refused.
+/// Server errors are not stored, so the request can be retried with the same
+/// key, and neither are responses marked with `NoStore`. A key whose request
+/// has not finished within `CLAIM_TIMEOUT_SECONDS` is taken up again by a
+/// retry with the same payload. Keys are scoped per user, so this runs after
+/// `jwt_guard`.
+pub async fn idempotency_guard(
+    extract::State(app_state): extract::State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+
+    let key = match request.headers().get(IDEMPOTENCY_KEY) {
+        Some(key) if mutating => key.to_str().map(str::to_string).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "The Idempotency-Key header must be text."
+                })),
+            )
+        })?,
+        _ => return Ok(next.run(request).await),
+    };
+
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("The Idempotency-Key header must be 1 to {} characters.", MAX_KEY_LENGTH)
+            })),
+        ));
+    }
+
+    let user_id = match request.extensions().get::<User>() {
+        Some(user) => user.id,
+        None => return Ok(next.run(request).await),
+    };
+
+    let (parts, body) = request.into_parts();
+
+    let body = to_bytes(body, MAX_BODY_SIZE).await.map_err(|_| {
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({
+                "error": "Payload Too Large",
+                "reason": "The request body is too large."
+            })),
+        )
+    })?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(parts.uri.to_string());
+    hasher.update(&body);
+    let request_hash: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    sqlx::query!(
+        r#"
+        DELETE FROM idempotency_key WHERE user_id = $1 AND expires_at < CURRENT_TIMESTAMP
+        "#,
+        user_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let claimed = sqlx::query_scalar!(
+        r#"
+        INSERT INTO idempotency_key (user_id, key, request_hash, expires_at)
+        VALUES ($1, $2, $3, CURRENT_TIMESTAMP + MAKE_INTERVAL(hours => $4))
+        ON CONFLICT (user_id, key) DO UPDATE
+        SET claimed_at = CURRENT_TIMESTAMP, expires_at = EXCLUDED.expires_at
+        WHERE idempotency_key.status_code IS NULL
+            AND idempotency_key.request_hash = EXCLUDED.request_hash
+            AND idempotency_key.claimed_at < CURRENT_TIMESTAMP - MAKE_INTERVAL(secs => $5)
+        RETURNING id
+        "#,
+        user_id,
+        key,
+        request_hash,
+        app_state.config.idempotency_key_ttl_hours as i32,
+        CLAIM_TIMEOUT_SECONDS
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let claimed_id = match claimed {
+        Some(claimed_id) => claimed_id,
+        None => {
+            let stored = sqlx::query!(
+                r#"
+                SELECT request_hash, status_code, content_type, response_body
+                FROM idempotency_key
+                WHERE user_id = $1 AND key = $2
+                "#,
+                user_id,
+                key
+            )
+            .fetch_one(&app_state.pool)
+            .await
+            .map_err(database_error)?;
+
+            if stored.request_hash != request_hash {
+                return Err((
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "Conflict",
+                        "reason": "The Idempotency-Key was already used for a different request."
+                    })),
+                ));
+            }
+
+            return match (stored.status_code, stored.response_body) {
+                (Some(status_code), Some(response_body)) => {
+                    let status = StatusCode::from_u16(status_code as u16)
+                        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                    let mut response = (status, response_body).into_response();
+
+                    if let Some(content_type) = stored
+                        .content_type
+                        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+                    {
+                        response
+                            .headers_mut()
+                            .insert(header::CONTENT_TYPE, content_type);
+                    }
+
+                    response
+                        .headers_mut()
+                        .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+
+                    Ok(response)
+                }
+                _ => Err((
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "Conflict",
+                        "reason": "A request with this Idempotency-Key is still being processed."
+                    })),
+                )),
+            };
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let (parts, body) = response.into_parts();
+
+    let body = match to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(error) => {
+            tracing::error!("🔥 Failed to read response for idempotency key: {}", error);
+            release(&app_state, claimed_id).await;
+
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to read response."
+                })),
+            ));
+        }
+    };
+
+    if parts.status.is_server_error() || parts.extensions.get::<NoStore>().is_some() {
+        release(&app_state, claimed_id).await;
+    } else {
+        let content_type = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok());
+
+        let stored = sqlx::query!(
+            r#"
+            UPDATE idempotency_key
+            SET status_code = $1, content_type = $2, response_body = $3
+            WHERE id = $4
+            "#,
+            parts.status.as_u16() as i32,
+            content_type,
+            body.as_ref(),
+            claimed_id
+        )
+        .execute(&app_state.pool)
+        .await;
+
+        if let Err(error) = stored {
+            tracing::error!("🔥 Failed to store idempotent response: {}", error);
+            release(&app_state, claimed_id).await;
+        }
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+/// Frees a key whose request failed or whose response is not stored, so that
+/// it can be retried.
+async fn release(app_state: &AppState, claimed_id: Uuid) {
+    let released = sqlx::query!(
+        r#"
+        DELETE FROM idempotency_key WHERE id = $1
+        "#,
+        claimed_id
+    )
+    .execute(&app_state.pool)
+    .await;
+
+    if let Err(error) = released {
+        tracing::error!("🔥 Failed to release idempotency key: {}", error);
+    }
+}
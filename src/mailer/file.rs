@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use anyhow::Error;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::config::Config;
+
+use super::Email;
+
+#[derive(Clone)]
+pub struct FileMailer {
+    directory: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            directory: PathBuf::from(&config.mail_directory),
+            from: config.mail_from.clone(),
+        }
+    }
+
+    pub async fn send(&self, email: &Email) -> Result<(), Error> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+
+        let path = self.directory.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%d%H%M%S"),
+            Uuid::new_v4()
+        ));
+
+        tokio::fs::write(&path, email.to_message(&self.from)).await?;
+
+        tracing::info!("📧 Wrote email to {} to {}", email.to, path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+
+use crate::config::Config;
+
+use self::{file::FileMailer, smtp::SmtpMailer};
+
+pub mod file;
+pub mod smtp;
+
+/// A plain text email.
+#[derive(Debug, Clone)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Email {
+    /// The message as sent over SMTP or written to a file.
+    pub fn to_message(&self, from: &str) -> String {
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n",
+            from,
+            self.to,
+            self.subject,
+            Utc::now().to_rfc2822(),
+            self.body.lines().collect::<Vec<&str>>().join("\r\n")
+        )
+    }
+}
+
+/// How emails are sent. The file backend is the default and writes each email
+/// to the mail directory, the SMTP backend hands them to a relay such as
+/// MailHog or the local MTA.
+#[derive(Clone)]
+pub enum Mailer {
+    File(FileMailer),
+    Smtp(SmtpMailer),
+}
+
+impl Mailer {
+    pub fn init(config: &Config) -> Result<Self, Error> {
+        match config.mail_backend.as_str() {
+            "file" => Ok(Mailer::File(FileMailer::new(config))),
+            "smtp" => Ok(Mailer::Smtp(SmtpMailer::new(config)?)),
+            backend => Err(anyhow!("Unknown mail backend \"{}\".", backend)),
+        }
+    }
+
+    pub async fn send(&self, email: &Email) -> Result<(), Error> {
+        if email.to.contains(['\r', '\n']) || email.subject.contains(['\r', '\n']) {
+            return Err(anyhow!("Email headers can not contain line breaks."));
+        }
+
+        match self {
+            Mailer::File(mailer) => mailer.send(email).await,
+            Mailer::Smtp(mailer) => mailer.send(email).await,
+        }
+    }
+}
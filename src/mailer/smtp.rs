@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Error};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+};
+
+use crate::config::Config;
+
+use super::Email;
+
+/// Sends plain SMTP without TLS or authentication, for a relay on the same
+/// host or network such as MailHog or the local MTA.
+#[derive(Clone)]
+pub struct SmtpMailer {
+    host: String,
+    port: u16,
+    from: String,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let host = config
+            .smtp_host
+            .clone()
+            .ok_or_else(|| anyhow!("SMTP_HOST is required for the smtp mail backend."))?;
+
+        Ok(Self {
+            host,
+            port: config.smtp_port,
+            from: config.mail_from.clone(),
+        })
+    }
+
+    pub async fn send(&self, email: &Email) -> Result<(), Error> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+
+        expect(&mut reader, 220).await?;
+
+        let commands = [
+            ("EHLO localhost".to_string(), 250),
+            (format!("MAIL FROM:<{}>", address(&self.from)), 250),
+            (format!("RCPT TO:<{}>", address(&email.to)), 250),
+            ("DATA".to_string(), 354),
+        ];
+
+        for (command, code) in commands {
+            writer
+                .write_all(format!("{}\r\n", command).as_bytes())
+                .await?;
+            expect(&mut reader, code).await?;
+        }
+
+        // A line with a single dot ends the data, so dots starting a line are
+        // doubled.
+        let message = email
+            .to_message(&self.from)
+            .split("\r\n")
+            .map(|line| match line.starts_with('.') {
+                true => format!(".{}", line),
+                false => line.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\r\n");
+
+        writer
+            .write_all(format!("{}.\r\n", message).as_bytes())
+            .await?;
+        expect(&mut reader, 250).await?;
+
+        writer.write_all(b"QUIT\r\n").await?;
+
+        Ok(())
+    }
+}
+
+/// The bare address of "Name <address>".
+fn address(mailbox: &str) -> &str {
+    match (mailbox.find('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+/// Reads a reply, including the lines of a multi-line reply, and checks its
+/// code.
+async fn expect<R>(reader: &mut R, code: u16) -> Result<(), Error>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line).await? == 0 {
+            return Err(anyhow!("The SMTP server closed the connection."));
+        }
+
+        let reply_code: u16 = line
+            .get(..3)
+            .and_then(|reply_code| reply_code.parse().ok())
+            .ok_or_else(|| anyhow!("Invalid SMTP reply \"{}\".", line.trim_end()))?;
+
+        if reply_code != code {
+            return Err(anyhow!("Unexpected SMTP reply \"{}\".", line.trim_end()));
+        }
+
+        if line.as_bytes().get(3) != Some(&b'-') {
+            return Ok(());
+        }
+    }
+}
@@ -1,32 +1,57 @@
 use std::net::SocketAddr;
 
 use anyhow::{Error, Result};
+use authentication::oidc::Oidc;
 use axum::{
     extract::DefaultBodyLimit,
     http::{header, HeaderValue, Method},
     Router,
 };
 use bcrypt::hash;
+use collection_feed::CollectionFeed;
 use config::Config;
+use mailer::Mailer;
 use router::create_router;
+use scales::Scales;
+use scheduler::Scheduler;
+use sms::Sms;
 use sqlx::{postgres::PgPoolOptions, Pool, Postgres};
+use storage::Storage;
 use tokio::net::TcpListener;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing_appender::rolling;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 pub mod authentication;
+pub mod collection_feed;
 pub mod config;
 pub mod data;
 pub mod documentation;
+pub mod geo;
+pub mod idempotency;
+pub mod mailer;
+pub mod network;
+pub mod pdf;
 pub mod router;
 pub mod routes;
+pub mod scales;
+pub mod scheduler;
+pub mod sms;
+pub mod storage;
 pub mod utilities;
+pub mod webhooks;
 
 #[derive(Clone)]
 pub struct AppState {
     pub config: Config,
     pub pool: Pool<Postgres>,
+    pub scheduler: Scheduler,
+    pub scales: Scales,
+    pub collection_feed: CollectionFeed,
+    pub storage: Storage,
+    pub mailer: Mailer,
+    pub sms: Sms,
+    pub oidc: Option<Oidc>,
 }
 
 #[tokio::main]
@@ -153,9 +178,58 @@ pub async fn main() -> Result<(), Error> {
         }
     }
 
+    let scheduler = match Scheduler::init(pool.clone(), &config).await {
+        Ok(scheduler) => scheduler,
+        Err(error) => {
+            tracing::error!("🔥 Failed to start scheduler: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let scales = match Scales::init(&pool).await {
+        Ok(scales) => scales,
+        Err(error) => {
+            tracing::error!("🔥 Failed to start scale listeners: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let storage = match Storage::init(&config) {
+        Ok(storage) => storage,
+        Err(error) => {
+            tracing::error!("🔥 Failed to set up file storage: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let mailer = match Mailer::init(&config) {
+        Ok(mailer) => mailer,
+        Err(error) => {
+            tracing::error!("🔥 Failed to set up the mailer: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let sms = match Sms::init(&config) {
+        Ok(sms) => sms,
+        Err(error) => {
+            tracing::error!("🔥 Failed to set up text messages: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    webhooks::worker::start(pool.clone());
+
     let app_state = AppState {
         config: config.clone(),
         pool,
+        scheduler,
+        scales,
+        collection_feed: CollectionFeed::new(),
+        storage,
+        mailer,
+        sms,
+        oidc: Oidc::init(&config),
     };
 
     let router: Router = create_router(app_state.clone()).await;
@@ -168,7 +242,13 @@ pub async fn main() -> Result<(), Error> {
             "https://3reco.vps2.lone-wolf.dev".parse::<HeaderValue>()?,
         ])
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            header::ACCEPT,
+            idempotency::IDEMPOTENCY_KEY,
+        ])
+        .expose_headers([idempotency::IDEMPOTENT_REPLAYED])
         .allow_credentials(true);
 
     let router: Router = router
@@ -182,7 +262,11 @@ pub async fn main() -> Result<(), Error> {
 
     tracing::info!("🚀 Server listening on: {}", address);
 
-    axum::serve(listener, router.into_make_service()).await?;
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,101 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use axum::http::HeaderMap;
+use reqwest::Url;
+use tokio::net::lookup_host;
+
+/// Whether an address is on the public internet. The server connects to
+/// scales and webhook endpoints on behalf of users, which must not reach the
+/// loopback, private or link-local networks of the server.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // 0.0.0.0/8, "this network"
+        || first == 0
+        // 100.64.0.0/10, shared address space of carrier-grade NAT
+        || (first == 100 && (64..128).contains(&second))
+        // 198.18.0.0/15, benchmarking
+        || (first == 198 && (18..20).contains(&second))
+        // 240.0.0.0/4, reserved
+        || first >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // fc00::/7, unique local
+        || (first & 0xfe00) == 0xfc00
+        // fe80::/10, link-local
+        || (first & 0xffc0) == 0xfe80
+        // 2001:db8::/32, documentation
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// Resolves a host and fails when it resolves to an address that is not
+/// public. Connect to the returned addresses rather than the host, so that
+/// the name can not resolve to another address in the meantime.
+pub async fn resolve_public(host: &str, port: u16) -> Result<Vec<SocketAddr>, String> {
+    let addresses: Vec<SocketAddr> = lookup_host((host, port))
+        .await
+        .map_err(|_| format!("The host \"{}\" could not be resolved.", host))?
+        .collect();
+
+    if addresses.is_empty() {
+        return Err(format!("The host \"{}\" could not be resolved.", host));
+    }
+
+    if addresses.iter().any(|address| !is_public_ip(address.ip())) {
+        return Err(format!(
+            "The host \"{}\" is not a public address. Loopback, private and link-local addresses are not allowed.",
+            host
+        ));
+    }
+
+    Ok(addresses)
+}
+
+/// Resolves the host of a url like `resolve_public`.
+pub async fn resolve_public_url(url: &Url) -> Result<Vec<SocketAddr>, String> {
+    let host = url
+        .host_str()
+        .ok_or("The url has no host.")?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = url.port_or_known_default().ok_or("The url has no port.")?;
+
+    resolve_public(host, port).await
+}
+
+/// The address of the client. The server listens behind a reverse proxy, which
+/// appends the address it was connected from to `X-Forwarded-For`, so the
+/// last entry is the one that can be trusted.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> IpAddr {
+    headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .next_back()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(peer.ip())
+}
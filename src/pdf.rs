@@ -0,0 +1,147 @@
+/// A minimal PDF writer for plain text documents.
+///
+/// Text is laid out top to bottom in a monospaced font on A4 pages, which is
+/// all that is needed for statements and tabular reports. Lines are added one
+/// at a time and a new page is started automatically when a page is full.
+pub struct PdfDocument {
+    pages: Vec<Vec<String>>,
+}
+
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 40;
+const FONT_SIZE: u32 = 9;
+const LINE_HEIGHT: u32 = 12;
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2 * MARGIN) / LINE_HEIGHT) as usize;
+
+impl Default for PdfDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self {
+            pages: vec![Vec::new()],
+        }
+    }
+
+    /// Adds a line of text to the current page.
+    pub fn line(&mut self, text: impl Into<String>) {
+        if self.pages.last().map(|page| page.len()).unwrap_or(0) >= LINES_PER_PAGE {
+            self.pages.push(Vec::new());
+        }
+
+        if let Some(page) = self.pages.last_mut() {
+            page.push(text.into());
+        }
+    }
+
+    /// Starts a new page, unless the current page is still empty.
+    pub fn page_break(&mut self) {
+        if self
+            .pages
+            .last()
+            .map(|page| !page.is_empty())
+            .unwrap_or(false)
+        {
+            self.pages.push(Vec::new());
+        }
+    }
+
+    /// Renders the document to the bytes of a PDF file.
+    pub fn render(&self) -> Vec<u8> {
+        // Object 1 is the catalog, 2 the page tree and 3 the font. Every page
+        // then takes two objects, the page itself and its content stream.
+        let mut objects: Vec<String> = Vec::new();
+
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|index| 4 + index * 2).collect();
+
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<String>>()
+                .join(" "),
+            page_ids.len()
+        ));
+        objects.push(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+                .to_string(),
+        );
+
+        for (index, lines) in self.pages.iter().enumerate() {
+            let mut content = format!(
+                "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+                FONT_SIZE,
+                LINE_HEIGHT,
+                MARGIN,
+                PAGE_HEIGHT - MARGIN
+            );
+
+            for line in lines {
+                content += &format!("({}) '\n", escape(line));
+            }
+
+            content += "ET";
+
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                PAGE_WIDTH,
+                PAGE_HEIGHT,
+                page_ids[index] + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}\nendstream",
+                content.len(),
+                content
+            ));
+        }
+
+        let mut output = b"%PDF-1.4\n".to_vec();
+        let mut offsets: Vec<usize> = Vec::new();
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output
+                .extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+        }
+
+        let xref_offset = output.len();
+
+        output.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        output.extend_from_slice(b"0000000000 65535 f \n");
+
+        for offset in offsets {
+            output.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+
+        output.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+
+        output
+    }
+}
+
+/// Escapes a line for use in a PDF string literal. Characters outside of
+/// printable ASCII are replaced as the built in fonts can not render them.
+fn escape(text: &str) -> String {
+    text.chars()
+        .map(|character| match character {
+            '(' => "\\(".to_string(),
+            ')' => "\\)".to_string(),
+            '\\' => "\\\\".to_string(),
+            ' '..='~' => character.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
@@ -1,6 +1,6 @@
 use axum::{
     middleware,
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
 use tower_http::trace::{self, TraceLayer};
@@ -11,9 +11,11 @@ use utoipa_swagger_ui::SwaggerUi;
 use crate::{
     authentication::jwt,
     documentation::api_documentation::ApiDoc,
+    idempotency,
     routes::{
-        authentication, business, collection, collector, export, fallback::get_fallback,
-        index::get_index, mfa, product, users,
+        api_key, authentication, bale, branch, business, collection, collector, epr, epr_stream,
+        export, fallback::get_fallback, impact, impact_factor, import, index::get_index, kyc, mfa,
+        pickup, product, report_job, sale, scale, statement, stock, sync, users, webhook,
     },
     AppState,
 };
@@ -39,6 +41,31 @@ pub async fn create_router(app_state: AppState) -> Router {
                         .post(business::update::business)
                         .delete(business::delete::business),
                 )
+                .route(
+                    "/:business_id/listing",
+                    get(business::listing::listing).post(business::listing::update),
+                )
+                .route("/:business_id/member", get(business::member::members))
+                .route(
+                    "/:business_id/member/invite",
+                    post(business::invitation::invite),
+                )
+                .route(
+                    "/:business_id/member/invitation",
+                    get(business::invitation::invitations),
+                )
+                .route(
+                    "/:business_id/member/invitation/:invitation_id",
+                    delete(business::invitation::revoke),
+                )
+                .route(
+                    "/:business_id/member/invitation/:invitation_id/resend",
+                    post(business::invitation::resend),
+                )
+                .route(
+                    "/:business_id/member/:user_id",
+                    post(business::member::update).delete(business::member::remove),
+                )
                 .route("/add", post(business::add::business)),
         )
         .nest(
@@ -52,6 +79,11 @@ pub async fn create_router(app_state: AppState) -> Router {
                         .delete(collector::delete::collector),
                 )
                 .route("/search/:query", get(collector::search::collector))
+                .route("/registration", get(collector::registration::registrations))
+                .route(
+                    "/:collector_id/review",
+                    post(collector::registration::review),
+                )
                 .route("/add", post(collector::add::collector)),
         )
         .nest(
@@ -76,8 +108,273 @@ pub async fn create_router(app_state: AppState) -> Router {
                         .post(collection::update::collection)
                         .delete(collection::delete::collection),
                 )
+                .route("/:collection_id/paid", post(collection::paid::paid))
+                .route(
+                    "/:collection_id/photo",
+                    get(collection::photo::photos).post(collection::photo::upload),
+                )
+                .route("/photo/:photo_id", delete(collection::photo::delete))
                 .route("/add", post(collection::add::collection)),
         )
+        .nest(
+            "/scale",
+            Router::new()
+                .route("/", get(scale::view::scales))
+                .route(
+                    "/:scale_id",
+                    get(scale::view::scale)
+                        .post(scale::update::scale)
+                        .delete(scale::delete::scale),
+                )
+                .route("/:scale_id/reading", get(scale::reading::reading))
+                .route("/:scale_id/feed", get(scale::reading::feed))
+                .route("/add", post(scale::add::scale)),
+        )
+        .nest(
+            "/impact",
+            Router::new()
+                .route(
+                    "/collection/:collection_id",
+                    get(impact::collection::collection),
+                )
+                .route("/collector", get(impact::collector::collector))
+                .route("/business", get(impact::business::business))
+                .route("/period", get(impact::period::period))
+                .nest(
+                    "/factor",
+                    Router::new()
+                        .route("/", get(impact_factor::view::impact_factors))
+                        .route(
+                            "/:impact_factor_id",
+                            get(impact_factor::view::impact_factor)
+                                .post(impact_factor::update::impact_factor)
+                                .delete(impact_factor::delete::impact_factor),
+                        )
+                        .route("/add", post(impact_factor::add::impact_factor)),
+                ),
+        )
+        .nest(
+            "/epr",
+            Router::new()
+                .nest(
+                    "/stream",
+                    Router::new()
+                        .route("/", get(epr_stream::view::epr_streams))
+                        .route(
+                            "/:epr_stream_id",
+                            get(epr_stream::view::epr_stream)
+                                .post(epr_stream::update::epr_stream)
+                                .delete(epr_stream::delete::epr_stream),
+                        )
+                        .route("/add", post(epr_stream::add::epr_stream)),
+                )
+                .route("/product", get(epr::product::products))
+                .route(
+                    "/product/:product_id",
+                    post(epr::product::assign).delete(epr::product::unassign),
+                )
+                .nest(
+                    "/report",
+                    Router::new()
+                        .route("/", get(epr::view::epr_reports))
+                        .route("/preview", get(epr::preview::preview))
+                        .route("/generate", post(epr::generate::generate))
+                        .route(
+                            "/:epr_report_id",
+                            get(epr::view::epr_report).delete(epr::delete::epr_report),
+                        )
+                        .route("/:epr_report_id/export", get(epr::export::export))
+                        .route("/:epr_report_id/submit", post(epr::submit::submit)),
+                ),
+        )
+        .nest(
+            "/statement",
+            Router::new()
+                .route("/me", get(statement::collector::me))
+                .route("/bulk", get(statement::bulk::bulk))
+                .route(
+                    "/collector/:collector_id",
+                    get(statement::collector::collector),
+                ),
+        )
+        .nest(
+            "/import",
+            Router::new()
+                .route("/collector", post(import::collector::collector))
+                .route("/business", post(import::business::business))
+                .route("/collection", post(import::collection::collection))
+                .route("/collection/staged", get(import::staged::staged))
+                .route(
+                    "/collection/staged/:import_row_id/resolve",
+                    post(import::staged::resolve),
+                )
+                .route(
+                    "/collection/staged/:import_row_id/discard",
+                    post(import::staged::discard),
+                ),
+        )
+        .nest(
+            "/report",
+            Router::new()
+                .nest(
+                    "/job",
+                    Router::new()
+                        .route("/", get(report_job::view::report_jobs))
+                        .route(
+                            "/:report_job_id",
+                            get(report_job::view::report_job)
+                                .post(report_job::update::report_job)
+                                .delete(report_job::delete::report_job),
+                        )
+                        .route("/:report_job_id/run", post(report_job::run::run))
+                        .route(
+                            "/:report_job_id/runs",
+                            get(report_job::view::report_job_runs),
+                        )
+                        .route("/add", post(report_job::add::report_job)),
+                )
+                .route(
+                    "/run/:report_job_run_id/download",
+                    get(report_job::download::download),
+                ),
+        )
+        .nest(
+            "/webhook",
+            Router::new()
+                .route("/", get(webhook::view::webhooks))
+                .route(
+                    "/:webhook_id",
+                    get(webhook::view::webhook)
+                        .post(webhook::update::webhook)
+                        .delete(webhook::delete::webhook),
+                )
+                .route(
+                    "/:webhook_id/deliveries",
+                    get(webhook::delivery::deliveries),
+                )
+                .route("/delivery/:delivery_id", get(webhook::delivery::delivery))
+                .route(
+                    "/delivery/:delivery_id/retry",
+                    post(webhook::delivery::retry),
+                )
+                .route("/add", post(webhook::add::webhook)),
+        )
+        .nest(
+            "/api_key",
+            Router::new()
+                .route("/", get(api_key::view::api_keys))
+                .route(
+                    "/:api_key_id",
+                    get(api_key::view::api_key).delete(api_key::delete::api_key),
+                )
+                .route("/add", post(api_key::add::api_key)),
+        )
+        .nest(
+            "/kyc",
+            Router::new()
+                .route("/", get(kyc::view::documents))
+                .route(
+                    "/collector/:collector_id",
+                    get(kyc::view::collector_documents).post(kyc::add::collector_document),
+                )
+                .route(
+                    "/business/:business_id",
+                    get(kyc::view::business_documents).post(kyc::add::business_document),
+                )
+                .route("/document/:document_id", delete(kyc::delete::document))
+                .route("/document/:document_id/download", get(kyc::view::download))
+                .route("/document/:document_id/review", post(kyc::review::review)),
+        )
+        .route("/sync", post(sync::apply::sync))
+        .nest(
+            "/pickup",
+            Router::new()
+                .route("/", get(pickup::view::pickups))
+                .route("/add", post(pickup::add::pickup))
+                .route("/route", get(pickup::route::route))
+                .route(
+                    "/vehicle",
+                    get(pickup::vehicle::vehicles).post(pickup::vehicle::add),
+                )
+                .route("/vehicle/:vehicle_id", post(pickup::vehicle::update))
+                .route(
+                    "/driver",
+                    get(pickup::driver::drivers).post(pickup::driver::add),
+                )
+                .route("/driver/:driver_id", post(pickup::driver::update))
+                .route("/:pickup_id", get(pickup::view::pickup))
+                .route("/:pickup_id/assign", post(pickup::update::assign))
+                .route("/:pickup_id/cancel", post(pickup::update::cancel))
+                .route("/:pickup_id/complete", post(pickup::complete::complete)),
+        )
+        .nest(
+            "/stock",
+            Router::new()
+                .route("/", get(stock::view::stock))
+                .route(
+                    "/movement",
+                    get(stock::view::movements).post(stock::movement::record),
+                )
+                .route("/balance", get(stock::balance::balance)),
+        )
+        .nest(
+            "/sale",
+            Router::new()
+                .route("/", get(sale::view::sales))
+                .route("/add", post(sale::add::sale))
+                .route("/margin", get(sale::margin::margin))
+                .route(
+                    "/offtaker",
+                    get(sale::offtaker::offtakers).post(sale::offtaker::add),
+                )
+                .route("/offtaker/:offtaker_id", post(sale::offtaker::update))
+                .route("/:sale_id", delete(sale::delete::sale)),
+        )
+        .nest(
+            "/bale",
+            Router::new()
+                .route("/", get(bale::view::bales))
+                .route("/add", post(bale::add::bale))
+                .route("/code/:code", get(bale::view::code))
+                .route("/:bale_id", get(bale::view::bale))
+                .route("/:bale_id/collection", post(bale::update::add))
+                .route(
+                    "/:bale_id/collection/:collection_id",
+                    delete(bale::update::remove),
+                )
+                .route("/:bale_id/close", post(bale::update::close))
+                .route("/:bale_id/sale", post(bale::update::sale)),
+        )
+        .nest(
+            "/branch",
+            Router::new()
+                .route("/", get(branch::view::branches))
+                .route("/add", post(branch::add::branch))
+                .route("/report", get(branch::report::report))
+                .route(
+                    "/:branch_id",
+                    get(branch::view::branch).post(branch::update::branch),
+                )
+                .route(
+                    "/:branch_id/price",
+                    get(branch::price::prices).post(branch::price::set),
+                )
+                .route(
+                    "/:branch_id/price/:product_id",
+                    delete(branch::price::remove),
+                )
+                .route(
+                    "/:branch_id/staff",
+                    get(branch::staff::staff).post(branch::staff::add),
+                )
+                .route("/:branch_id/staff/:user_id", delete(branch::staff::remove)),
+        )
+        .nest(
+            "/trace",
+            Router::new()
+                .route("/collection/:collection_id", get(bale::trace::collection))
+                .route("/sale/:sale_id", get(bale::trace::sale)),
+        )
         .nest(
             "/users",
             Router::new()
@@ -88,7 +385,10 @@ pub async fn create_router(app_state: AppState) -> Router {
                         .post(users::update::user)
                         .delete(users::delete::user),
                 )
-                .route("/add", post(users::add::user)),
+                .route("/add", post(users::add::user))
+                .route("/invite", post(users::invitation::invite))
+                .route("/invitation", get(users::invitation::invitations))
+                .route("/:user_id/invite/resend", post(users::invitation::resend)),
         )
         .nest(
             "/authentication",
@@ -101,14 +401,51 @@ pub async fn create_router(app_state: AppState) -> Router {
                         .route("/verify", post(mfa::verify::verify)),
                 ),
         )
+        // replays retried requests, needs the user from jwt_guard
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            idempotency::idempotency_guard,
+        ))
         .route_layer(middleware::from_fn_with_state(
             app_state.clone(),
             jwt::jwt_guard,
         ))
+        // collection feed, authenticated by header or by token query
+        .route("/collection/feed", get(collection::feed::feed))
+        // photo downloads, authenticated by a signed link
+        .route(
+            "/collection/photo/:photo_id/download",
+            get(collection::photo::download),
+        )
+        // buy-back centre map and proximity search
+        .route("/business/nearest", get(business::location::nearest))
+        .route("/business/geojson", get(business::location::geojson))
+        // public directory of buy-back centres
+        .nest(
+            "/directory",
+            Router::new()
+                .route("/", get(business::directory::directory))
+                .route("/:business_id", get(business::directory::entry)),
+        )
+        // collector self-registration
+        .nest(
+            "/registration",
+            Router::new()
+                .route("/collector", post(collector::registration::register))
+                .route("/collector/code", post(collector::registration::code)),
+        )
         // authentication
         .nest(
             "/authentication",
-            Router::new().route("/login", post(authentication::login::user)),
+            Router::new()
+                .route("/login", post(authentication::login::user))
+                .route("/activate", post(authentication::activate::user))
+                .route("/oidc/authorize", get(authentication::oidc::authorize))
+                .route("/oidc/callback", post(authentication::oidc::callback))
+                .route(
+                    "/invitation/accept",
+                    post(authentication::invitation::accept),
+                ),
         )
         .route_layer(middleware::from_fn_with_state(
             app_state.clone(),
@@ -0,0 +1,147 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::{api_key::generate_key, token::hash_token},
+    data::entities::{api_key::ApiKey, business_member::BusinessMember, user::User},
+    idempotency::NoStore,
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::validate_permissions;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddApiKeyPayload {
+    pub name: String,
+    /// The areas of the api the key may use, e.g. `collection:read` or
+    /// `scale:write`. Write includes read.
+    pub permissions: Vec<String>,
+    /// Makes the key a key of the business, which its managers can see and
+    /// revoke. Either way the key acts as the user creating it, with their
+    /// role in the business.
+    pub business_id: Option<Uuid>,
+    /// The key never expires when empty.
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+/// Creates an API key. The key is only returned here, afterwards only its
+/// prefix is shown. Send it as `Authorization: Bearer <key>`.
+#[utoipa::path(
+    post,
+    path = "/api_key/add",
+    request_body = AddApiKeyPayload,
+    tag = "API Key",
+    security(("bearer_auth" = [])),
+)]
+pub async fn api_key(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddApiKeyPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bad_request = |reason: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    if payload.name.trim().is_empty() {
+        return Err(bad_request("A name is required."));
+    }
+
+    validate_permissions(&payload.permissions)?;
+
+    if payload
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now().naive_utc())
+    {
+        return Err(bad_request("The expiry must be in the future."));
+    }
+
+    if let Some(business_id) = payload.business_id {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "You do not have permission to add API keys for this business.",
+        )?;
+
+        // The key acts as its creator, so it is only bound to the business
+        // through their membership.
+        let is_member = membership
+            .as_ref()
+            .is_some_and(|membership| membership.business_id == business_id);
+
+        if !is_member {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "Only members of the business can add API keys for it."
+                })),
+            ));
+        }
+    }
+
+    let (key, prefix) = generate_key();
+
+    let api_key = sqlx::query_as!(
+        ApiKey,
+        r#"
+        INSERT INTO api_key (name, prefix, key_hash, user_id, business_id, permissions, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        payload.name.trim(),
+        prefix,
+        hash_token(&key),
+        authenticated_user.id,
+        payload.business_id,
+        &payload.permissions,
+        payload.expires_at
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::CREATED,
+        (
+            // The key is only ever returned here.
+            extract::Extension(NoStore),
+            Json(json!({
+                "success": true,
+                "api_key": api_key,
+                "key": key
+            })),
+        ),
+    ))
+}
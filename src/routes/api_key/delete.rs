@@ -0,0 +1,58 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{api_key::ApiKey, business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::find_api_key;
+
+/// Revokes an API key, it stops working straight away. Revoked keys are kept
+/// for their history.
+#[utoipa::path(
+    delete,
+    path = "/api_key/{api_key_id}",
+    params(("api_key_id" = String, Path, description = "The API keys id.")),
+    tag = "API Key",
+    security(("bearer_auth" = [])),
+)]
+pub async fn api_key(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(api_key_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let api_key = find_api_key(&app_state, &authenticated_user, &membership, api_key_id).await?;
+
+    let api_key = sqlx::query_as!(
+        ApiKey,
+        r#"
+        UPDATE api_key SET revoked_at = COALESCE(revoked_at, CURRENT_TIMESTAMP) WHERE id = $1
+        RETURNING *
+        "#,
+        api_key.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "api_key": api_key
+        })),
+    ))
+}
@@ -0,0 +1,94 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::{
+        api_key::{is_permission, AREAS},
+        roles::Role,
+    },
+    data::entities::{api_key::ApiKey, business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+pub mod add;
+pub mod delete;
+pub mod view;
+
+pub fn validate_permissions(permissions: &[String]) -> Result<(), (StatusCode, Json<Value>)> {
+    if permissions.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "An API key needs at least one permission."
+            })),
+        ));
+    }
+
+    if let Some(permission) = permissions
+        .iter()
+        .find(|permission| !is_permission(permission))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!(
+                    "Unknown permission \"{}\", expected an area with :read or :write. The areas are {}.",
+                    permission,
+                    AREAS.join(", ")
+                )
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds a key the user may manage. Users manage their own keys, and managers
+/// of a business manage the keys of the business.
+pub async fn find_api_key(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    api_key_id: Uuid,
+) -> Result<ApiKey, (StatusCode, Json<Value>)> {
+    let api_key = sqlx::query_as!(
+        ApiKey,
+        r#"
+        SELECT * FROM api_key WHERE id = $1
+        "#,
+        api_key_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let may_manage = |api_key: &ApiKey| {
+        authenticated_user.role() == Role::SystemAdmin
+            || api_key.user_id == authenticated_user.id
+            || api_key.business_id.is_some_and(|business_id| {
+                require_business_manager(authenticated_user, membership, business_id, "").is_ok()
+            })
+    };
+
+    api_key.filter(may_manage).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "API key not found."
+        })),
+    ))
+}
@@ -0,0 +1,101 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{api_key::ApiKey, business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_api_key;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiKeyQuery {
+    /// The keys of this business instead of the own keys.
+    pub business_id: Option<Uuid>,
+}
+
+/// Lists the own API keys, or those of a business the user manages, including
+/// revoked and expired keys.
+#[utoipa::path(
+    get,
+    path = "/api_key",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, to list its keys."),
+    ),
+    tag = "API Key",
+    security(("bearer_auth" = [])),
+)]
+pub async fn api_keys(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<ApiKeyQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if let Some(business_id) = query.business_id {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "You do not have permission to view the API keys of this business.",
+        )?;
+    }
+
+    let api_keys = sqlx::query_as!(
+        ApiKey,
+        r#"
+        SELECT * FROM api_key
+        WHERE CASE WHEN $1::uuid IS NULL THEN user_id = $2 AND business_id IS NULL ELSE business_id = $1 END
+        ORDER BY created_at DESC
+        "#,
+        query.business_id,
+        authenticated_user.id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "api_keys": api_keys
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api_key/{api_key_id}",
+    params(("api_key_id" = String, Path, description = "The API keys id.")),
+    tag = "API Key",
+    security(("bearer_auth" = [])),
+)]
+pub async fn api_key(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(api_key_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let api_key = find_api_key(&app_state, &authenticated_user, &membership, api_key_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "api_key": api_key
+        })),
+    ))
+}
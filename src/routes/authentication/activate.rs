@@ -0,0 +1,111 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bcrypt::hash;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    authentication::token::hash_token,
+    data::entities::{user::User, user_activation::UserActivation},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ActivatePayload {
+    /// The token from the activation link.
+    pub token: String,
+    pub password: String,
+}
+
+/// Sets the password of an invited user and activates their account. Each
+/// link can be used once.
+#[utoipa::path(
+    post,
+    path = "/authentication/activate",
+    request_body = ActivatePayload,
+    tag = "Authentication",
+)]
+pub async fn user(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Json(payload): extract::Json<ActivatePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if payload.password.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A password is required."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let activation = sqlx::query_as!(
+        UserActivation,
+        r#"
+        UPDATE user_activation SET used_at = CURRENT_TIMESTAMP
+        WHERE token_hash = $1 AND used_at IS NULL AND revoked_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+        RETURNING *
+        "#,
+        hash_token(&payload.token)
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "The activation link is invalid or has expired."
+        })),
+    ))?;
+
+    let hashed_password = hash(&payload.password, 4).map_err(|error| {
+        tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        UPDATE users SET password = $2, active = TRUE, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        activation.user_id,
+        hashed_password
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "user": user
+        })),
+    ))
+}
@@ -0,0 +1,181 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bcrypt::{hash, verify};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    authentication::{roles::Role, token::hash_token},
+    data::entities::{
+        business_invitation::BusinessInvitation, business_member::BusinessMember, user::User,
+    },
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AcceptInvitationPayload {
+    pub token: String,
+    /// The password of the new account, or of the existing account with the
+    /// invited email.
+    pub password: String,
+}
+
+/// Joins the business that sent the invitation. An account is created for the
+/// invited email when there is none yet.
+#[utoipa::path(
+    post,
+    path = "/authentication/invitation/accept",
+    request_body = AcceptInvitationPayload,
+    tag = "Authentication",
+)]
+pub async fn accept(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Json(payload): extract::Json<AcceptInvitationPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let invitation = sqlx::query_as!(
+        BusinessInvitation,
+        r#"
+        SELECT * FROM business_invitation
+        WHERE token_hash = $1 AND accepted_at IS NULL AND revoked_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+        FOR UPDATE
+        "#,
+        hash_token(&payload.token)
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "The invitation is invalid or has expired."
+        })),
+    ))?;
+
+    let existing_user = sqlx::query_as!(
+        User,
+        r#"
+        SELECT * FROM users WHERE TRIM(LOWER(email)) = $1
+        "#,
+        invitation.email
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let user = match existing_user {
+        Some(user) => {
+            let password_matches = verify(&payload.password, &user.password).unwrap_or(false);
+
+            if !password_matches {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    Json(json!({
+                        "error": "Unauthorized",
+                        "reason": "The password does not match the account of the invited email."
+                    })),
+                ));
+            }
+
+            if user.role() != Role::Business {
+                return Err((
+                    StatusCode::CONFLICT,
+                    Json(json!({
+                        "error": "Conflict",
+                        "reason": "Only business accounts can join a business."
+                    })),
+                ));
+            }
+
+            user
+        }
+        None => {
+            let hashed_password = hash(&payload.password, 4).map_err(|error| {
+                tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Unknown error occured. Please contact the api developer."
+                    })),
+                )
+            })?;
+
+            sqlx::query_as!(
+                User,
+                r#"
+                INSERT INTO users (email, password, role) VALUES ($1, $2, $3) RETURNING *
+                "#,
+                invitation.email,
+                hashed_password,
+                Role::Business.to_string()
+            )
+            .fetch_one(&mut *transaction)
+            .await
+            .map_err(database_error)?
+        }
+    };
+
+    let member = sqlx::query_as!(
+        BusinessMember,
+        r#"
+        INSERT INTO business_member (business_id, user_id, role, branch_id)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        invitation.business_id,
+        user.id,
+        invitation.role,
+        invitation.branch_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "This user already belongs to a business."
+            })),
+        ),
+        error => database_error(error),
+    })?;
+
+    sqlx::query!(
+        r#"
+        UPDATE business_invitation SET accepted_by = $2, accepted_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        "#,
+        invitation.id,
+        user.id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "user": user,
+            "member": member
+        })),
+    ))
+}
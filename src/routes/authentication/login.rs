@@ -8,6 +8,7 @@ use utoipa::ToSchema;
 use crate::{
     authentication::jwt::{create_jwt, Claims},
     data::entities::user::User,
+    routes::collector::{STATUS_PENDING, STATUS_REJECTED},
     AppState,
 };
 
@@ -88,9 +89,46 @@ pub async fn user(
 
     // Check if the users account is active.
     if !user.active {
+        let pending = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM user_activation WHERE user_id = $1 AND used_at IS NULL AND revoked_at IS NULL
+            ) AS "exists!"
+            "#,
+            user.id
+        )
+        .fetch_one(&app_state.pool)
+        .await
+        .map_err(|error|{
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Internal Server Error", "reason": "Failed to query database."})))
+        })?;
+
+        let registration_status = sqlx::query_scalar!(
+            r#"
+            SELECT status FROM collector_profile WHERE user_id = $1
+            "#,
+            user.id
+        )
+        .fetch_optional(&app_state.pool)
+        .await
+        .map_err(|error|{
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({"error": "Internal Server Error", "reason": "Failed to query database."})))
+        })?;
+
+        let reason = match registration_status.as_deref() {
+            _ if pending => "Account not activated. Use the link in your invitation email to choose a password.",
+            Some(STATUS_PENDING) => "Your registration is waiting for approval.",
+            Some(STATUS_REJECTED) => "Your registration was rejected.",
+            _ => "Account deactivated.",
+        };
+
         return Err((
             StatusCode::UNAUTHORIZED,
-            Json(json!({"error": "Unauthorized","reason": "Account deactivated."})),
+            Json(json!({"error": "Unauthorized","reason": reason})),
         ));
     }
 
@@ -1,2 +1,5 @@
+pub mod activate;
 pub mod check;
+pub mod invitation;
 pub mod login;
+pub mod oidc;
@@ -0,0 +1,401 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bcrypt::hash;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    authentication::{
+        jwt::{create_jwt, Claims},
+        oidc::{Oidc, Provider},
+        roles::Role,
+        token::{generate_token, hash_token},
+    },
+    data::entities::{oidc_login::OidcLogin, user::User},
+    AppState,
+};
+
+/// How long a started sign-in can be completed for.
+const LOGIN_TTL_MINUTES: i64 = 10;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CallbackPayload {
+    /// The code from the redirect back from the identity provider.
+    pub code: String,
+    /// The state from the redirect back from the identity provider.
+    pub state: String,
+}
+
+fn configured(app_state: &AppState) -> Result<&Oidc, (StatusCode, Json<Value>)> {
+    app_state.oidc.as_ref().ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Single sign-on is not configured."
+        })),
+    ))
+}
+
+async fn provider(oidc: &Oidc) -> Result<Provider, (StatusCode, Json<Value>)> {
+    oidc.provider().await.map_err(|error| {
+        tracing::error!("🔥 Failed to discover the identity provider: {}", error);
+
+        (
+            StatusCode::BAD_GATEWAY,
+            Json(json!({
+                "error": "Bad Gateway",
+                "reason": "The identity provider could not be reached."
+            })),
+        )
+    })
+}
+
+/// Starts a single sign-on for staff. Send the browser to the returned url,
+/// the identity provider redirects back with a code and state for the
+/// callback.
+#[utoipa::path(
+    get,
+    path = "/authentication/oidc/authorize",
+    tag = "Authentication",
+    responses(
+        (
+            status = 200,
+            content_type = "application/json",
+            description = "The url to sign in at.",
+        ),
+        (
+            status = 404,
+            content_type = "application/json",
+            description = "Single sign-on is not configured.",
+        ),
+    ),
+)]
+pub async fn authorize(
+    extract::State(app_state): extract::State<AppState>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let oidc = configured(&app_state)?;
+    let provider = provider(oidc).await?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let state = generate_token();
+    let nonce = generate_token();
+    let code_verifier = generate_token();
+
+    let url = oidc
+        .authorization_url(&provider, &state, &nonce, &code_verifier)
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to build the authorization url: {}", error);
+
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(json!({
+                    "error": "Bad Gateway",
+                    "reason": "The identity provider's authorization endpoint is invalid."
+                })),
+            )
+        })?;
+
+    // Sign-ins that were never completed are cleared out as new ones start.
+    sqlx::query!(
+        r#"
+        DELETE FROM oidc_login WHERE expires_at <= CURRENT_TIMESTAMP
+        "#
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO oidc_login (state_hash, code_verifier, nonce, expires_at)
+        VALUES ($1, $2, $3, $4)
+        "#,
+        hash_token(&state),
+        code_verifier,
+        nonce,
+        (Utc::now() + Duration::minutes(LOGIN_TTL_MINUTES)).naive_utc()
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "url": url
+        })),
+    ))
+}
+
+/// Completes a single sign-on. Staff are matched by their account at the
+/// identity provider, or else by email, and are created on their first
+/// sign-in. Their role follows their groups at the provider.
+#[utoipa::path(
+    post,
+    path = "/authentication/oidc/callback",
+    tag = "Authentication",
+    request_body = CallbackPayload,
+    responses(
+        (
+            status = 200,
+            content_type = "application/json",
+            description = "Authorized.",
+        ),
+        (
+            status = 400,
+            content_type = "application/json",
+            description = "The sign-in is unknown or has expired.",
+        ),
+        (
+            status = 401,
+            content_type = "application/json",
+            description = "Unauthorized.",
+        ),
+        (
+            status = 409,
+            content_type = "application/json",
+            description = "The email belongs to an account that must sign in with a password.",
+        ),
+    ),
+)]
+pub async fn callback(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Json(payload): extract::Json<CallbackPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let oidc = configured(&app_state)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let unauthorized = |reason: &str| {
+        (
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": reason
+            })),
+        )
+    };
+
+    // Each sign-in can only be completed once.
+    let login = sqlx::query_as!(
+        OidcLogin,
+        r#"
+        DELETE FROM oidc_login WHERE state_hash = $1
+        RETURNING *
+        "#,
+        hash_token(&payload.state)
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .filter(|login| login.expires_at > Utc::now().naive_utc())
+    .ok_or((
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Bad Request",
+            "reason": "The sign-in is unknown or has expired. Please start again."
+        })),
+    ))?;
+
+    let provider = provider(oidc).await?;
+
+    let identity = oidc
+        .identity(&provider, &payload.code, &login.code_verifier, &login.nonce)
+        .await
+        .map_err(|error| {
+            tracing::warn!("Single sign-on was refused: {}", error);
+
+            unauthorized("The identity provider did not confirm the sign-in.")
+        })?;
+
+    let role = oidc
+        .role(&identity.groups)
+        .ok_or_else(|| unauthorized("You are not in a group that may sign in."))?;
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let linked = sqlx::query_as!(
+        User,
+        r#"
+        SELECT users.* FROM user_identity
+        INNER JOIN users ON users.id = user_identity.user_id
+        WHERE user_identity.issuer = $1 AND user_identity.subject = $2
+        "#,
+        identity.issuer,
+        identity.subject
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let user = match linked {
+        Some(user) => {
+            sqlx::query!(
+                r#"
+                UPDATE user_identity SET last_login_at = CURRENT_TIMESTAMP
+                WHERE issuer = $1 AND subject = $2
+                "#,
+                identity.issuer,
+                identity.subject
+            )
+            .execute(&mut *transaction)
+            .await
+            .map_err(database_error)?;
+
+            user
+        }
+        None => {
+            let email = identity.email.clone().ok_or_else(|| {
+                unauthorized("The identity provider did not share a verified email address.")
+            })?;
+
+            let existing = sqlx::query_as!(
+                User,
+                r#"
+                SELECT * FROM users WHERE TRIM(LOWER(email)) = $1
+                ORDER BY created_at
+                LIMIT 1
+                "#,
+                email
+            )
+            .fetch_optional(&mut *transaction)
+            .await
+            .map_err(database_error)?;
+
+            let user = match existing {
+                Some(user)
+                    if user.role == Role::Collector.to_string()
+                        || user.role == Role::Business.to_string() =>
+                {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        Json(json!({
+                            "error": "Conflict",
+                            "reason": "This email belongs to an account that signs in with a password."
+                        })),
+                    ));
+                }
+                Some(user) => user,
+                None => {
+                    // Staff created here only ever sign in through the
+                    // identity provider, so nobody knows their password.
+                    let hashed_password = hash(generate_token(), 4).map_err(|error| {
+                        tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(json!({
+                                "error": "Internal Server Error",
+                                "reason": "Unknown error occured. Please contact the api developer."
+                            })),
+                        )
+                    })?;
+
+                    sqlx::query_as!(
+                        User,
+                        r#"
+                        INSERT INTO users (email, password, role, active) VALUES ($1, $2, $3, TRUE)
+                        RETURNING *
+                        "#,
+                        email,
+                        hashed_password,
+                        role.to_string()
+                    )
+                    .fetch_one(&mut *transaction)
+                    .await
+                    .map_err(database_error)?
+                }
+            };
+
+            sqlx::query!(
+                r#"
+                INSERT INTO user_identity (user_id, issuer, subject) VALUES ($1, $2, $3)
+                "#,
+                user.id,
+                identity.issuer,
+                identity.subject
+            )
+            .execute(&mut *transaction)
+            .await
+            .map_err(database_error)?;
+
+            user
+        }
+    };
+
+    if !user.active {
+        return Err(unauthorized("Account deactivated."));
+    }
+
+    // The identity provider decides the role, so a change of groups there
+    // takes effect on the next sign-in.
+    let user = if user.role != role.to_string() {
+        sqlx::query_as!(
+            User,
+            r#"
+            UPDATE users SET role = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2
+            RETURNING *
+            "#,
+            role.to_string(),
+            user.id
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?
+    } else {
+        user
+    };
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let claims = Claims {
+        sub: user.clone().email,
+        iss: "Thusa Managed Executive Reports API.".to_string(),
+        role: user.role.to_string(),
+        ..Default::default()
+    };
+
+    let token = create_jwt(claims).await.map_err(|error| {
+        tracing::error!("🔥 Failed to create JWT token: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "user": user,
+            "token": token
+        })),
+    ))
+}
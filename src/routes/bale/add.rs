@@ -0,0 +1,135 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{bale::Bale, business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{add_collections, bale_scope, bale_view};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddBalePayload {
+    pub business_id: Uuid,
+    /// The material in the bale.
+    pub product_id: Uuid,
+    /// The barcode on the bale, generated when not given.
+    pub code: Option<String>,
+    /// Collections to put into the bale straight away.
+    #[serde(default)]
+    pub collection_ids: Vec<Uuid>,
+}
+
+/// Opens a bale for a material, optionally with the collections in it.
+#[utoipa::path(
+    post,
+    path = "/bale/add",
+    request_body = AddBalePayload,
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn bale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddBalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if bale_scope(&authenticated_user, &membership, Some(payload.business_id))?
+        != Some(payload.business_id)
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You may only manage the bales of your own business."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let product_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM product WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        payload.product_id,
+        payload.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !product_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let code = match payload.code.map(|code| code.trim().to_string()) {
+        Some(code) if !code.is_empty() => code,
+        _ => format!(
+            "BL-{}",
+            Uuid::new_v4().simple().to_string()[..10].to_uppercase()
+        ),
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let bale = sqlx::query_as!(
+        Bale,
+        r#"
+        INSERT INTO bale (business_id, product_id, code, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.product_id,
+        code,
+        authenticated_user.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A bale with this code already exists."
+            })),
+        ),
+        error => database_error(error),
+    })?;
+
+    if !payload.collection_ids.is_empty() {
+        add_collections(&mut transaction, &bale, &payload.collection_ids).await?;
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use axum::{http::StatusCode, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        bale::Bale, business_member::BusinessMember, collection::Collection, user::User,
+    },
+    routes::business::business_scope,
+    AppState,
+};
+
+pub mod add;
+pub mod trace;
+pub mod update;
+pub mod view;
+
+pub const STATUS_OPEN: &str = "Open";
+pub const STATUS_CLOSED: &str = "Closed";
+pub const STATUS_SOLD: &str = "Sold";
+
+/// A bale with the collections in it.
+#[derive(Debug, Clone, Serialize)]
+pub struct BaleView {
+    #[serde(flatten)]
+    pub bale: Bale,
+    pub collected_weight: BigDecimal,
+    pub collections: Vec<Collection>,
+}
+
+/// The business whose bales the user asked for. Business users always get
+/// their own business, staff any business or all of them.
+pub fn bale_scope(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Option<Uuid>,
+) -> Result<Option<Uuid>, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access bales."
+            })),
+        ));
+    }
+
+    Ok(business_scope(authenticated_user, membership)?.or(business_id))
+}
+
+/// Finds a bale the user may see.
+pub async fn find_bale(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    bale_id: Uuid,
+) -> Result<Bale, (StatusCode, Json<Value>)> {
+    let business_id = bale_scope(authenticated_user, membership, None)?;
+
+    sqlx::query_as!(
+        Bale,
+        r#"
+        SELECT * FROM bale WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        bale_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Bale not found."
+        })),
+    ))
+}
+
+pub async fn bale_view(app_state: &AppState, bale: Bale) -> Result<BaleView, sqlx::Error> {
+    let collections = sqlx::query_as!(
+        Collection,
+        r#"
+        SELECT collection.* FROM collection
+        INNER JOIN bale_collection ON bale_collection.collection_id = collection.id
+        WHERE bale_collection.bale_id = $1
+        ORDER BY collection.created_at
+        "#,
+        bale.id
+    )
+    .fetch_all(&app_state.pool)
+    .await?;
+
+    let collected_weight = collections
+        .iter()
+        .fold(BigDecimal::zero(), |total, collection| {
+            total + &collection.weight
+        });
+
+    Ok(BaleView {
+        bale,
+        collected_weight,
+        collections,
+    })
+}
+
+/// Puts collections into an open bale. The collections must be of the bale's
+/// business and product and not be in another bale.
+pub async fn add_collections(
+    transaction: &mut Transaction<'_, Postgres>,
+    bale: &Bale,
+    collection_ids: &[Uuid],
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let collection_ids: Vec<Uuid> = collection_ids
+        .iter()
+        .copied()
+        .collect::<HashSet<Uuid>>()
+        .into_iter()
+        .collect();
+
+    let collections = sqlx::query!(
+        r#"
+        SELECT
+            collection.id,
+            collection.business_id,
+            collection.product_id,
+            bale_collection.bale_id AS "bale_id?"
+        FROM collection
+        LEFT JOIN bale_collection ON bale_collection.collection_id = collection.id
+        WHERE collection.id = ANY($1)
+        "#,
+        &collection_ids
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let invalid = |reason: String| {
+        Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        ))
+    };
+
+    if collections.len() != collection_ids.len() {
+        return invalid("Some of the collections were not found.".to_string());
+    }
+
+    for collection in &collections {
+        if collection.business_id != bale.business_id || collection.product_id != bale.product_id {
+            return invalid(format!(
+                "Collection {} is not of the bale's business and product.",
+                collection.id
+            ));
+        }
+
+        if let Some(bale_id) = collection.bale_id {
+            return invalid(format!(
+                "Collection {} is already in bale {}.",
+                collection.id, bale_id
+            ));
+        }
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO bale_collection (bale_id, collection_id)
+        SELECT $1, UNNEST($2::uuid[])
+        "#,
+        bale.id,
+        &collection_ids
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        bale::Bale, business_member::BusinessMember, collection::Collection,
+        material_sale::MaterialSale, offtaker::Offtaker, user::User,
+    },
+    AppState,
+};
+
+use super::bale_scope;
+
+/// A collection in a bale with the collector who brought it in.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TracedCollection {
+    pub collection_id: Uuid,
+    pub weight: BigDecimal,
+    pub collected_at: Option<NaiveDateTime>,
+    pub collector_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub id_number: String,
+    pub collector_code: Option<String>,
+}
+
+/// A bale of a sale with the collections in it.
+#[derive(Debug, Clone, Serialize)]
+pub struct TracedBale {
+    #[serde(flatten)]
+    pub bale: Bale,
+    pub collections: Vec<TracedCollection>,
+}
+
+/// Follows a collection forward to the bale it went into, the sale the bale
+/// left with and the off-taker who bought it.
+#[utoipa::path(
+    get,
+    path = "/trace/collection/{collection_id}",
+    params(("collection_id" = String, Path, description = "The collections id.")),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collection(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collection_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = bale_scope(&authenticated_user, &membership, None)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+        SELECT * FROM collection WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        collection_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Collection not found."
+        })),
+    ))?;
+
+    let bale = sqlx::query_as!(
+        Bale,
+        r#"
+        SELECT bale.* FROM bale
+        INNER JOIN bale_collection ON bale_collection.bale_id = bale.id
+        WHERE bale_collection.collection_id = $1
+        "#,
+        collection.id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let sale = match bale.as_ref().and_then(|bale| bale.sale_id) {
+        Some(sale_id) => sqlx::query_as!(
+            MaterialSale,
+            r#"
+            SELECT * FROM material_sale WHERE id = $1
+            "#,
+            sale_id
+        )
+        .fetch_optional(&app_state.pool)
+        .await
+        .map_err(database_error)?,
+        None => None,
+    };
+
+    let offtaker = match sale.as_ref() {
+        Some(sale) => sqlx::query_as!(
+            Offtaker,
+            r#"
+            SELECT * FROM offtaker WHERE id = $1
+            "#,
+            sale.offtaker_id
+        )
+        .fetch_optional(&app_state.pool)
+        .await
+        .map_err(database_error)?,
+        None => None,
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "collection": collection,
+            "bale": bale,
+            "sale": sale,
+            "offtaker": offtaker
+        })),
+    ))
+}
+
+/// Follows a sale back to the bales sold, the collections in them and the
+/// collectors who brought those in.
+#[utoipa::path(
+    get,
+    path = "/trace/sale/{sale_id}",
+    params(("sale_id" = String, Path, description = "The sales id.")),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(sale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = bale_scope(&authenticated_user, &membership, None)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let sale = sqlx::query_as!(
+        MaterialSale,
+        r#"
+        SELECT * FROM material_sale WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        sale_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Sale not found."
+        })),
+    ))?;
+
+    let offtaker = sqlx::query_as!(
+        Offtaker,
+        r#"
+        SELECT * FROM offtaker WHERE id = $1
+        "#,
+        sale.offtaker_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let bales = sqlx::query_as!(
+        Bale,
+        r#"
+        SELECT * FROM bale WHERE sale_id = $1 ORDER BY code
+        "#,
+        sale.id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let mut traced_bales = Vec::with_capacity(bales.len());
+
+    for bale in bales {
+        let collections = sqlx::query_as!(
+            TracedCollection,
+            r#"
+            SELECT
+                collection.id AS collection_id,
+                collection.weight,
+                collection.created_at AS collected_at,
+                collector.id AS collector_id,
+                collector.first_name,
+                collector.last_name,
+                collector.id_number,
+                collector.collector_code
+            FROM bale_collection
+            INNER JOIN collection ON collection.id = bale_collection.collection_id
+            INNER JOIN collector_profile AS collector ON collector.id = collection.collector_id
+            WHERE bale_collection.bale_id = $1
+            ORDER BY collection.created_at
+            "#,
+            bale.id
+        )
+        .fetch_all(&app_state.pool)
+        .await
+        .map_err(database_error)?;
+
+        traced_bales.push(TracedBale { bale, collections });
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "sale": sale,
+            "offtaker": offtaker,
+            "bales": traced_bales
+        })),
+    ))
+}
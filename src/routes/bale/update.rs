@@ -0,0 +1,320 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{bale::Bale, business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{add_collections, bale_view, find_bale, STATUS_CLOSED, STATUS_OPEN, STATUS_SOLD};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct BaleCollectionsPayload {
+    pub collection_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CloseBalePayload {
+    /// The weighed bale, defaults to the weight of its collections.
+    pub weight: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct BaleSalePayload {
+    pub sale_id: Uuid,
+}
+
+fn database_error(error: sqlx::Error) -> (StatusCode, Json<Value>) {
+    tracing::error!("🔥 Failed to query database: {}", error);
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "Internal Server Error",
+            "reason": "Failed to query database."
+        })),
+    )
+}
+
+/// Locks the bale for the rest of the transaction and checks that it has the
+/// status the change needs.
+async fn lock_bale(
+    transaction: &mut Transaction<'_, Postgres>,
+    bale: &Bale,
+    status: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let current_status = sqlx::query_scalar!(
+        r#"
+        SELECT status FROM bale WHERE id = $1 FOR UPDATE
+        "#,
+        bale.id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    if current_status != status {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The bale is {} and not {}.", current_status, status)
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Puts collections into an open bale.
+#[utoipa::path(
+    post,
+    path = "/bale/{bale_id}/collection",
+    params(("bale_id" = String, Path, description = "The bales id.")),
+    request_body = BaleCollectionsPayload,
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn add(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(bale_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<BaleCollectionsPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bale = find_bale(&app_state, &authenticated_user, &membership, bale_id).await?;
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    lock_bale(&mut transaction, &bale, STATUS_OPEN).await?;
+    add_collections(&mut transaction, &bale, &payload.collection_ids).await?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
+
+/// Takes a collection out of an open bale.
+#[utoipa::path(
+    delete,
+    path = "/bale/{bale_id}/collection/{collection_id}",
+    params(
+        ("bale_id" = String, Path, description = "The bales id."),
+        ("collection_id" = String, Path, description = "The collections id."),
+    ),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn remove(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((bale_id, collection_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bale = find_bale(&app_state, &authenticated_user, &membership, bale_id).await?;
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    lock_bale(&mut transaction, &bale, STATUS_OPEN).await?;
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM bale_collection WHERE bale_id = $1 AND collection_id = $2
+        "#,
+        bale.id,
+        collection_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .rows_affected();
+
+    if removed == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "The collection is not in this bale."
+            })),
+        ));
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
+
+/// Closes a bale once it is pressed and weighed. No collections can be added
+/// to or taken out of a closed bale.
+#[utoipa::path(
+    post,
+    path = "/bale/{bale_id}/close",
+    params(("bale_id" = String, Path, description = "The bales id.")),
+    request_body = CloseBalePayload,
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn close(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(bale_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<CloseBalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bale = find_bale(&app_state, &authenticated_user, &membership, bale_id).await?;
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    lock_bale(&mut transaction, &bale, STATUS_OPEN).await?;
+
+    let collected_weight = sqlx::query_scalar!(
+        r#"
+        SELECT SUM(collection.weight) FROM bale_collection
+        INNER JOIN collection ON collection.id = bale_collection.collection_id
+        WHERE bale_collection.bale_id = $1
+        "#,
+        bale.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let Some(collected_weight) = collected_weight else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A bale needs at least one collection before it can be closed."
+            })),
+        ));
+    };
+
+    let weight = payload.weight.unwrap_or(collected_weight);
+
+    if weight <= BigDecimal::zero() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The weight must be more than 0."
+            })),
+        ));
+    }
+
+    let bale = sqlx::query_as!(
+        Bale,
+        r#"
+        UPDATE bale SET status = $2, weight = $3, closed_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        bale.id,
+        STATUS_CLOSED,
+        weight
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
+
+/// Links a closed bale to the sale it left the business with.
+#[utoipa::path(
+    post,
+    path = "/bale/{bale_id}/sale",
+    params(("bale_id" = String, Path, description = "The bales id.")),
+    request_body = BaleSalePayload,
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(bale_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<BaleSalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bale = find_bale(&app_state, &authenticated_user, &membership, bale_id).await?;
+
+    let sale_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM material_sale WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        payload.sale_id,
+        bale.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !sale_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Sale not found."
+            })),
+        ));
+    }
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    lock_bale(&mut transaction, &bale, STATUS_CLOSED).await?;
+
+    let bale = sqlx::query_as!(
+        Bale,
+        r#"
+        UPDATE bale SET status = $2, sale_id = $3
+        WHERE id = $1
+        RETURNING *
+        "#,
+        bale.id,
+        STATUS_SOLD,
+        payload.sale_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
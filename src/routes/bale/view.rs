@@ -0,0 +1,163 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{bale::Bale, business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{bale_scope, bale_view, find_bale};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BalesQuery {
+    pub business_id: Option<Uuid>,
+    pub status: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/bale",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("status" = Option<String>, Query, description = "Open, Closed or Sold."),
+    ),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn bales(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<BalesQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = bale_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let bales = sqlx::query_as!(
+        Bale,
+        r#"
+        SELECT * FROM bale
+        WHERE ($1::uuid IS NULL OR business_id = $1)
+            AND ($2::text IS NULL OR status = $2)
+        ORDER BY created_at DESC
+        "#,
+        business_id,
+        query.status
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bales": bales
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bale/{bale_id}",
+    params(("bale_id" = String, Path, description = "The bales id.")),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn bale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(bale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let bale = find_bale(&app_state, &authenticated_user, &membership, bale_id).await?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
+
+/// Looks a bale up by the barcode on it.
+#[utoipa::path(
+    get,
+    path = "/bale/code/{code}",
+    params(("code" = String, Path, description = "The barcode on the bale.")),
+    tag = "Bale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn code(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(code): extract::Path<String>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = bale_scope(&authenticated_user, &membership, None)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let bale = sqlx::query_as!(
+        Bale,
+        r#"
+        SELECT * FROM bale WHERE code = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        code.trim(),
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Bale not found."
+        })),
+    ))?;
+
+    let bale = bale_view(&app_state, bale).await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "bale": bale
+        })),
+    ))
+}
@@ -0,0 +1,119 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{branch::Branch, business_member::BusinessMember, user::User},
+    geo::validate_coordinates,
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::branch_scope;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddBranchPayload {
+    pub business_id: Uuid,
+    /// Unique per business.
+    pub name: String,
+    pub phone_number: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+/// Adds a drop-off site to a business.
+#[utoipa::path(
+    post,
+    path = "/branch/add",
+    request_body = AddBranchPayload,
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn branch(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddBranchPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if branch_scope(&authenticated_user, &membership, Some(payload.business_id))?
+        != Some(payload.business_id)
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You may only manage the branches of your own business."
+            })),
+        ));
+    }
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        payload.business_id,
+        "Only owners and managers can add branches.",
+    )?;
+
+    validate_coordinates(payload.latitude, payload.longitude)?;
+
+    let branch = sqlx::query_as!(
+        Branch,
+        r#"
+        INSERT INTO branch (business_id, name, phone_number, address, city, state, zip_code, latitude, longitude)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.name.trim(),
+        payload.phone_number.unwrap_or_default(),
+        payload.address.unwrap_or_default(),
+        payload.city.unwrap_or_default(),
+        payload.state.unwrap_or_default(),
+        payload.zip_code.unwrap_or_default(),
+        payload.latitude,
+        payload.longitude
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A branch with this name already exists."
+            })),
+        ),
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "branch": branch
+        })),
+    ))
+}
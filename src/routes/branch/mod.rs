@@ -0,0 +1,120 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{branch::Branch, business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+pub mod add;
+pub mod price;
+pub mod report;
+pub mod staff;
+pub mod update;
+pub mod view;
+
+/// The business whose branches the user asked for. Business users always get
+/// their own business, staff any business or all of them.
+pub fn branch_scope(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Option<Uuid>,
+) -> Result<Option<Uuid>, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access branches."
+            })),
+        ));
+    }
+
+    Ok(business_scope(authenticated_user, membership)?.or(business_id))
+}
+
+/// Finds a branch the user may see.
+pub async fn find_branch(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    branch_id: Uuid,
+) -> Result<Branch, (StatusCode, Json<Value>)> {
+    let business_id = branch_scope(authenticated_user, membership, None)?;
+
+    sqlx::query_as!(
+        Branch,
+        r#"
+        SELECT * FROM branch WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        branch_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Branch not found."
+        })),
+    ))
+}
+
+/// Checks that collections can be captured at the branch of the business.
+pub async fn require_active_branch(
+    app_state: &AppState,
+    branch_id: Uuid,
+    business_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let active = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM branch WHERE id = $1 AND business_id = $2 AND active) AS "exists!"
+        "#,
+        branch_id,
+        business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if !active {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "No active branch of the business found."
+            })),
+        ));
+    }
+
+    Ok(())
+}
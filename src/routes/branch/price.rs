@@ -0,0 +1,245 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{branch_price::BranchPrice, business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_branch;
+
+/// The price a branch pays for a product.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BranchProductPrice {
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub product_price: BigDecimal,
+    pub branch_price: Option<BigDecimal>,
+    /// The branch price when set, otherwise the product price.
+    pub price: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct SetBranchPricePayload {
+    pub product_id: Uuid,
+    pub price: BigDecimal,
+}
+
+/// The price list of a branch, with every product of the business.
+#[utoipa::path(
+    get,
+    path = "/branch/{branch_id}/price",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn prices(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    let prices = sqlx::query_as!(
+        BranchProductPrice,
+        r#"
+        SELECT
+            product.id AS product_id,
+            product.name AS product_name,
+            product.price AS product_price,
+            branch_price.price AS "branch_price?",
+            COALESCE(branch_price.price, product.price) AS "price!"
+        FROM product
+        LEFT JOIN branch_price ON branch_price.product_id = product.id AND branch_price.branch_id = $1
+        WHERE product.business_id = $2
+        ORDER BY product.name
+        "#,
+        branch.id,
+        branch.business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "prices": prices
+        })),
+    ))
+}
+
+/// Sets the price a branch pays for a product in place of the product price.
+#[utoipa::path(
+    post,
+    path = "/branch/{branch_id}/price",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    request_body = SetBranchPricePayload,
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn set(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<SetBranchPricePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        branch.business_id,
+        "Only owners and managers can set branch prices.",
+    )?;
+
+    if payload.price < BigDecimal::zero() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The price may not be negative."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let product_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM product WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        payload.product_id,
+        branch.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !product_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let price = sqlx::query_as!(
+        BranchPrice,
+        r#"
+        INSERT INTO branch_price (branch_id, product_id, price)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (branch_id, product_id) DO UPDATE SET price = EXCLUDED.price, updated_at = CURRENT_TIMESTAMP
+        RETURNING *
+        "#,
+        branch.id,
+        payload.product_id,
+        payload.price
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "price": price
+        })),
+    ))
+}
+
+/// Goes back to the product price for a product at the branch.
+#[utoipa::path(
+    delete,
+    path = "/branch/{branch_id}/price/{product_id}",
+    params(
+        ("branch_id" = String, Path, description = "The branches id."),
+        ("product_id" = String, Path, description = "The products id."),
+    ),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn remove(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((branch_id, product_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        branch.business_id,
+        "Only owners and managers can set branch prices.",
+    )?;
+
+    let price = sqlx::query_as!(
+        BranchPrice,
+        r#"
+        DELETE FROM branch_price WHERE branch_id = $1 AND product_id = $2
+        RETURNING *
+        "#,
+        branch.id,
+        product_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "The branch has no price for this product."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "price": price
+        })),
+    ))
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::stock::StockQuery,
+    AppState,
+};
+
+use super::branch_scope;
+
+/// What a branch collected over the period.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BranchTotal {
+    pub business_id: Uuid,
+    pub branch_id: Uuid,
+    pub branch_name: String,
+    pub collections: i64,
+    pub collectors: i64,
+    pub weight: BigDecimal,
+    pub purchase_cost: BigDecimal,
+}
+
+/// The branches of a business rolled up.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BusinessTotal {
+    pub business_id: Uuid,
+    pub collections: i64,
+    pub weight: BigDecimal,
+    pub purchase_cost: BigDecimal,
+    pub branches: Vec<BranchTotal>,
+}
+
+/// Collections, weight and purchase cost per branch over the period, rolled up
+/// per business. Branches without collections are included.
+#[utoipa::path(
+    get,
+    path = "/branch/report",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("product_id" = Option<String>, Query, description = "Only collections of this product."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = branch_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let branches = sqlx::query_as!(
+        BranchTotal,
+        r#"
+        SELECT
+            branch.business_id,
+            branch.id AS branch_id,
+            branch.name AS branch_name,
+            COUNT(collection.id) AS "collections!",
+            COUNT(DISTINCT collection.collector_id) AS "collectors!",
+            COALESCE(SUM(collection.weight), 0) AS "weight!",
+            COALESCE(SUM(ROUND(collection.weight * collection.price, 2)), 0) AS "purchase_cost!"
+        FROM branch
+        LEFT JOIN collection ON collection.branch_id = branch.id
+            AND ($2::uuid IS NULL OR collection.product_id = $2)
+            AND ($3::timestamp IS NULL OR collection.created_at >= $3)
+            AND ($4::timestamp IS NULL OR collection.created_at < $4)
+        WHERE ($1::uuid IS NULL OR branch.business_id = $1)
+        GROUP BY branch.business_id, branch.id, branch.name, branch.is_default
+        ORDER BY branch.business_id, branch.is_default DESC, branch.name
+        "#,
+        business_id,
+        query.product_id,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let mut businesses: BTreeMap<Uuid, BusinessTotal> = BTreeMap::new();
+
+    for branch in branches {
+        let business = businesses
+            .entry(branch.business_id)
+            .or_insert_with(|| BusinessTotal {
+                business_id: branch.business_id,
+                collections: 0,
+                weight: BigDecimal::zero(),
+                purchase_cost: BigDecimal::zero(),
+                branches: Vec::new(),
+            });
+
+        business.collections += branch.collections;
+        business.weight += &branch.weight;
+        business.purchase_cost += &branch.purchase_cost;
+        business.branches.push(branch);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "businesses": businesses.into_values().collect::<Vec<_>>()
+        })),
+    ))
+}
@@ -0,0 +1,218 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_branch;
+
+/// A member of the business working at a branch.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BranchStaff {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddBranchStaffPayload {
+    /// A member of the business.
+    pub user_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/branch/{branch_id}/staff",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn staff(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    let staff = sqlx::query_as!(
+        BranchStaff,
+        r#"
+        SELECT users.id AS user_id, users.email, business_member.role, users.active, business_member.created_at
+        FROM business_member
+        INNER JOIN users ON users.id = business_member.user_id
+        WHERE business_member.branch_id = $1
+        ORDER BY users.email
+        "#,
+        branch.id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "staff": staff
+        })),
+    ))
+}
+
+/// Puts a member of the business to work at a branch, where they capture
+/// collections by default.
+#[utoipa::path(
+    post,
+    path = "/branch/{branch_id}/staff",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    request_body = AddBranchStaffPayload,
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn add(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<AddBranchStaffPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        branch.business_id,
+        "Only owners and managers can assign branch staff.",
+    )?;
+
+    let staff = sqlx::query_as!(
+        BranchStaff,
+        r#"
+        WITH business_member AS (
+            UPDATE business_member SET branch_id = $1, updated_at = CURRENT_TIMESTAMP
+            WHERE user_id = $2 AND business_id = $3
+            RETURNING *
+        )
+        SELECT users.id AS user_id, users.email, business_member.role, users.active, business_member.created_at
+        FROM business_member
+        INNER JOIN users ON users.id = business_member.user_id
+        "#,
+        branch.id,
+        payload.user_id,
+        branch.business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "The user is not a member of the business."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "staff": staff
+        })),
+    ))
+}
+
+/// Takes a member off a branch. They stay a member of the business.
+#[utoipa::path(
+    delete,
+    path = "/branch/{branch_id}/staff/{user_id}",
+    params(
+        ("branch_id" = String, Path, description = "The branches id."),
+        ("user_id" = String, Path, description = "The users id."),
+    ),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn remove(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((branch_id, user_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        branch.business_id,
+        "Only owners and managers can assign branch staff.",
+    )?;
+
+    let removed = sqlx::query!(
+        r#"
+        UPDATE business_member SET branch_id = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE branch_id = $1 AND user_id = $2
+        "#,
+        branch.id,
+        user_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .rows_affected();
+
+    if removed == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "The user does not work at this branch."
+            })),
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true
+        })),
+    ))
+}
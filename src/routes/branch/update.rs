@@ -0,0 +1,160 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{branch::Branch, business_member::BusinessMember, user::User},
+    geo::validate_coordinates,
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_branch;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateBranchPayload {
+    pub name: Option<String>,
+    pub phone_number: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Makes this the default branch of the business in place of the current one.
+    pub is_default: Option<bool>,
+    /// Inactive branches take no new collections. The default branch can not be
+    /// deactivated.
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/branch/{branch_id}",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    request_body = UpdateBranchPayload,
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn branch(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateBranchPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        branch.business_id,
+        "Only owners and managers can update branches.",
+    )?;
+
+    validate_coordinates(
+        payload.latitude.or(branch.latitude),
+        payload.longitude.or(branch.longitude),
+    )?;
+
+    if payload.is_default == Some(false) && branch.is_default {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "Make another branch the default branch instead."
+            })),
+        ));
+    }
+
+    let is_default = payload.is_default == Some(true) && !branch.is_default;
+
+    let database_error = |error: sqlx::Error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A branch with this name already exists."
+            })),
+        ),
+        sqlx::Error::Database(error) if error.is_check_violation() => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The default branch can not be deactivated."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    if is_default {
+        sqlx::query!(
+            r#"
+            UPDATE branch SET is_default = FALSE, updated_at = CURRENT_TIMESTAMP
+            WHERE business_id = $1 AND is_default
+            "#,
+            branch.business_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+    }
+
+    let branch = sqlx::query_as!(
+        Branch,
+        r#"
+        UPDATE branch SET
+            name = COALESCE($2, name),
+            phone_number = COALESCE($3, phone_number),
+            address = COALESCE($4, address),
+            city = COALESCE($5, city),
+            state = COALESCE($6, state),
+            zip_code = COALESCE($7, zip_code),
+            latitude = COALESCE($8, latitude),
+            longitude = COALESCE($9, longitude),
+            is_default = is_default OR $10,
+            active = COALESCE($11, active),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        branch.id,
+        payload.name.as_deref().map(str::trim),
+        payload.phone_number,
+        payload.address,
+        payload.city,
+        payload.state,
+        payload.zip_code,
+        payload.latitude,
+        payload.longitude,
+        is_default,
+        payload.active
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "branch": branch
+        })),
+    ))
+}
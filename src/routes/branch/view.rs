@@ -0,0 +1,87 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{branch::Branch, business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{branch_scope, find_branch};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BranchesQuery {
+    pub business_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/branch",
+    params(("business_id" = Option<String>, Query, description = "The businesses id, for staff.")),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn branches(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<BranchesQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = branch_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let branches = sqlx::query_as!(
+        Branch,
+        r#"
+        SELECT * FROM branch
+        WHERE ($1::uuid IS NULL OR business_id = $1)
+        ORDER BY business_id, is_default DESC, name
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "branches": branches
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/branch/{branch_id}",
+    params(("branch_id" = String, Path, description = "The branches id.")),
+    tag = "Branch",
+    security(("bearer_auth" = [])),
+)]
+pub async fn branch(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(branch_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let branch = find_branch(&app_state, &authenticated_user, &membership, branch_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "branch": branch
+        })),
+    ))
+}
@@ -6,7 +6,9 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{business::Business, user::User},
+    data::entities::{business::Business, business_member::BusinessMember, user::User},
+    geo::validate_coordinates,
+    webhooks::{self, BUSINESS_CREATED},
     AppState,
 };
 
@@ -21,6 +23,8 @@ pub struct AddBusinessPayload {
     pub city: String,
     pub state: String,
     pub zip_code: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 #[utoipa::path(
@@ -47,6 +51,8 @@ pub async fn business(
         ));
     }
 
+    validate_coordinates(payload.latitude, payload.longitude)?;
+
     let existing_user = sqlx::query!(
         r#"
         SELECT * FROM users WHERE id = $1
@@ -76,10 +82,10 @@ pub async fn business(
         ));
     }
 
-    let existing_business = sqlx::query_as!(
-        Business,
+    let existing_membership = sqlx::query_as!(
+        BusinessMember,
         r#"
-        SELECT * FROM business_profile WHERE user_id = $1
+        SELECT * FROM business_member WHERE user_id = $1
         "#,
         payload.user_id
     )
@@ -96,21 +102,26 @@ pub async fn business(
         )
     })?;
 
-    if existing_business.is_some() {
-        return Err((
-            StatusCode::BAD_REQUEST,
+    // A user belongs to one business and becomes the owner of the new one.
+    let already_member = || {
+        (
+            StatusCode::CONFLICT,
             Json(json!({
-                "error": "Bad Request",
-                "reason": "User already has a business profile.",
+                "error": "Conflict",
+                "reason": "The user is already a member of a business. Remove them from it before making them the owner of a new business.",
             })),
-        ));
+        )
+    };
+
+    if existing_membership.is_some() {
+        return Err(already_member());
     }
 
     let business = sqlx::query_as!(
         Business,
         r#"
-        INSERT INTO business_profile (user_id, business_name, business_type, business_description, phone_number, address, city, state, zip_code)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        INSERT INTO business_profile (user_id, business_name, business_type, business_description, phone_number, address, city, state, zip_code, latitude, longitude)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
         RETURNING *
         "#,
         payload.user_id,
@@ -121,21 +132,36 @@ pub async fn business(
         payload.address,
         payload.city,
         payload.state,
-        payload.zip_code
+        payload.zip_code,
+        payload.latitude,
+        payload.longitude
     )
     .fetch_one(&app_state.pool)
     .await
-    .map_err(|error| {
-        tracing::error!("🔥 Failed to query database: {}", error);
+    .map_err(|error| match error {
+        // The owner membership is added by a trigger, which fails when the
+        // user joined a business in the meantime.
+        sqlx::Error::Database(error) if error.is_unique_violation() => already_member(),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
 
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(
-                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
-            ),
-        )
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+                ),
+            )
+        }
     })?;
 
+    webhooks::emit(
+        &app_state.pool,
+        BUSINESS_CREATED,
+        Some(business.id),
+        json!(business),
+    )
+    .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::AppState;
+
+const DAYS_OF_WEEK: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DirectoryQuery {
+    /// Only centres that buy a product with this name.
+    pub material: Option<String>,
+    pub city: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AcceptedMaterial {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpeningTimes {
+    pub day_of_week: i16,
+    pub day: &'static str,
+    pub opens_at: String,
+    pub closes_at: String,
+}
+
+/// A listed business with only the details it chose to make public.
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryEntry {
+    pub id: Uuid,
+    pub business_name: String,
+    pub business_type: String,
+    pub business_description: String,
+    pub city: String,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zip_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operating_hours: Option<Vec<OpeningTimes>>,
+    pub materials: Vec<AcceptedMaterial>,
+}
+
+/// Finds the listed businesses, optionally one business or those in a city or
+/// buying a material.
+async fn directory_entries(
+    app_state: &AppState,
+    business_id: Option<Uuid>,
+    query: &DirectoryQuery,
+) -> Result<Vec<DirectoryEntry>, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let businesses = sqlx::query!(
+        r#"
+        SELECT business_profile.*, directory_listing.show_phone_number, directory_listing.show_address,
+            directory_listing.show_operating_hours, directory_listing.show_prices,
+            directory_listing.contact_email, directory_listing.website
+        FROM business_profile
+        INNER JOIN directory_listing ON directory_listing.business_id = business_profile.id
+        WHERE directory_listing.listed
+            AND ($1::uuid IS NULL OR business_profile.id = $1)
+            AND ($2::text IS NULL OR business_profile.city ILIKE $2)
+            AND ($3::text IS NULL OR EXISTS (
+                SELECT 1 FROM product WHERE product.business_id = business_profile.id AND product.name ILIKE $3
+            ))
+        ORDER BY business_profile.business_name
+        "#,
+        business_id,
+        query.city.as_deref().map(str::trim),
+        query.material.as_deref().map(str::trim)
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let business_ids: Vec<Uuid> = businesses.iter().map(|business| business.id).collect();
+
+    let products = sqlx::query!(
+        r#"
+        SELECT business_id, name, description, price FROM product
+        WHERE business_id = ANY($1)
+        ORDER BY name
+        "#,
+        &business_ids
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let operating_hours = sqlx::query!(
+        r#"
+        SELECT business_id, day_of_week, opens_at, closes_at FROM operating_hours
+        WHERE business_id = ANY($1)
+        ORDER BY day_of_week
+        "#,
+        &business_ids
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let mut materials: HashMap<Uuid, Vec<(String, String, BigDecimal)>> = HashMap::new();
+
+    for product in products {
+        materials.entry(product.business_id).or_default().push((
+            product.name,
+            product.description,
+            product.price,
+        ));
+    }
+
+    let mut opening_times: HashMap<Uuid, Vec<OpeningTimes>> = HashMap::new();
+
+    for hours in operating_hours {
+        opening_times
+            .entry(hours.business_id)
+            .or_default()
+            .push(OpeningTimes {
+                day_of_week: hours.day_of_week,
+                day: DAYS_OF_WEEK[(hours.day_of_week - 1) as usize],
+                opens_at: hours.opens_at.format("%H:%M").to_string(),
+                closes_at: hours.closes_at.format("%H:%M").to_string(),
+            });
+    }
+
+    Ok(businesses
+        .into_iter()
+        .map(|business| {
+            let show_address = business.show_address;
+
+            DirectoryEntry {
+                materials: materials
+                    .remove(&business.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, description, price)| AcceptedMaterial {
+                        name,
+                        description,
+                        price: business.show_prices.then_some(price),
+                    })
+                    .collect(),
+                operating_hours: business
+                    .show_operating_hours
+                    .then(|| opening_times.remove(&business.id).unwrap_or_default()),
+                id: business.id,
+                business_name: business.business_name,
+                business_type: business.business_type,
+                business_description: business.business_description,
+                city: business.city,
+                state: business.state,
+                phone_number: business.show_phone_number.then_some(business.phone_number),
+                contact_email: business.contact_email,
+                website: business.website,
+                address: show_address.then_some(business.address),
+                zip_code: show_address.then_some(business.zip_code),
+                latitude: business.latitude.filter(|_| show_address),
+                longitude: business.longitude.filter(|_| show_address),
+            }
+        })
+        .collect())
+}
+
+/// The public directory of buy-back centres with the materials they buy and
+/// when they are open.
+#[utoipa::path(
+    get,
+    path = "/directory",
+    params(
+        ("material" = Option<String>, Query, description = "Only centres buying a product with this name."),
+        ("city" = Option<String>, Query, description = "Only centres in this city."),
+    ),
+    tag = "Directory",
+)]
+pub async fn directory(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<DirectoryQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let businesses = directory_entries(&app_state, None, &query).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "businesses": businesses
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/directory/{business_id}",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    tag = "Directory",
+)]
+pub async fn entry(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(business_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let query = DirectoryQuery {
+        material: None,
+        city: None,
+    };
+
+    let business = directory_entries(&app_state, Some(business_id), &query)
+        .await?
+        .pop()
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "business": business
+        })),
+    ))
+}
@@ -0,0 +1,456 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::{
+        roles::BusinessRole,
+        token::{generate_token, hash_token},
+    },
+    data::entities::{
+        business_invitation::BusinessInvitation, business_member::BusinessMember, user::User,
+    },
+    mailer::Email,
+    AppState,
+};
+
+use super::{member::acting_role, require_business_manager};
+
+/// How long an invitation can be accepted for.
+pub const INVITATION_TTL_DAYS: i64 = 7;
+
+/// Replaces the open invitations of an email to the business with a new one
+/// and emails its link. The email is sent before the transaction commits, so
+/// that an invitation that never went out is not kept.
+async fn send_invitation(
+    app_state: &AppState,
+    transaction: &mut Transaction<'_, Postgres>,
+    business_id: Uuid,
+    email: &str,
+    role: &BusinessRole,
+    branch_id: Option<Uuid>,
+    invited_by: &User,
+) -> Result<BusinessInvitation, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business or branch not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE business_invitation SET revoked_at = CURRENT_TIMESTAMP
+        WHERE business_id = $1 AND email = $2 AND accepted_at IS NULL AND revoked_at IS NULL
+        "#,
+        business_id,
+        email
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let token = generate_token();
+    let expires_at = (Utc::now() + Duration::days(INVITATION_TTL_DAYS)).naive_utc();
+
+    let invitation = sqlx::query_as!(
+        BusinessInvitation,
+        r#"
+        INSERT INTO business_invitation (business_id, email, role, branch_id, token_hash, invited_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        business_id,
+        email,
+        role.to_string(),
+        branch_id,
+        hash_token(&token),
+        invited_by.id,
+        expires_at
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let business_name = sqlx::query_scalar!(
+        r#"
+        SELECT business_name FROM business_profile WHERE id = $1
+        "#,
+        business_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let email = Email {
+        to: invitation.email.clone(),
+        subject: format!("Join {} on 3reco", business_name),
+        body: format!(
+            "{} invited you to join {} on 3reco as {}.\n\nAccept the invitation:\n{}/invitation?token={}\n\nThe link expires on {} UTC.",
+            invited_by.email,
+            business_name,
+            role,
+            app_state.config.app_url,
+            token,
+            expires_at.format("%Y-%m-%d %H:%M")
+        ),
+    };
+
+    app_state.mailer.send(&email).await.map_err(|error| {
+        tracing::error!("🔥 Failed to send invitation email: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to send the invitation email."
+            })),
+        )
+    })?;
+
+    Ok(invitation)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct InviteMemberPayload {
+    pub email: String,
+    pub role: BusinessRole,
+    /// The branch the member will work at.
+    pub branch_id: Option<Uuid>,
+}
+
+/// Invites someone to join the business by email. The link in the email is
+/// redeemed at `/authentication/invitation/accept`. Inviting the same email
+/// again replaces the earlier invitation.
+#[utoipa::path(
+    post,
+    path = "/business/{business_id}/member/invite",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    request_body = InviteMemberPayload,
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn invite(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<InviteMemberPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can invite members.",
+    )?;
+
+    if payload.role == BusinessRole::Owner
+        && acting_role(&authenticated_user, &membership) != BusinessRole::Owner
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "Only owners can invite owners."
+            })),
+        ));
+    }
+
+    let email = payload.email.trim().to_lowercase();
+
+    if !email.contains('@') {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A valid email is required."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let already_member = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM business_member
+            INNER JOIN users ON users.id = business_member.user_id
+            WHERE TRIM(LOWER(users.email)) = $1
+        ) AS "exists!"
+        "#,
+        email
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if already_member {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "This user already belongs to a business."
+            })),
+        ));
+    }
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let invitation = send_invitation(
+        &app_state,
+        &mut transaction,
+        business_id,
+        &email,
+        &payload.role,
+        payload.branch_id,
+        &authenticated_user,
+    )
+    .await?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "invitation": invitation
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/business/{business_id}/member/invitation",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn invitations(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can see invitations.",
+    )?;
+
+    let invitations = sqlx::query_as!(
+        BusinessInvitation,
+        r#"
+        SELECT * FROM business_invitation WHERE business_id = $1 ORDER BY created_at DESC
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "invitations": invitations
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/business/{business_id}/member/invitation/{invitation_id}",
+    params(
+        ("business_id" = String, Path, description = "The businesses id."),
+        ("invitation_id" = String, Path, description = "The invitations id."),
+    ),
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn revoke(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((business_id, invitation_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can revoke invitations.",
+    )?;
+
+    let invitation = sqlx::query_as!(
+        BusinessInvitation,
+        r#"
+        UPDATE business_invitation SET revoked_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND business_id = $2 AND accepted_at IS NULL AND revoked_at IS NULL
+        RETURNING *
+        "#,
+        invitation_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "No open invitation found."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "invitation": invitation
+        })),
+    ))
+}
+
+/// Emails a new link for an invitation that was not accepted, for example when
+/// it expired. The earlier link stops working.
+#[utoipa::path(
+    post,
+    path = "/business/{business_id}/member/invitation/{invitation_id}/resend",
+    params(
+        ("business_id" = String, Path, description = "The businesses id."),
+        ("invitation_id" = String, Path, description = "The invitations id."),
+    ),
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn resend(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((business_id, invitation_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can invite members.",
+    )?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let previous = sqlx::query_as!(
+        BusinessInvitation,
+        r#"
+        SELECT * FROM business_invitation
+        WHERE id = $1 AND business_id = $2 AND accepted_at IS NULL AND revoked_at IS NULL
+        FOR UPDATE
+        "#,
+        invitation_id,
+        business_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "No open invitation found."
+        })),
+    ))?;
+
+    let role = BusinessRole::from(previous.role.as_str());
+
+    if role == BusinessRole::Owner
+        && acting_role(&authenticated_user, &membership) != BusinessRole::Owner
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "Only owners can invite owners."
+            })),
+        ));
+    }
+
+    let invitation = send_invitation(
+        &app_state,
+        &mut transaction,
+        business_id,
+        &previous.email,
+        &role,
+        previous.branch_id,
+        &authenticated_user,
+    )
+    .await?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "invitation": invitation
+        })),
+    ))
+}
@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, directory_listing::DirectoryListing,
+        operating_hours::OperatingHours, user::User,
+    },
+    routes::business::business_scope,
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct OperatingHoursPayload {
+    /// 1 is Monday and 7 is Sunday.
+    pub day_of_week: i16,
+    /// Like 08:00.
+    pub opens_at: String,
+    /// Like 17:30.
+    pub closes_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateListingPayload {
+    /// Whether the business appears in the public directory and on the map.
+    pub listed: Option<bool>,
+    pub show_phone_number: Option<bool>,
+    pub show_address: Option<bool>,
+    pub show_operating_hours: Option<bool>,
+    pub show_prices: Option<bool>,
+    /// An empty string removes the email.
+    pub contact_email: Option<String>,
+    /// An empty string removes the website.
+    pub website: Option<String>,
+    /// Replaces all operating hours, one entry per day the business is open.
+    pub operating_hours: Option<Vec<OperatingHoursPayload>>,
+}
+
+/// Only staff and the business itself manage its listing.
+async fn authorize_business(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let allowed = match authenticated_user.role() {
+        Role::Staff | Role::SystemAdmin => true,
+        Role::Business => business_scope(authenticated_user, membership)? == Some(business_id),
+        _ => false,
+    };
+
+    if !allowed {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to manage this listing."
+            })),
+        ));
+    }
+
+    let exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM business_profile WHERE id = $1) AS "exists!"
+        "#,
+        business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if !exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the hours and parses their times, allowing each day at most once.
+fn parse_operating_hours(
+    operating_hours: &[OperatingHoursPayload],
+) -> Result<Vec<(i16, NaiveTime, NaiveTime)>, String> {
+    let mut days = HashSet::new();
+
+    operating_hours
+        .iter()
+        .map(|hours| {
+            if !(1..=7).contains(&hours.day_of_week) {
+                return Err("day_of_week must be from 1 (Monday) to 7 (Sunday).".to_string());
+            }
+
+            if !days.insert(hours.day_of_week) {
+                return Err(format!(
+                    "Day {} has more than one entry.",
+                    hours.day_of_week
+                ));
+            }
+
+            let parse_time = |time: &str| {
+                NaiveTime::parse_from_str(time.trim(), "%H:%M")
+                    .map_err(|_| format!("{} is not a time like 08:00.", time))
+            };
+
+            let opens_at = parse_time(&hours.opens_at)?;
+            let closes_at = parse_time(&hours.closes_at)?;
+
+            if opens_at >= closes_at {
+                return Err(format!(
+                    "Day {} must close after it opens.",
+                    hours.day_of_week
+                ));
+            }
+
+            Ok((hours.day_of_week, opens_at, closes_at))
+        })
+        .collect()
+}
+
+async fn listing_response(
+    app_state: &AppState,
+    business_id: Uuid,
+) -> Result<Value, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let listing = sqlx::query_as!(
+        DirectoryListing,
+        r#"
+        SELECT * FROM directory_listing WHERE business_id = $1
+        "#,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let operating_hours = sqlx::query_as!(
+        OperatingHours,
+        r#"
+        SELECT * FROM operating_hours WHERE business_id = $1 ORDER BY day_of_week
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok(json!({
+        "success": true,
+        "listing": listing,
+        "operating_hours": operating_hours
+    }))
+}
+
+/// The directory listing of a business, null until the business sets it up,
+/// and its operating hours.
+#[utoipa::path(
+    get,
+    path = "/business/{business_id}/listing",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    tag = "Directory",
+    security(("bearer_auth" = [])),
+)]
+pub async fn listing(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    authorize_business(&app_state, &authenticated_user, &membership, business_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(listing_response(&app_state, business_id).await?),
+    ))
+}
+
+/// Opts the business in or out of the public directory and chooses which of
+/// its details are public. Fields left out keep their value.
+#[utoipa::path(
+    post,
+    path = "/business/{business_id}/listing",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    request_body = UpdateListingPayload,
+    tag = "Directory",
+    security(("bearer_auth" = [])),
+)]
+pub async fn update(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateListingPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    authorize_business(&app_state, &authenticated_user, &membership, business_id).await?;
+
+    let operating_hours = match &payload.operating_hours {
+        Some(operating_hours) => {
+            Some(parse_operating_hours(operating_hours).map_err(|reason| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "Bad Request",
+                        "reason": reason
+                    })),
+                )
+            })?)
+        }
+        None => None,
+    };
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO directory_listing (business_id, listed, show_phone_number, show_address, show_operating_hours, show_prices, contact_email, website)
+        VALUES ($1, COALESCE($2, FALSE), COALESCE($3, TRUE), COALESCE($4, TRUE), COALESCE($5, TRUE), COALESCE($6, TRUE), NULLIF($7, ''), NULLIF($8, ''))
+        ON CONFLICT (business_id) DO UPDATE SET
+            listed = COALESCE($2, directory_listing.listed),
+            show_phone_number = COALESCE($3, directory_listing.show_phone_number),
+            show_address = COALESCE($4, directory_listing.show_address),
+            show_operating_hours = COALESCE($5, directory_listing.show_operating_hours),
+            show_prices = COALESCE($6, directory_listing.show_prices),
+            contact_email = NULLIF(COALESCE($7, directory_listing.contact_email), ''),
+            website = NULLIF(COALESCE($8, directory_listing.website), ''),
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        business_id,
+        payload.listed,
+        payload.show_phone_number,
+        payload.show_address,
+        payload.show_operating_hours,
+        payload.show_prices,
+        payload.contact_email.as_ref().map(|email| email.trim()),
+        payload.website.as_ref().map(|website| website.trim())
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    if let Some(operating_hours) = operating_hours {
+        sqlx::query!(
+            r#"
+            DELETE FROM operating_hours WHERE business_id = $1
+            "#,
+            business_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        for (day_of_week, opens_at, closes_at) in operating_hours {
+            sqlx::query!(
+                r#"
+                INSERT INTO operating_hours (business_id, day_of_week, opens_at, closes_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+                business_id,
+                day_of_week,
+                opens_at,
+                closes_at
+            )
+            .execute(&mut *transaction)
+            .await
+            .map_err(database_error)?;
+        }
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(listing_response(&app_state, business_id).await?),
+    ))
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    geo::{haversine_km, validate_coordinates, KM_PER_DEGREE_LATITUDE},
+    AppState,
+};
+
+const DEFAULT_RADIUS_KM: f64 = 10.0;
+const MAX_RADIUS_KM: f64 = 500.0;
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NearestQuery {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Defaults to 10 km, at most 500 km.
+    pub radius_km: Option<f64>,
+    /// Defaults to 20, at most 100.
+    pub limit: Option<usize>,
+}
+
+/// The public details of a buy-back centre.
+#[derive(Debug, Clone, Serialize)]
+pub struct DropOffPoint {
+    pub id: Uuid,
+    pub business_name: String,
+    pub business_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// The names of the products the centre buys.
+    pub materials: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_km: Option<f64>,
+}
+
+/// Finds the active buy-back centres, those listed in the directory with a
+/// public location and at least one product, optionally only those between two
+/// latitudes.
+async fn drop_off_points(
+    app_state: &AppState,
+    latitudes: Option<(f64, f64)>,
+) -> Result<Vec<DropOffPoint>, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let (minimum_latitude, maximum_latitude) = latitudes.unwrap_or((-90.0, 90.0));
+
+    let businesses = sqlx::query!(
+        r#"
+        SELECT business_profile.*, directory_listing.show_phone_number FROM business_profile
+        INNER JOIN directory_listing ON directory_listing.business_id = business_profile.id
+        WHERE directory_listing.listed
+            AND directory_listing.show_address
+            AND latitude BETWEEN $1 AND $2
+            AND longitude IS NOT NULL
+            AND EXISTS (SELECT 1 FROM product WHERE product.business_id = business_profile.id)
+        "#,
+        minimum_latitude,
+        maximum_latitude
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let business_ids: Vec<Uuid> = businesses.iter().map(|business| business.id).collect();
+
+    let products = sqlx::query!(
+        r#"
+        SELECT business_id, name FROM product WHERE business_id = ANY($1) ORDER BY name
+        "#,
+        &business_ids
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let mut materials: HashMap<Uuid, Vec<String>> = HashMap::new();
+
+    for product in products {
+        materials
+            .entry(product.business_id)
+            .or_default()
+            .push(product.name);
+    }
+
+    Ok(businesses
+        .into_iter()
+        .filter_map(|business| {
+            Some(DropOffPoint {
+                latitude: business.latitude?,
+                longitude: business.longitude?,
+                materials: materials.remove(&business.id).unwrap_or_default(),
+                id: business.id,
+                business_name: business.business_name,
+                business_type: business.business_type,
+                phone_number: business.show_phone_number.then_some(business.phone_number),
+                address: business.address,
+                city: business.city,
+                state: business.state,
+                zip_code: business.zip_code,
+                distance_km: None,
+            })
+        })
+        .collect())
+}
+
+/// Lists the buy-back centres within a radius of a coordinate, nearest first.
+#[utoipa::path(
+    get,
+    path = "/business/nearest",
+    params(
+        ("latitude" = f64, Query, description = "The latitude to search from."),
+        ("longitude" = f64, Query, description = "The longitude to search from."),
+        ("radius_km" = Option<f64>, Query, description = "The search radius in kilometres. Defaults to 10, at most 500."),
+        ("limit" = Option<usize>, Query, description = "The most centres to return. Defaults to 20, at most 100."),
+    ),
+    tag = "Business",
+)]
+pub async fn nearest(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<NearestQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    validate_coordinates(Some(query.latitude), Some(query.longitude))?;
+
+    let radius_km = query.radius_km.unwrap_or(DEFAULT_RADIUS_KM);
+
+    if !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("The radius must be more than 0 and at most {} km.", MAX_RADIUS_KM)
+            })),
+        ));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    // Only centres within the radius north or south can be near, which keeps
+    // the distance calculation to a small part of the table.
+    let latitude_delta = radius_km / KM_PER_DEGREE_LATITUDE;
+    let origin = (query.latitude, query.longitude);
+
+    let mut drop_off_points: Vec<DropOffPoint> = drop_off_points(
+        &app_state,
+        Some((
+            query.latitude - latitude_delta,
+            query.latitude + latitude_delta,
+        )),
+    )
+    .await?
+    .into_iter()
+    .map(|mut drop_off_point| {
+        let distance_km = haversine_km(origin, (drop_off_point.latitude, drop_off_point.longitude));
+        drop_off_point.distance_km = Some((distance_km * 100.0).round() / 100.0);
+        drop_off_point
+    })
+    .filter(|drop_off_point| drop_off_point.distance_km.unwrap_or_default() <= radius_km)
+    .collect();
+
+    drop_off_points.sort_by(|a, b| a.distance_km.partial_cmp(&b.distance_km).unwrap());
+    drop_off_points.truncate(limit);
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "businesses": drop_off_points
+        })),
+    ))
+}
+
+/// Every active buy-back centre as a GeoJSON feature collection, for the
+/// public map.
+#[utoipa::path(get, path = "/business/geojson", tag = "Business")]
+pub async fn geojson(
+    extract::State(app_state): extract::State<AppState>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let features: Vec<Value> = drop_off_points(&app_state, None)
+        .await?
+        .into_iter()
+        .map(|drop_off_point| {
+            json!({
+                "type": "Feature",
+                "id": drop_off_point.id,
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [drop_off_point.longitude, drop_off_point.latitude]
+                },
+                "properties": drop_off_point
+            })
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        (
+            [("content-type", "application/geo+json")],
+            Json(json!({
+                "type": "FeatureCollection",
+                "features": features
+            })),
+        ),
+    ))
+}
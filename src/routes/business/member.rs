@@ -0,0 +1,366 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::{BusinessRole, Role},
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::require_business_manager;
+
+/// A member of a business with their account.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MemberView {
+    pub id: Uuid,
+    pub business_id: Uuid,
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub branch_id: Option<Uuid>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateMemberPayload {
+    pub role: Option<BusinessRole>,
+    /// The branch the member works at.
+    pub branch_id: Option<Uuid>,
+}
+
+/// The role the user acts with in a business. Staff act as owners.
+pub fn acting_role(authenticated_user: &User, membership: &Option<BusinessMember>) -> BusinessRole {
+    match (authenticated_user.role(), membership) {
+        (Role::Staff | Role::SystemAdmin, _) => BusinessRole::Owner,
+        (_, Some(membership)) => membership.role(),
+        (_, None) => BusinessRole::ReadOnly,
+    }
+}
+
+/// Checks that the business keeps at least one owner once the member is
+/// demoted or removed. Locks the owners for the rest of the transaction.
+async fn require_other_owner(
+    transaction: &mut Transaction<'_, Postgres>,
+    business_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let owners = sqlx::query_scalar!(
+        r#"
+        SELECT user_id FROM business_member WHERE business_id = $1 AND role = $2 FOR UPDATE
+        "#,
+        business_id,
+        BusinessRole::Owner.to_string()
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if owners.iter().all(|owner| *owner == user_id) {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A business needs at least one owner."
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/business/{business_id}/member",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn members(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let allowed = match authenticated_user.role() {
+        Role::Staff | Role::SystemAdmin => true,
+        Role::Business => business_scope(&authenticated_user, &membership)? == Some(business_id),
+        _ => false,
+    };
+
+    if !allowed {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You may only see the members of your own business."
+            })),
+        ));
+    }
+
+    let members = sqlx::query_as!(
+        MemberView,
+        r#"
+        SELECT
+            business_member.id,
+            business_member.business_id,
+            business_member.user_id,
+            users.email,
+            business_member.role,
+            business_member.branch_id,
+            users.active,
+            business_member.created_at
+        FROM business_member
+        INNER JOIN users ON users.id = business_member.user_id
+        WHERE business_member.business_id = $1
+        ORDER BY users.email
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "members": members
+        })),
+    ))
+}
+
+/// Changes the role or branch of a member. Only owners can make or change
+/// owners, and the last owner can not be demoted.
+#[utoipa::path(
+    post,
+    path = "/business/{business_id}/member/{user_id}",
+    params(
+        ("business_id" = String, Path, description = "The businesses id."),
+        ("user_id" = String, Path, description = "The members user id."),
+    ),
+    request_body = UpdateMemberPayload,
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn update(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((business_id, user_id)): extract::Path<(Uuid, Uuid)>,
+    extract::Json(payload): extract::Json<UpdateMemberPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can change members.",
+    )?;
+
+    let database_error = |error: sqlx::Error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Branch not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let member = sqlx::query_as!(
+        BusinessMember,
+        r#"
+        SELECT * FROM business_member WHERE business_id = $1 AND user_id = $2 FOR UPDATE
+        "#,
+        business_id,
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Member not found."
+        })),
+    ))?;
+
+    let role = payload.role.unwrap_or(member.role());
+    let touches_owner = member.role() == BusinessRole::Owner || role == BusinessRole::Owner;
+
+    if touches_owner && acting_role(&authenticated_user, &membership) != BusinessRole::Owner {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "Only owners can make or change owners."
+            })),
+        ));
+    }
+
+    if member.role() == BusinessRole::Owner && role != BusinessRole::Owner {
+        require_other_owner(&mut transaction, business_id, user_id).await?;
+    }
+
+    let member = sqlx::query_as!(
+        BusinessMember,
+        r#"
+        UPDATE business_member SET
+            role = $3,
+            branch_id = COALESCE($4, branch_id),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE business_id = $1 AND user_id = $2
+        RETURNING *
+        "#,
+        business_id,
+        user_id,
+        role.to_string(),
+        payload.branch_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "member": member
+        })),
+    ))
+}
+
+/// Removes a member from the business. Their account stays, but no longer
+/// belongs to a business.
+#[utoipa::path(
+    delete,
+    path = "/business/{business_id}/member/{user_id}",
+    params(
+        ("business_id" = String, Path, description = "The businesses id."),
+        ("user_id" = String, Path, description = "The members user id."),
+    ),
+    tag = "Business",
+    security(("bearer_auth" = [])),
+)]
+pub async fn remove(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path((business_id, user_id)): extract::Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can remove members.",
+    )?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let member = sqlx::query_as!(
+        BusinessMember,
+        r#"
+        SELECT * FROM business_member WHERE business_id = $1 AND user_id = $2 FOR UPDATE
+        "#,
+        business_id,
+        user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Member not found."
+        })),
+    ))?;
+
+    if member.role() == BusinessRole::Owner {
+        if acting_role(&authenticated_user, &membership) != BusinessRole::Owner {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "Only owners can remove owners."
+                })),
+            ));
+        }
+
+        require_other_owner(&mut transaction, business_id, user_id).await?;
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM business_member WHERE id = $1
+        "#,
+        member.id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "member": member
+        })),
+    ))
+}
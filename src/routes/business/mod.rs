@@ -1,4 +1,70 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+};
+
 pub mod add;
 pub mod delete;
+pub mod directory;
+pub mod invitation;
+pub mod listing;
+pub mod location;
+pub mod member;
 pub mod update;
 pub mod view;
+
+/// Checks that the user runs the business: staff, or an owner or manager of
+/// the business.
+pub fn require_business_manager(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Uuid,
+    reason: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let manages_business = match authenticated_user.role() {
+        Role::Staff | Role::SystemAdmin => true,
+        Role::Business => membership.as_ref().is_some_and(|membership| {
+            membership.business_id == business_id && membership.role().can_manage()
+        }),
+        _ => false,
+    };
+
+    if !manages_business {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": reason
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Business users may only see their own business, everyone else may see all
+/// businesses. A business user's business is the one they are a member of,
+/// which jwt_guard already looked up.
+pub fn business_scope(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+) -> Result<Option<Uuid>, (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Business {
+        return Ok(None);
+    }
+
+    match membership {
+        Some(membership) => Ok(Some(membership.business_id)),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        )),
+    }
+}
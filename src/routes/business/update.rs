@@ -7,6 +7,8 @@ use uuid::Uuid;
 use crate::{
     authentication::roles::Role,
     data::entities::{business::Business, user::User},
+    geo::validate_coordinates,
+    webhooks::{self, BUSINESS_UPDATED},
     AppState,
 };
 
@@ -20,6 +22,9 @@ pub struct UpdateBusinessPayload {
     pub city: Option<String>,
     pub state: Option<String>,
     pub zip_code: Option<String>,
+    /// Set together with the longitude.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 #[utoipa::path(
@@ -49,6 +54,8 @@ pub async fn business(
         ));
     }
 
+    validate_coordinates(payload.latitude, payload.longitude)?;
+
     let existing_business = sqlx::query_as!(
         Business,
         r#"
@@ -92,13 +99,15 @@ pub async fn business(
     let city = payload.city.unwrap_or(business.city);
     let state = payload.state.unwrap_or(business.state);
     let zip_code = payload.zip_code.unwrap_or(business.zip_code);
+    let latitude = payload.latitude.or(business.latitude);
+    let longitude = payload.longitude.or(business.longitude);
 
     let business = sqlx::query_as!(
         Business,
         r#"
         UPDATE business_profile
-        SET business_name = $1, business_type = $2, business_description = $3, phone_number = $4, address = $5, city = $6, state = $7, zip_code = $8
-        WHERE id = $9
+        SET business_name = $1, business_type = $2, business_description = $3, phone_number = $4, address = $5, city = $6, state = $7, zip_code = $8, latitude = $9, longitude = $10
+        WHERE id = $11
         RETURNING *
         "#,
         business_name,
@@ -109,6 +118,8 @@ pub async fn business(
         city,
         state,
         zip_code,
+        latitude,
+        longitude,
         business.id
     )
     .fetch_one(&app_state.pool)
@@ -125,6 +136,14 @@ pub async fn business(
         )
     })?;
 
+    webhooks::emit(
+        &app_state.pool,
+        BUSINESS_UPDATED,
+        Some(business.id),
+        json!(business),
+    )
+    .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
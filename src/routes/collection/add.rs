@@ -7,7 +7,15 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{collection::Collection, user::User},
+    data::entities::{
+        business_member::BusinessMember, collection::Collection, scale::Scale, user::User,
+    },
+    geo::validate_coordinates,
+    routes::{
+        branch::require_active_branch, business::business_scope,
+        collector::require_approved_collector,
+    },
+    webhooks::{self, COLLECTION_CREATED},
     AppState,
 };
 
@@ -16,7 +24,15 @@ pub struct AddCollectionPayload {
     pub business_id: Uuid,
     pub collector_id: Uuid,
     pub product_id: Uuid,
-    pub weight: BigDecimal,
+    /// Defaults to the branch of the user, or the default branch of the business.
+    pub branch_id: Option<Uuid>,
+    /// Typed in by hand when the collection is not weighed on a connected scale.
+    pub weight: Option<BigDecimal>,
+    /// Takes the current stable weight of the scale instead of `weight`.
+    pub scale_id: Option<Uuid>,
+    /// Where the collection was captured, set together with the longitude.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 #[utoipa::path(
@@ -29,6 +45,7 @@ pub struct AddCollectionPayload {
 pub async fn collection(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Json(payload): extract::Json<AddCollectionPayload>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
     let requirement_a = authenticated_user.role() != Role::Staff
@@ -44,17 +61,97 @@ pub async fn collection(
         ));
     }
 
+    if let Some(business_id) = business_scope(&authenticated_user, &membership)? {
+        if business_id != payload.business_id {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(
+                    json!({ "error": "Unauthorized", "reason": "You may only add collections to your own business." }),
+                ),
+            ));
+        }
+    }
+
+    validate_coordinates(payload.latitude, payload.longitude)?;
+    require_approved_collector(&app_state, payload.collector_id).await?;
+
+    let weight = match (payload.scale_id, payload.weight) {
+        (Some(scale_id), _) => {
+            let scale = sqlx::query_as!(
+                Scale,
+                r#"
+                SELECT * FROM scale WHERE id = $1
+                "#,
+                scale_id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(
+                        json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+                    ),
+                )
+            })?;
+
+            match scale {
+                Some(scale) if scale.business_id == payload.business_id => {}
+                _ => {
+                    return Err((
+                        StatusCode::NOT_FOUND,
+                        Json(json!({ "error": "Not Found", "reason": "Scale not found." })),
+                    ))
+                }
+            }
+
+            app_state
+                .scales
+                .stable_weight(scale_id)
+                .await
+                .map_err(|reason| {
+                    (
+                        StatusCode::CONFLICT,
+                        Json(json!({ "error": "Conflict", "reason": reason })),
+                    )
+                })?
+        }
+        (None, Some(weight)) => weight,
+        (None, None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(
+                    json!({ "error": "Bad Request", "reason": "Either a weight or a scale is required." }),
+                ),
+            ))
+        }
+    };
+
+    let branch_id = payload.branch_id.or(membership
+        .as_ref()
+        .filter(|membership| membership.business_id == payload.business_id)
+        .and_then(|membership| membership.branch_id));
+
+    if let Some(branch_id) = branch_id {
+        require_active_branch(&app_state, branch_id, payload.business_id).await?;
+    }
+
     let collection = sqlx::query_as!(
         Collection,
         r#"
-            INSERT INTO collection (business_id, collector_id, product_id, weight)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO collection (business_id, collector_id, product_id, weight, latitude, longitude, branch_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING *
         "#,
         payload.business_id,
         payload.collector_id,
         payload.product_id,
-        payload.weight,
+        weight,
+        payload.latitude,
+        payload.longitude,
+        branch_id,
     )
     .fetch_one(&app_state.pool)
     .await
@@ -69,6 +166,19 @@ pub async fn collection(
         )
     })?;
 
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_CREATED,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_CREATED, &collection)
+        .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
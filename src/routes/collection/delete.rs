@@ -5,10 +5,14 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{collection::Collection, user::User},
+    data::entities::{business_member::BusinessMember, collection::Collection, user::User},
+    routes::business::business_scope,
+    webhooks::{self, COLLECTION_DELETED},
     AppState,
 };
 
+use super::photo::collection_photos;
+
 #[utoipa::path(
     delete,
     path = "/collection/{collection_id}",
@@ -19,6 +23,7 @@ use crate::{
 pub async fn collection(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Path(collection_id): extract::Path<Uuid>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
     let requirement_a = authenticated_user.role() != Role::SystemAdmin
@@ -62,6 +67,23 @@ pub async fn collection(
 
     let collection = existing_collection.unwrap();
 
+    if business_scope(&authenticated_user, &membership)?
+        .is_some_and(|business_id| business_id != collection.business_id)
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You may only delete collections of your own business." }),
+            ),
+        ));
+    }
+
+    let photo_keys: Vec<String> = collection_photos(&app_state, collection.id)
+        .await?
+        .into_iter()
+        .flat_map(|photo| [photo.storage_key, photo.thumbnail_key])
+        .collect();
+
     sqlx::query!(
         r#"
         DELETE FROM collection WHERE id = $1
@@ -81,6 +103,21 @@ pub async fn collection(
         )
     })?;
 
+    app_state.storage.delete_all(&photo_keys).await;
+
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_DELETED,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_DELETED, &collection)
+        .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
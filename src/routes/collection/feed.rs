@@ -0,0 +1,345 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{
+        self,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::{
+    authentication::{jwt::validate_jwt, roles::Role},
+    data::entities::{
+        business_member::BusinessMember, collection_event::CollectionEvent, user::User,
+    },
+    routes::business::business_scope,
+    AppState,
+};
+
+/// How many missed events are replayed on reconnect before the client is told
+/// to reload instead.
+const MAX_REPLAY: i64 = 1000;
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeedQuery {
+    /// Browsers cannot set headers on a WebSocket, so the JWT may be passed
+    /// here instead of in the Authorization header.
+    pub token: Option<String>,
+    /// Replays the events after this one before streaming new events.
+    pub last_event_id: Option<i64>,
+}
+
+/// The events a connection may see, decided by the role of the user.
+#[derive(Debug, Clone, Copy)]
+struct FeedScope {
+    business_id: Option<Uuid>,
+    collector_id: Option<Uuid>,
+}
+
+impl FeedScope {
+    fn allows(&self, event: &CollectionEvent) -> bool {
+        self.business_id.is_none_or(|id| id == event.business_id)
+            && self.collector_id.is_none_or(|id| id == event.collector_id)
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/collection/feed",
+    params(
+        ("token" = Option<String>, Query, description = "The JWT, when it is not sent in the Authorization header."),
+        ("last_event_id" = Option<i64>, Query, description = "The id of the last event received, to resume after a reconnect."),
+    ),
+    responses((status = 101, description = "Switches to a WebSocket streaming collection events.")),
+    tag = "Collection",
+    security(("bearer_auth" = [])),
+)]
+pub async fn feed(
+    extract::State(app_state): extract::State<AppState>,
+    authenticated_user: Option<extract::Extension<Option<User>>>,
+    extract::Query(query): extract::Query<FeedQuery>,
+    upgrade: WebSocketUpgrade,
+) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    let authenticated_user = match (authenticated_user, &query.token) {
+        (Some(extract::Extension(Some(user))), _) => user,
+        (_, Some(token)) => {
+            let claims = validate_jwt(token, app_state.clone()).await?;
+
+            sqlx::query_as!(
+                User,
+                r#"
+                SELECT * FROM users WHERE email = $1
+                "#,
+                claims.sub
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                )
+            })?
+            .ok_or((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "User not found."
+                })),
+            ))?
+        }
+        _ => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "You must be logged in to follow collections."
+                })),
+            ))
+        }
+    };
+
+    let scope = match authenticated_user.role() {
+        Role::Staff | Role::SystemAdmin => FeedScope {
+            business_id: None,
+            collector_id: None,
+        },
+        Role::Business => {
+            // The feed authenticates itself, so jwt_guard did not look up the
+            // membership.
+            let membership = sqlx::query_as!(
+                BusinessMember,
+                r#"
+                SELECT * FROM business_member WHERE user_id = $1
+                "#,
+                authenticated_user.id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                )
+            })?;
+
+            FeedScope {
+                business_id: business_scope(&authenticated_user, &membership)?,
+                collector_id: None,
+            }
+        }
+        Role::Collector => {
+            let collector = sqlx::query!(
+                r#"
+                SELECT id FROM collector_profile WHERE user_id = $1
+                "#,
+                authenticated_user.id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                )
+            })?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Collector not found."
+                })),
+            ))?;
+
+            FeedScope {
+                business_id: None,
+                collector_id: Some(collector.id),
+            }
+        }
+    };
+
+    Ok(upgrade
+        .on_upgrade(move |socket| stream_events(socket, app_state, scope, query.last_event_id)))
+}
+
+/// Sends the events of the scope until the client goes away. The connection
+/// subscribes before replaying missed events, and skips anything at or before
+/// the last event sent, so that nothing is lost or repeated in between.
+async fn stream_events(
+    mut socket: WebSocket,
+    app_state: AppState,
+    scope: FeedScope,
+    last_event_id: Option<i64>,
+) {
+    let mut receiver = app_state.collection_feed.subscribe();
+
+    let mut cursor = match last_event_id {
+        Some(last_event_id) => last_event_id,
+        None => match latest_event_id(&app_state).await {
+            Some(latest) => latest,
+            None => return,
+        },
+    };
+
+    if last_event_id.is_some()
+        && replay(&mut socket, &app_state, scope, &mut cursor)
+            .await
+            .is_err()
+    {
+        return;
+    }
+
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = heartbeat.tick() => {
+                let message = json!({ "type": "heartbeat", "event_id": cursor });
+
+                if socket.send(Message::Text(message.to_string())).await.is_err() {
+                    return;
+                }
+            }
+            event = receiver.recv() => match event {
+                Ok(event) if event.id > cursor => {
+                    cursor = event.id;
+
+                    if scope.allows(&event) && send_event(&mut socket, &event).await.is_err() {
+                        return;
+                    }
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("🐢 Collection feed lagged by {} events, replaying.", skipped);
+
+                    if replay(&mut socket, &app_state, scope, &mut cursor).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Closed) => return,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+        }
+    }
+}
+
+async fn latest_event_id(app_state: &AppState) -> Option<i64> {
+    let latest = sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(MAX(id), 0) AS "id!" FROM collection_event
+        "#
+    )
+    .fetch_one(&app_state.pool)
+    .await;
+
+    match latest {
+        Ok(latest) => Some(latest),
+        Err(error) => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+            None
+        }
+    }
+}
+
+/// Sends the stored events after the cursor. When too many were missed, or
+/// the missed events were already pruned, the client is told to reload its
+/// collections, and the feed carries on from the latest event.
+async fn replay(
+    socket: &mut WebSocket,
+    app_state: &AppState,
+    scope: FeedScope,
+    cursor: &mut i64,
+) -> Result<(), ()> {
+    let oldest = sqlx::query_scalar!(
+        r#"
+        SELECT MIN(id) FROM collection_event
+        "#
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+    })?;
+
+    let events = sqlx::query_as!(
+        CollectionEvent,
+        r#"
+        SELECT * FROM collection_event
+        WHERE id > $1
+            AND ($2::uuid IS NULL OR business_id = $2)
+            AND ($3::uuid IS NULL OR collector_id = $3)
+        ORDER BY id
+        LIMIT $4
+        "#,
+        *cursor,
+        scope.business_id,
+        scope.collector_id,
+        MAX_REPLAY + 1
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+    })?;
+
+    let pruned = oldest.is_some_and(|oldest| *cursor < oldest - 1);
+
+    if pruned || events.len() as i64 > MAX_REPLAY {
+        *cursor = latest_event_id(app_state).await.ok_or(())?;
+
+        let message = json!({ "type": "resync", "event_id": *cursor });
+
+        return socket
+            .send(Message::Text(message.to_string()))
+            .await
+            .map_err(|_| ());
+    }
+
+    for event in events {
+        send_event(socket, &event).await?;
+        *cursor = event.id;
+    }
+
+    Ok(())
+}
+
+async fn send_event(socket: &mut WebSocket, event: &CollectionEvent) -> Result<(), ()> {
+    let message = json!({
+        "type": event.event_type,
+        "event_id": event.id,
+        "collection": event.collection,
+        "created_at": event.created_at
+    });
+
+    socket
+        .send(Message::Text(message.to_string()))
+        .await
+        .map_err(|_| ())
+}
@@ -1,4 +1,7 @@
 pub mod add;
 pub mod delete;
+pub mod feed;
+pub mod paid;
+pub mod photo;
 pub mod update;
 pub mod view;
@@ -0,0 +1,168 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, collection::Collection, user::User},
+    routes::{business::business_scope, kyc::missing_collector_documents},
+    webhooks::{self, COLLECTION_PAID, COLLECTION_UPDATED},
+    AppState,
+};
+
+/// Records that the collector has been paid for the collection. The collector
+/// must have their required KYC documents verified first.
+#[utoipa::path(
+    post,
+    path = "/collection/{collection_id}/paid",
+    params(("collection_id" = String, Path, description = "The collections id.")),
+    tag = "Collection",
+    security(("bearer_auth" = [])),
+)]
+pub async fn paid(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collection_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to pay collections."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let existing_collection = sqlx::query_as!(
+        Collection,
+        r#"
+        SELECT * FROM collection WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        collection_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let collection = match existing_collection {
+        Some(collection) => collection,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Collection not found."
+                })),
+            ))
+        }
+    };
+
+    if collection.paid_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "The collection has already been paid."
+            })),
+        ));
+    }
+
+    let missing_documents = missing_collector_documents(&app_state.pool, collection.collector_id)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+    if !missing_documents.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!(
+                    "The collector cannot be paid until these documents are verified: {}.",
+                    missing_documents.join(", ")
+                ),
+                "missing_documents": missing_documents
+            })),
+        ));
+    }
+
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+        UPDATE collection
+        SET paid_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND paid_at IS NULL
+        RETURNING *
+        "#,
+        collection.id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::CONFLICT,
+        Json(json!({
+            "error": "Conflict",
+            "reason": "The collection has already been paid."
+        })),
+    ))?;
+
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_PAID,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_UPDATED, &collection)
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "collection": collection
+        })),
+    ))
+}
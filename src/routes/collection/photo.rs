@@ -0,0 +1,629 @@
+use std::io::Cursor;
+
+use axum::{
+    extract::{self, Multipart},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Duration, Utc};
+use image::{DynamicImage, ImageFormat, ImageOutputFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, collection::Collection, collection_photo::CollectionPhoto,
+        user::User,
+    },
+    routes::business::business_scope,
+    storage::{download_signature, verify_download_signature},
+    AppState,
+};
+
+const MAX_PHOTO_SIZE: usize = 10_000_000;
+const MAX_PHOTOS_PER_UPLOAD: usize = 10;
+const THUMBNAIL_SIZE: u32 = 320;
+/// How long the download links in a response stay valid.
+const DOWNLOAD_LINK_MINUTES: i64 = 15;
+
+pub const VARIANT_ORIGINAL: &str = "original";
+pub const VARIANT_THUMBNAIL: &str = "thumbnail";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PhotoView {
+    #[serde(flatten)]
+    pub photo: CollectionPhoto,
+    pub url: String,
+    pub thumbnail_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DownloadQuery {
+    /// Either original or thumbnail, defaults to original.
+    pub variant: Option<String>,
+    pub expires: i64,
+    pub signature: String,
+}
+
+fn download_url(secret: &str, photo_id: Uuid, variant: &str, expires: i64) -> String {
+    let resource = format!("collection_photo/{}/{}", photo_id, variant);
+
+    format!(
+        "/collection/photo/{}/download?variant={}&expires={}&signature={}",
+        photo_id,
+        variant,
+        expires,
+        download_signature(secret, &resource, expires)
+    )
+}
+
+/// Adds short-lived download links to photos.
+pub fn photo_views(app_state: &AppState, photos: Vec<CollectionPhoto>) -> Vec<PhotoView> {
+    let secret = &app_state.config.jwt_secret;
+    let expires = (Utc::now() + Duration::minutes(DOWNLOAD_LINK_MINUTES)).timestamp();
+
+    photos
+        .into_iter()
+        .map(|photo| PhotoView {
+            url: download_url(secret, photo.id, VARIANT_ORIGINAL, expires),
+            thumbnail_url: download_url(secret, photo.id, VARIANT_THUMBNAIL, expires),
+            photo,
+        })
+        .collect()
+}
+
+pub async fn collection_photos(
+    app_state: &AppState,
+    collection_id: Uuid,
+) -> Result<Vec<CollectionPhoto>, (StatusCode, Json<Value>)> {
+    sqlx::query_as!(
+        CollectionPhoto,
+        r#"
+        SELECT * FROM collection_photo WHERE collection_id = $1 ORDER BY created_at
+        "#,
+        collection_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })
+}
+
+/// Finds a collection whose photos the user may manage. Businesses only see
+/// their own collections.
+async fn find_collection(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    collection_id: Uuid,
+) -> Result<Collection, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access collection photos."
+            })),
+        ));
+    }
+
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+        SELECT * FROM collection WHERE id = $1
+        "#,
+        collection_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let business_id = business_scope(authenticated_user, membership)?;
+
+    match collection {
+        Some(collection)
+            if business_id.is_none() || business_id == Some(collection.business_id) =>
+        {
+            Ok(collection)
+        }
+        _ => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Collection not found."
+            })),
+        )),
+    }
+}
+
+/// A decoded upload with its thumbnail.
+struct ProcessedPhoto {
+    extension: &'static str,
+    width: u32,
+    height: u32,
+    thumbnail: Vec<u8>,
+}
+
+/// Checks that the upload really is a JPEG or PNG image of the declared type
+/// and renders its thumbnail.
+fn process_photo(bytes: &[u8], content_type: &str) -> Result<ProcessedPhoto, String> {
+    let (format, extension) = match content_type {
+        "image/jpeg" => (ImageFormat::Jpeg, "jpg"),
+        "image/png" => (ImageFormat::Png, "png"),
+        _ => return Err("Only JPEG and PNG photos are accepted.".to_string()),
+    };
+
+    if image::guess_format(bytes).ok() != Some(format) {
+        return Err(format!("The file is not a valid {} image.", content_type));
+    }
+
+    let photo = image::load_from_memory_with_format(bytes, format)
+        .map_err(|error| format!("The image could not be read: {}", error))?;
+
+    let thumbnail =
+        DynamicImage::ImageRgb8(photo.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgb8());
+    let mut thumbnail_bytes = Cursor::new(Vec::new());
+
+    thumbnail
+        .write_to(&mut thumbnail_bytes, ImageOutputFormat::Jpeg(80))
+        .map_err(|error| format!("The thumbnail could not be created: {}", error))?;
+
+    Ok(ProcessedPhoto {
+        extension,
+        width: photo.width(),
+        height: photo.height(),
+        thumbnail: thumbnail_bytes.into_inner(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/collection/{collection_id}/photo",
+    params(("collection_id" = String, Path, description = "The collections id.")),
+    tag = "Collection",
+    security(("bearer_auth" = [])),
+)]
+pub async fn photos(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collection_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let collection =
+        find_collection(&app_state, &authenticated_user, &membership, collection_id).await?;
+    let photos = collection_photos(&app_state, collection.id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "photos": photo_views(&app_state, photos)
+        })),
+    ))
+}
+
+/// Attaches the photos in the "file" fields of a multipart upload to a
+/// collection. Each photo is stored with a thumbnail.
+#[utoipa::path(
+    post,
+    path = "/collection/{collection_id}/photo",
+    params(("collection_id" = String, Path, description = "The collections id.")),
+    request_body(content = String, description = "A multipart upload with one or more JPEG or PNG photos in \"file\" fields.", content_type = "multipart/form-data"),
+    tag = "Collection",
+    security(("bearer_auth" = [])),
+)]
+pub async fn upload(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collection_id): extract::Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let collection =
+        find_collection(&app_state, &authenticated_user, &membership, collection_id).await?;
+
+    let invalid_upload = |reason: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    let mut uploads = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|error| invalid_upload(format!("Invalid multipart upload: {}", error)))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        if uploads.len() == MAX_PHOTOS_PER_UPLOAD {
+            return Err(invalid_upload(format!(
+                "At most {} photos can be uploaded at once.",
+                MAX_PHOTOS_PER_UPLOAD
+            )));
+        }
+
+        let file_name = field.file_name().unwrap_or("photo").to_string();
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|error| invalid_upload(format!("Failed to read upload: {}", error)))?;
+
+        if bytes.len() > MAX_PHOTO_SIZE {
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(json!({
+                    "error": "Payload Too Large",
+                    "reason": format!("{} is larger than {} MB.", file_name, MAX_PHOTO_SIZE / 1_000_000)
+                })),
+            ));
+        }
+
+        uploads.push((file_name, content_type, bytes.to_vec()));
+    }
+
+    if uploads.is_empty() {
+        return Err(invalid_upload(
+            "The upload must contain a \"file\" field.".to_string(),
+        ));
+    }
+
+    let mut processed = Vec::with_capacity(uploads.len());
+
+    for (file_name, content_type, bytes) in uploads {
+        let (photo, bytes) = tokio::task::spawn_blocking(move || {
+            let photo = process_photo(&bytes, &content_type);
+            (photo, bytes)
+        })
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to process photo: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to process photo."
+                })),
+            )
+        })?;
+
+        let photo = photo.map_err(|reason| invalid_upload(format!("{}: {}", file_name, reason)))?;
+
+        processed.push((file_name, bytes, photo));
+    }
+
+    let storage_error = |error: anyhow::Error| {
+        tracing::error!("🔥 Failed to store photo: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to store photo."
+            })),
+        )
+    };
+
+    let mut stored_keys = Vec::new();
+    let mut photos = Vec::with_capacity(processed.len());
+
+    for (file_name, bytes, processed) in processed {
+        let photo_id = Uuid::new_v4();
+        let content_type = match processed.extension {
+            "png" => "image/png",
+            _ => "image/jpeg",
+        };
+        let storage_key = format!(
+            "collections/{}/{}.{}",
+            collection.id, photo_id, processed.extension
+        );
+        let thumbnail_key = format!("collections/{}/{}-thumbnail.jpg", collection.id, photo_id);
+        let size_bytes = bytes.len() as i64;
+
+        let stored = async {
+            app_state
+                .storage
+                .put(&storage_key, bytes, content_type)
+                .await?;
+            stored_keys.push(storage_key.clone());
+
+            app_state
+                .storage
+                .put(&thumbnail_key, processed.thumbnail, "image/jpeg")
+                .await?;
+            stored_keys.push(thumbnail_key.clone());
+
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+
+        if let Err(error) = stored {
+            app_state.storage.delete_all(&stored_keys).await;
+            return Err(storage_error(error));
+        }
+
+        let photo = sqlx::query_as!(
+            CollectionPhoto,
+            r#"
+            INSERT INTO collection_photo (id, collection_id, storage_key, thumbnail_key, file_name, content_type, size_bytes, width, height, uploaded_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+            photo_id,
+            collection.id,
+            storage_key,
+            thumbnail_key,
+            file_name,
+            content_type,
+            size_bytes,
+            processed.width as i32,
+            processed.height as i32,
+            authenticated_user.id
+        )
+        .fetch_one(&app_state.pool)
+        .await;
+
+        match photo {
+            Ok(photo) => photos.push(photo),
+            Err(error) => {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                let photo_ids: Vec<Uuid> = photos
+                    .iter()
+                    .map(|photo: &CollectionPhoto| photo.id)
+                    .collect();
+                let _ = sqlx::query!(
+                    r#"
+                    DELETE FROM collection_photo WHERE id = ANY($1)
+                    "#,
+                    &photo_ids
+                )
+                .execute(&app_state.pool)
+                .await;
+                app_state.storage.delete_all(&stored_keys).await;
+
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                ));
+            }
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "photos": photo_views(&app_state, photos)
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/collection/photo/{photo_id}",
+    params(("photo_id" = String, Path, description = "The photos id.")),
+    tag = "Collection",
+    security(("bearer_auth" = [])),
+)]
+pub async fn delete(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(photo_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to delete collection photos."
+            })),
+        ));
+    }
+
+    let photo = sqlx::query_as!(
+        CollectionPhoto,
+        r#"
+        SELECT * FROM collection_photo WHERE id = $1
+        "#,
+        photo_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Photo not found."
+        })),
+    ))?;
+
+    find_collection(
+        &app_state,
+        &authenticated_user,
+        &membership,
+        photo.collection_id,
+    )
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM collection_photo WHERE id = $1
+        "#,
+        photo.id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state
+        .storage
+        .delete_all(&[photo.storage_key.clone(), photo.thumbnail_key.clone()])
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "photo": photo
+        })),
+    ))
+}
+
+/// Serves a photo through a signed link from a collection response, so that
+/// it can be shown without sending the JWT.
+#[utoipa::path(
+    get,
+    path = "/collection/photo/{photo_id}/download",
+    params(
+        ("photo_id" = String, Path, description = "The photos id."),
+        ("variant" = Option<String>, Query, description = "Either original or thumbnail."),
+        ("expires" = i64, Query, description = "When the link expires, as a unix timestamp."),
+        ("signature" = String, Query, description = "The signature of the link."),
+    ),
+    tag = "Collection",
+)]
+pub async fn download(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(photo_id): extract::Path<Uuid>,
+    extract::Query(query): extract::Query<DownloadQuery>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let variant = query.variant.as_deref().unwrap_or(VARIANT_ORIGINAL);
+    let resource = format!("collection_photo/{}/{}", photo_id, variant);
+
+    if !verify_download_signature(
+        &app_state.config.jwt_secret,
+        &resource,
+        query.expires,
+        &query.signature,
+    ) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "error": "Forbidden",
+                "reason": "The download link is invalid or has expired."
+            })),
+        ));
+    }
+
+    let photo = sqlx::query_as!(
+        CollectionPhoto,
+        r#"
+        SELECT * FROM collection_photo WHERE id = $1
+        "#,
+        photo_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Photo not found."
+        })),
+    ))?;
+
+    let (key, content_type) = match variant {
+        VARIANT_THUMBNAIL => (&photo.thumbnail_key, "image/jpeg"),
+        _ => (&photo.storage_key, photo.content_type.as_str()),
+    };
+
+    let body = app_state.storage.get(key).await.map_err(|error| {
+        tracing::error!("🔥 Failed to read photo {}: {}", key, error);
+
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Photo file not found."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        (
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (header::CACHE_CONTROL, "private, max-age=900".to_string()),
+            ],
+            body,
+        )
+            .into_response(),
+    ))
+}
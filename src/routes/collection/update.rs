@@ -7,7 +7,12 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{collection::Collection, user::User},
+    data::entities::{business_member::BusinessMember, collection::Collection, user::User},
+    routes::{
+        branch::require_active_branch, business::business_scope,
+        collector::require_approved_collector,
+    },
+    webhooks::{self, COLLECTION_UPDATED},
     AppState,
 };
 
@@ -17,6 +22,9 @@ pub struct UpdateCollectionPayload {
     pub collector_id: Option<Uuid>,
     pub product_id: Option<Uuid>,
     pub weight: Option<BigDecimal>,
+    /// Moving a collection to another business without a branch puts it in the
+    /// default branch of that business.
+    pub branch_id: Option<Uuid>,
 }
 
 #[utoipa::path(
@@ -30,6 +38,7 @@ pub struct UpdateCollectionPayload {
 pub async fn collection(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Path(collection_id): extract::Path<Uuid>,
     extract::Json(payload): extract::Json<UpdateCollectionPayload>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
@@ -81,22 +90,45 @@ pub async fn collection(
     let collection = existing_collection.unwrap();
 
     let business_id = payload.business_id.unwrap_or(collection.business_id);
+
+    if let Some(own_business_id) = business_scope(&authenticated_user, &membership)? {
+        if collection.business_id != own_business_id || business_id != own_business_id {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "You may only change collections of your own business."
+                })),
+            ));
+        }
+    }
+
     let collector_id = payload.collector_id.unwrap_or(collection.collector_id);
     let product_id = payload.product_id.unwrap_or(collection.product_id);
     let weight = payload.weight.unwrap_or(collection.weight);
+    let branch_id = payload.branch_id.unwrap_or(collection.branch_id);
+
+    if let Some(branch_id) = payload.branch_id {
+        require_active_branch(&app_state, branch_id, business_id).await?;
+    }
+
+    if collector_id != collection.collector_id {
+        require_approved_collector(&app_state, collector_id).await?;
+    }
 
     let collection = sqlx::query_as!(
         Collection,
         r#"
         UPDATE collection
-        SET business_id = $1, collector_id = $2, product_id = $3, weight = $4
-        WHERE id = $5
+        SET business_id = $1, collector_id = $2, product_id = $3, weight = $4, branch_id = $5
+        WHERE id = $6
         RETURNING *
         "#,
         business_id,
         collector_id,
         product_id,
         weight,
+        branch_id,
         collection_id
     )
     .fetch_one(&app_state.pool)
@@ -113,6 +145,19 @@ pub async fn collection(
         )
     })?;
 
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_UPDATED,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_UPDATED, &collection)
+        .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
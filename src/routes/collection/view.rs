@@ -10,6 +10,8 @@ use crate::{
     AppState,
 };
 
+use super::photo::{collection_photos, photo_views};
+
 #[utoipa::path(
     get,
     path = "/collection",
@@ -89,7 +91,9 @@ pub async fn collections(
             let business = sqlx::query_as!(
                 Business,
                 r#"
-                SELECT * FROM business_profile WHERE user_id = $1
+                SELECT business_profile.* FROM business_profile
+                INNER JOIN business_member ON business_member.business_id = business_profile.id
+                WHERE business_member.user_id = $1
                 "#,
                 authenticated_user.id
             )
@@ -225,11 +229,15 @@ pub async fn collection(
         ));
     }
 
+    let collection = collection.unwrap();
+    let photos = collection_photos(&app_state, collection.id).await?;
+
     Ok((
         StatusCode::OK,
         Json(json!({
             "success": true,
-            "collection": collection.unwrap()
+            "collection": collection,
+            "photos": photo_views(&app_state, photos)
         })),
     ))
 }
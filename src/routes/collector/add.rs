@@ -7,6 +7,7 @@ use uuid::Uuid;
 use crate::{
     authentication::roles::Role,
     data::entities::{collector::Collector, user::User},
+    webhooks::{self, COLLECTOR_REGISTERED},
     AppState,
 };
 
@@ -24,6 +25,7 @@ pub struct AddCollectorPayload {
     pub bank_name: String,
     pub bank_account_holder: String,
     pub bank_account_number: String,
+    pub collector_code: Option<String>,
 }
 
 #[utoipa::path(
@@ -113,8 +115,8 @@ pub async fn collector(
     let collector = sqlx::query_as!(
         Collector,
         r#"
-        INSERT INTO collector_profile (user_id, first_name, last_name, id_number, phone_number, address, city, state, zip_code, bank_name, bank_account_holder, bank_account_number)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        INSERT INTO collector_profile (user_id, first_name, last_name, id_number, phone_number, address, city, state, zip_code, bank_name, bank_account_holder, bank_account_number, collector_code)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         RETURNING *
         "#,
         payload.user_id,
@@ -128,7 +130,8 @@ pub async fn collector(
         payload.zip_code,
         payload.bank_name,
         payload.bank_account_holder,
-        payload.bank_account_number
+        payload.bank_account_number,
+        payload.collector_code
     )
     .fetch_one(&app_state.pool)
     .await
@@ -143,6 +146,14 @@ pub async fn collector(
         )
     })?;
 
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTOR_REGISTERED,
+        None,
+        json!(collector),
+    )
+    .await;
+
     Ok((
         StatusCode::OK,
         Json(json!({
@@ -1,5 +1,68 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::AppState;
+
 pub mod add;
 pub mod delete;
+pub mod registration;
+pub mod search;
 pub mod update;
 pub mod view;
-pub mod search;
+
+pub const STATUS_PENDING: &str = "Pending";
+pub const STATUS_APPROVED: &str = "Approved";
+pub const STATUS_REJECTED: &str = "Rejected";
+
+/// Phone numbers are compared by their digits and leading plus only.
+pub fn normalise_phone_number(phone_number: &str) -> String {
+    phone_number
+        .chars()
+        .filter(|character| character.is_ascii_digit() || *character == '+')
+        .collect()
+}
+
+/// Collections can only be recorded against approved collectors.
+pub async fn require_approved_collector(
+    app_state: &AppState,
+    collector_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let status = sqlx::query_scalar!(
+        r#"
+        SELECT status FROM collector_profile WHERE id = $1
+        "#,
+        collector_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    match status.as_deref() {
+        Some(STATUS_APPROVED) => Ok(()),
+        Some(status) => Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The collector's registration is {}.", status.to_lowercase())
+            })),
+        )),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Collector not found."
+            })),
+        )),
+    }
+}
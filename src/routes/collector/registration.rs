@@ -0,0 +1,690 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use bcrypt::hash;
+use chrono::{Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::{roles::Role, token::hash_token},
+    data::entities::{
+        business_member::BusinessMember, collector::Collector,
+        phone_verification::PhoneVerification, user::User,
+    },
+    geo::{haversine_km, validate_coordinates},
+    network::client_ip,
+    routes::business::{business_scope, require_business_manager},
+    webhooks::{self, COLLECTOR_REGISTERED},
+    AppState,
+};
+
+use super::{normalise_phone_number, STATUS_APPROVED, STATUS_PENDING, STATUS_REJECTED};
+
+/// How long a verification code can be used for.
+const OTP_TTL_MINUTES: i64 = 10;
+/// How long to wait before another code can be sent to the same number.
+const OTP_RESEND_SECONDS: i64 = 60;
+/// Codes are texted at the platform's expense, so requests are also limited
+/// per number, per client address and overall within an hour.
+const OTP_MAX_PER_NUMBER_PER_HOUR: i64 = 5;
+const OTP_MAX_PER_ADDRESS_PER_HOUR: i64 = 10;
+const OTP_MAX_PER_HOUR: i64 = 500;
+/// Wrong guesses allowed before a new code has to be requested.
+const OTP_MAX_ATTEMPTS: i32 = 5;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct RequestCodePayload {
+    pub phone_number: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct RegisterCollectorPayload {
+    /// The code texted to the phone number.
+    pub code: String,
+    pub email: String,
+    pub password: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub id_number: String,
+    pub phone_number: String,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+    pub bank_name: String,
+    pub bank_account_holder: String,
+    pub bank_account_number: String,
+    /// The business the collector wants to sell to. Defaults to the business
+    /// nearest to the coordinates.
+    pub business_id: Option<Uuid>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegistrationQuery {
+    pub business_id: Option<Uuid>,
+    /// Defaults to Pending.
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ReviewRegistrationPayload {
+    /// Either Approved or Rejected.
+    pub status: String,
+    /// Required when rejecting, shown to the collector.
+    pub reason: Option<String>,
+}
+
+/// Texts a verification code to the phone number, which is needed to register
+/// as a collector.
+#[utoipa::path(
+    post,
+    path = "/registration/collector/code",
+    request_body = RequestCodePayload,
+    tag = "Collector",
+)]
+pub async fn code(
+    extract::State(app_state): extract::State<AppState>,
+    extract::ConnectInfo(peer): extract::ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    extract::Json(payload): extract::Json<RequestCodePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let phone_number = normalise_phone_number(&payload.phone_number);
+
+    if phone_number.trim_start_matches('+').len() < 9 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A valid phone number is required."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let ip_address = client_ip(&headers, peer).to_string();
+    let now = Utc::now().naive_utc();
+
+    let requests = sqlx::query!(
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE phone_number = $1 AND created_at > $3) AS "number_recent!",
+            COUNT(*) FILTER (WHERE phone_number = $1) AS "number!",
+            COUNT(*) FILTER (WHERE ip_address = $2) AS "address!",
+            COUNT(*) AS "total!"
+        FROM phone_verification
+        WHERE created_at > $4
+        "#,
+        phone_number,
+        ip_address,
+        now - Duration::seconds(OTP_RESEND_SECONDS),
+        now - Duration::hours(1)
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let too_many_requests = |reason: &str| {
+        Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({
+                "error": "Too Many Requests",
+                "reason": reason
+            })),
+        ))
+    };
+
+    if requests.number_recent > 0 {
+        return too_many_requests(
+            "A code was sent a moment ago. Please wait before asking for another.",
+        );
+    }
+
+    if requests.number >= OTP_MAX_PER_NUMBER_PER_HOUR
+        || requests.address >= OTP_MAX_PER_ADDRESS_PER_HOUR
+    {
+        return too_many_requests("Too many codes were requested. Please try again later.");
+    }
+
+    if requests.total >= OTP_MAX_PER_HOUR {
+        tracing::warn!(
+            "❗ Refused a verification code, {} were sent in the last hour.",
+            requests.total
+        );
+
+        return too_many_requests(
+            "Verification codes can not be sent right now. Please try again later.",
+        );
+    }
+
+    let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+    let expires_at = (Utc::now() + Duration::minutes(OTP_TTL_MINUTES)).naive_utc();
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let verification = sqlx::query_as!(
+        PhoneVerification,
+        r#"
+        INSERT INTO phone_verification (phone_number, code_hash, expires_at, ip_address)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        phone_number,
+        hash_token(&code),
+        expires_at,
+        ip_address
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let message = format!(
+        "Your 3reco verification code is {}. It expires in {} minutes.",
+        code, OTP_TTL_MINUTES
+    );
+
+    app_state
+        .sms
+        .send(&phone_number, &message)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to send verification code: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to send the verification code."
+                })),
+            )
+        })?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "expires_at": verification.expires_at
+        })),
+    ))
+}
+
+/// Registers a collector with a verified phone number. The account stays
+/// inactive and no collections can be recorded against the collector until
+/// staff or the chosen business approve the registration.
+#[utoipa::path(
+    post,
+    path = "/registration/collector",
+    request_body = RegisterCollectorPayload,
+    tag = "Collector",
+)]
+pub async fn register(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Json(payload): extract::Json<RegisterCollectorPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let email = payload.email.trim().to_lowercase();
+
+    if !email.contains('@') || payload.password.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A valid email and a password are required."
+            })),
+        ));
+    }
+
+    validate_coordinates(payload.latitude, payload.longitude)?;
+
+    let database_error = |error: sqlx::Error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    };
+
+    let phone_number = normalise_phone_number(&payload.phone_number);
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let verification = sqlx::query_as!(
+        PhoneVerification,
+        r#"
+        SELECT * FROM phone_verification
+        WHERE phone_number = $1 AND used_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+        ORDER BY created_at DESC
+        LIMIT 1
+        FOR UPDATE
+        "#,
+        phone_number
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .filter(|verification| verification.attempts < OTP_MAX_ATTEMPTS)
+    .ok_or((
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Bad Request",
+            "reason": "The verification code has expired or was used. Please request a new one."
+        })),
+    ))?;
+
+    if verification.code_hash != hash_token(&payload.code) {
+        sqlx::query!(
+            r#"
+            UPDATE phone_verification SET attempts = attempts + 1 WHERE id = $1
+            "#,
+            verification.id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        transaction.commit().await.map_err(database_error)?;
+
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The verification code is incorrect."
+            })),
+        ));
+    }
+
+    let existing_user = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM users WHERE TRIM(LOWER(email)) = $1) AS "exists!"
+        "#,
+        email
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    if existing_user {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A user with that email already exists."
+            })),
+        ));
+    }
+
+    let review_business_id = match (payload.business_id, payload.latitude, payload.longitude) {
+        (Some(business_id), _, _) => Some(business_id),
+        (None, Some(latitude), Some(longitude)) => {
+            let businesses = sqlx::query!(
+                r#"
+                SELECT id, latitude AS "latitude!", longitude AS "longitude!" FROM business_profile
+                WHERE latitude IS NOT NULL AND longitude IS NOT NULL
+                "#
+            )
+            .fetch_all(&mut *transaction)
+            .await
+            .map_err(database_error)?;
+
+            businesses
+                .iter()
+                .min_by(|a, b| {
+                    haversine_km((latitude, longitude), (a.latitude, a.longitude)).total_cmp(
+                        &haversine_km((latitude, longitude), (b.latitude, b.longitude)),
+                    )
+                })
+                .map(|business| business.id)
+        }
+        _ => None,
+    };
+
+    let hashed_password = hash(&payload.password, 4).map_err(|error| {
+        tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        INSERT INTO users (email, password, role, active) VALUES ($1, $2, $3, FALSE)
+        RETURNING *
+        "#,
+        email,
+        hashed_password,
+        Role::Collector.to_string()
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        INSERT INTO collector_profile (user_id, first_name, last_name, id_number, phone_number, address, city, state, zip_code, bank_name, bank_account_holder, bank_account_number, status, review_business_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        RETURNING *
+        "#,
+        user.id,
+        payload.first_name,
+        payload.last_name,
+        payload.id_number,
+        phone_number,
+        payload.address,
+        payload.city,
+        payload.state,
+        payload.zip_code,
+        payload.bank_name,
+        payload.bank_account_holder,
+        payload.bank_account_number,
+        STATUS_PENDING,
+        review_business_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    sqlx::query!(
+        r#"
+        UPDATE phone_verification SET used_at = CURRENT_TIMESTAMP WHERE id = $1
+        "#,
+        verification.id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "collector": collector
+        })),
+    ))
+}
+
+/// The registrations waiting for a review. Businesses see the registrations
+/// they can review, staff all of them.
+#[utoipa::path(
+    get,
+    path = "/collector/registration",
+    params(
+        ("business_id" = Option<String>, Query, description = "The reviewing businesses id, for staff."),
+        ("status" = Option<String>, Query, description = "Pending, Approved or Rejected. Defaults to Pending."),
+    ),
+    tag = "Collector",
+    security(("bearer_auth" = [])),
+)]
+pub async fn registrations(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<RegistrationQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access user data."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?.or(query.business_id);
+    let status = query.status.unwrap_or(STATUS_PENDING.to_string());
+
+    let collectors = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile
+        WHERE status = $1 AND ($2::uuid IS NULL OR review_business_id = $2)
+        ORDER BY created_at
+        "#,
+        status,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "collectors": collectors
+        })),
+    ))
+}
+
+/// Approves or rejects a pending registration. Approving activates the
+/// collector's account.
+#[utoipa::path(
+    post,
+    path = "/collector/{collector_id}/review",
+    params(("collector_id" = String, Path, description = "The collectors id.")),
+    request_body = ReviewRegistrationPayload,
+    tag = "Collector",
+    security(("bearer_auth" = [])),
+)]
+pub async fn review(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collector_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<ReviewRegistrationPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let reason = payload
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    let rejection_reason = match payload.status.as_str() {
+        STATUS_APPROVED => None,
+        STATUS_REJECTED if reason.is_some() => reason,
+        STATUS_REJECTED => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "A reason is required when rejecting a registration."
+                })),
+            ))
+        }
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "The status must be Approved or Rejected."
+                })),
+            ))
+        }
+    };
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile WHERE id = $1 FOR UPDATE
+        "#,
+        collector_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Collector not found."
+        })),
+    ))?;
+
+    // Businesses review the registrations addressed to them, the rest is left
+    // to staff.
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        match collector.review_business_id {
+            Some(business_id) => require_business_manager(
+                &authenticated_user,
+                &membership,
+                business_id,
+                "Only owners and managers can review registrations.",
+            )?,
+            None => {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    Json(json!({
+                        "error": "Unauthorized",
+                        "reason": "You do not have permission to review this registration."
+                    })),
+                ))
+            }
+        }
+    }
+
+    if collector.status != STATUS_PENDING {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The registration is already {}.", collector.status.to_lowercase())
+            })),
+        ));
+    }
+
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        UPDATE collector_profile
+        SET status = $1, rejection_reason = $2, reviewed_by = $3, reviewed_at = CURRENT_TIMESTAMP
+        WHERE id = $4
+        RETURNING *
+        "#,
+        payload.status,
+        rejection_reason,
+        authenticated_user.id,
+        collector.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    if collector.status == STATUS_APPROVED {
+        sqlx::query!(
+            r#"
+            UPDATE users SET active = TRUE, updated_at = CURRENT_TIMESTAMP WHERE id = $1
+            "#,
+            collector.user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    let message = match &collector.rejection_reason {
+        None => {
+            "Your 3reco collector registration was approved. You can now sell your recyclables."
+                .to_string()
+        }
+        Some(reason) => format!("Your 3reco collector registration was rejected: {}", reason),
+    };
+
+    // The review stands when the collector can not be told about it.
+    if let Err(error) = app_state.sms.send(&collector.phone_number, &message).await {
+        tracing::error!("🔥 Failed to send registration review: {}", error);
+    }
+
+    if collector.status == STATUS_APPROVED {
+        webhooks::emit(
+            &app_state.pool,
+            COLLECTOR_REGISTERED,
+            collector.review_business_id,
+            json!(collector),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "collector": collector
+        })),
+    ))
+}
@@ -21,8 +21,14 @@ pub struct SearchCollector {
     pub bank_name: String,
     pub bank_account_holder: String,
     pub bank_account_number: String,
+    pub collector_code: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: String,
+    pub review_business_id: Option<Uuid>,
+    pub rejection_reason: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<NaiveDateTime>,
     pub email: Option<String>,
 }
 
@@ -66,6 +72,7 @@ pub async fn collector(
             OR first_name ILIKE '%' || $1 || '%'
             OR last_name ILIKE '%' || $1 || '%'
             OR phone_number ILIKE '%' || $1 || '%'
+            OR collector_code ILIKE '%' || $1 || '%'
             OR users.email ILIKE '%' || $1 || '%'
             OR collector_profile.id::text ILIKE '%' || $1 || '%'
         "#,
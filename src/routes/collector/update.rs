@@ -23,6 +23,7 @@ pub struct UpdateCollectorPayload {
     pub bank_name: Option<String>,
     pub bank_account_holder: Option<String>,
     pub bank_account_number: Option<String>,
+    pub collector_code: Option<String>,
 }
 
 #[utoipa::path(
@@ -101,13 +102,14 @@ pub async fn collector(
     let bank_account_number = payload
         .bank_account_number
         .unwrap_or(collector.bank_account_number);
+    let collector_code = payload.collector_code.or(collector.collector_code);
 
     let collector = sqlx::query_as!(
         Collector,
         r#"
         UPDATE collector_profile
-        SET first_name = $1, last_name = $2, id_number = $3, phone_number = $4, address = $5, city = $6, state = $7, zip_code = $8, bank_name = $9, bank_account_holder = $10, bank_account_number = $11
-        WHERE id = $12
+        SET first_name = $1, last_name = $2, id_number = $3, phone_number = $4, address = $5, city = $6, state = $7, zip_code = $8, bank_name = $9, bank_account_holder = $10, bank_account_number = $11, collector_code = $12
+        WHERE id = $13
         RETURNING *
         "#,
         first_name,
@@ -121,6 +123,7 @@ pub async fn collector(
         bank_name,
         bank_account_holder,
         bank_account_number,
+        collector_code,
         collector.id
     )
     .fetch_one(&app_state.pool)
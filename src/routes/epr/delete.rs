@@ -0,0 +1,100 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_report::EprReport, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/epr/report/{epr_report_id}",
+    params(("epr_report_id" = String, Path, description = "The EPR reports id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_report(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(epr_report_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to delete EPR reports." }),
+            ),
+        ));
+    }
+
+    let existing_epr_report = sqlx::query_as!(
+        EprReport,
+        r#"
+        SELECT * FROM epr_report WHERE id = $1
+        "#,
+        epr_report_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_epr_report.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Not Found", "reason": "EPR report not found." })),
+        ));
+    }
+
+    if existing_epr_report.unwrap().submitted_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(
+                json!({ "error": "Conflict", "reason": "Submitted EPR reports can not be deleted." }),
+            ),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM epr_report WHERE id = $1 AND submitted_at IS NULL
+        RETURNING id
+        "#,
+        epr_report_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?
+    .ok_or((
+        StatusCode::CONFLICT,
+        Json(
+            json!({ "error": "Conflict", "reason": "Submitted EPR reports can not be deleted." }),
+        ),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true
+        })),
+    ))
+}
@@ -0,0 +1,65 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_report::EprReport, user::User},
+    AppState,
+};
+
+/// Returns the csv exactly as it was stored when the report was generated.
+#[utoipa::path(
+    get,
+    path = "/epr/report/{epr_report_id}/export",
+    params(("epr_report_id" = String, Path, description = "The EPR reports id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn export(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(epr_report_id): extract::Path<Uuid>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access EPR data."
+            })),
+        ));
+    }
+
+    let epr_report = sqlx::query_as!(
+        EprReport,
+        r#"
+        SELECT * FROM epr_report WHERE id = $1
+        "#,
+        epr_report_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    match epr_report {
+        Some(epr_report) => Ok((StatusCode::OK, epr_report.csv)),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "EPR report not found."
+            })),
+        )),
+    }
+}
@@ -0,0 +1,93 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_report::EprReport, user::User},
+    AppState,
+};
+
+use super::{render_csv, report_rows, EprPeriodQuery};
+
+#[utoipa::path(
+    post,
+    path = "/epr/report/generate",
+    params(
+        ("year" = i32, Query, description = "The reporting year."),
+        ("quarter" = u32, Query, description = "The reporting quarter (1 to 4)."),
+    ),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn generate(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<EprPeriodQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to generate EPR reports."
+            })),
+        ));
+    }
+
+    let (start, end) = query.bounds()?;
+
+    let rows = report_rows(&app_state.pool, start, end)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+    let csv_string = render_csv(&query.label(), &rows);
+    let checksum = format!("{:x}", md5::compute(csv_string.as_bytes()));
+
+    let epr_report = sqlx::query_as!(
+        EprReport,
+        r#"
+        INSERT INTO epr_report (year, quarter, period_start, period_end, rows, csv, checksum, generated_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+        query.year,
+        query.quarter as i32,
+        start,
+        end,
+        json!(rows),
+        csv_string,
+        checksum,
+        authenticated_user.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_report": epr_report
+        })),
+    ))
+}
@@ -0,0 +1,129 @@
+use axum::{http::StatusCode, Json};
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+
+pub mod delete;
+pub mod export;
+pub mod generate;
+pub mod preview;
+pub mod product;
+pub mod submit;
+pub mod view;
+
+/// The stream code used for collections whose product has not been mapped to
+/// an EPR material stream yet.
+pub const UNMAPPED_STREAM_CODE: &str = "UNMAPPED";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EprPeriodQuery {
+    pub year: i32,
+    pub quarter: u32,
+}
+
+impl EprPeriodQuery {
+    /// The reporting period label, e.g. `2024-Q3`.
+    pub fn label(&self) -> String {
+        format!("{}-Q{}", self.year, self.quarter)
+    }
+
+    /// The inclusive start and exclusive end of the calendar quarter.
+    pub fn bounds(&self) -> Result<(NaiveDateTime, NaiveDateTime), (StatusCode, Json<Value>)> {
+        let invalid_period = || {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "Invalid reporting period. The quarter must be between 1 and 4."
+                })),
+            )
+        };
+
+        if !(1..=4).contains(&self.quarter) {
+            return Err(invalid_period());
+        }
+
+        let start_month = (self.quarter - 1) * 3 + 1;
+        let (end_year, end_month) = match self.quarter {
+            4 => (self.year + 1, 1),
+            _ => (self.year, start_month + 3),
+        };
+
+        let start = NaiveDate::from_ymd_opt(self.year, start_month, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(invalid_period)?;
+        let end = NaiveDate::from_ymd_opt(end_year, end_month, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(invalid_period)?;
+
+        Ok((start, end))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EprReportRow {
+    pub province: String,
+    pub stream_code: String,
+    pub stream_name: String,
+    pub collections: i64,
+    pub weight: BigDecimal,
+    pub tonnage: BigDecimal,
+}
+
+/// Aggregates the collection weights of a period by province (taken from the
+/// collecting business) and EPR material stream.
+pub async fn report_rows(
+    pool: &Pool<Postgres>,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Result<Vec<EprReportRow>, sqlx::Error> {
+    sqlx::query_as!(
+        EprReportRow,
+        r#"
+            SELECT
+                COALESCE(NULLIF(TRIM(business.state), ''), 'Unknown') AS "province!",
+                COALESCE(stream.code, $3) AS "stream_code!",
+                COALESCE(stream.name, 'Unmapped') AS "stream_name!",
+                COUNT(collection.id) AS "collections!",
+                SUM(collection.weight) AS "weight!",
+                ROUND(SUM(collection.weight) / 1000, 3) AS "tonnage!"
+            FROM public.collection collection
+            LEFT JOIN public.business_profile business ON business.id = collection.business_id
+            LEFT JOIN public.product product ON product.id = collection.product_id
+            LEFT JOIN public.epr_product_stream mapping ON mapping.product_id = collection.product_id
+            LEFT JOIN public.epr_stream stream ON stream.id = mapping.stream_id
+            WHERE collection.created_at >= $1
+            AND collection.created_at < $2
+            GROUP BY 1, 2, 3
+            ORDER BY 1, 2
+        "#,
+        start,
+        end,
+        UNMAPPED_STREAM_CODE
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Renders report rows in the fixed EPR export format.
+pub fn render_csv(period: &str, rows: &[EprReportRow]) -> String {
+    let mut csv_string = "\"Reporting Period\",\"Province\",\"Material Stream Code\",\"Material Stream\",\"Collections\",\"Weight (kg)\",\"Tonnage (t)\"".to_string();
+
+    for row in rows {
+        csv_string += format!(
+            "\n\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+            period,
+            row.province,
+            row.stream_code,
+            row.stream_name,
+            row.collections,
+            row.weight,
+            row.tonnage,
+        )
+        .as_str();
+    }
+
+    csv_string
+}
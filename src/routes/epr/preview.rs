@@ -0,0 +1,57 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+
+use crate::{authentication::roles::Role, data::entities::user::User, AppState};
+
+use super::{report_rows, EprPeriodQuery};
+
+#[utoipa::path(
+    get,
+    path = "/epr/report/preview",
+    params(
+        ("year" = i32, Query, description = "The reporting year."),
+        ("quarter" = u32, Query, description = "The reporting quarter (1 to 4)."),
+    ),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn preview(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<EprPeriodQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access EPR data."
+            })),
+        ));
+    }
+
+    let (start, end) = query.bounds()?;
+
+    let rows = report_rows(&app_state.pool, start, end)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "period": query.label(),
+            "rows": rows
+        })),
+    ))
+}
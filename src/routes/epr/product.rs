@@ -0,0 +1,269 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_stream::EprStream, product::Product, user::User},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EprProductStream {
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub business_id: Uuid,
+    pub stream_id: Option<Uuid>,
+    pub stream_code: Option<String>,
+    pub stream_name: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AssignEprStreamPayload {
+    pub stream_id: Uuid,
+}
+
+/// Lists every product together with the EPR stream it is mapped to, so that
+/// unmapped products are easy to spot before a report is generated.
+#[utoipa::path(
+    get,
+    path = "/epr/product",
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn products(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access EPR data."
+            })),
+        ));
+    }
+
+    let products = sqlx::query_as!(
+        EprProductStream,
+        r#"
+            SELECT
+                product.id AS product_id,
+                product.name AS product_name,
+                product.business_id AS business_id,
+                stream.id AS "stream_id?",
+                stream.code AS "stream_code?",
+                stream.name AS "stream_name?",
+                mapping.created_at AS "created_at?"
+            FROM public.product product
+            LEFT JOIN public.epr_product_stream mapping ON mapping.product_id = product.id
+            LEFT JOIN public.epr_stream stream ON stream.id = mapping.stream_id
+            ORDER BY product.name
+        "#
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "products": products
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/epr/product/{product_id}",
+    params(("product_id" = String, Path, description = "The products id.")),
+    request_body = AssignEprStreamPayload,
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn assign(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(product_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<AssignEprStreamPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to map products to EPR streams."
+            })),
+        ));
+    }
+
+    let existing_product = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT * FROM product WHERE id = $1
+        "#,
+        product_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_product.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let existing_epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream WHERE id = $1
+        "#,
+        payload.stream_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_epr_stream.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "EPR stream not found."
+            })),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO epr_product_stream (product_id, stream_id)
+        VALUES ($1, $2)
+        ON CONFLICT (product_id) DO UPDATE SET stream_id = EXCLUDED.stream_id, created_at = CURRENT_TIMESTAMP
+        "#,
+        product_id,
+        payload.stream_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "product_id": product_id,
+            "epr_stream": existing_epr_stream.unwrap()
+        })),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/epr/product/{product_id}",
+    params(("product_id" = String, Path, description = "The products id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn unassign(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(product_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to map products to EPR streams."
+            })),
+        ));
+    }
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM epr_product_stream WHERE product_id = $1
+        "#,
+        product_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product is not mapped to an EPR stream."
+            })),
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true
+        })),
+    ))
+}
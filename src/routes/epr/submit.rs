@@ -0,0 +1,117 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_report::EprReport, user::User},
+    AppState,
+};
+
+/// Marks a report as submitted to the producer responsibility organisation.
+/// Submitted reports can no longer be deleted.
+#[utoipa::path(
+    post,
+    path = "/epr/report/{epr_report_id}/submit",
+    params(("epr_report_id" = String, Path, description = "The EPR reports id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn submit(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(epr_report_id): extract::Path<Uuid>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to submit EPR reports."
+            })),
+        ));
+    }
+
+    let existing_epr_report = sqlx::query_as!(
+        EprReport,
+        r#"
+        SELECT * FROM epr_report WHERE id = $1
+        "#,
+        epr_report_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_epr_report.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "EPR report not found."
+            })),
+        ));
+    }
+
+    let epr_report = existing_epr_report.unwrap();
+
+    if epr_report.submitted_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "EPR report has already been submitted."
+            })),
+        ));
+    }
+
+    let epr_report = sqlx::query_as!(
+        EprReport,
+        r#"
+        UPDATE epr_report
+        SET submitted_by = $1, submitted_at = CURRENT_TIMESTAMP
+        WHERE id = $2 AND submitted_at IS NULL
+        RETURNING *
+        "#,
+        authenticated_user.id,
+        epr_report.id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::CONFLICT,
+        Json(json!({
+            "error": "Conflict",
+            "reason": "EPR report has already been submitted."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_report": epr_report
+        })),
+    ))
+}
@@ -0,0 +1,106 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_stream::EprStream, user::User},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddEprStreamPayload {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/epr/stream/add",
+    request_body = AddEprStreamPayload,
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_stream(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Json(payload): extract::Json<AddEprStreamPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to add EPR streams." }),
+            ),
+        ));
+    }
+
+    let code = payload.code.trim().to_uppercase();
+
+    let existing_epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream WHERE code = $1
+        "#,
+        code
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_epr_stream.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "An EPR stream with that code already exists.",
+            })),
+        ));
+    }
+
+    let epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        INSERT INTO epr_stream (code, name, description)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+        code,
+        payload.name,
+        payload.description.unwrap_or_default(),
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_stream": epr_stream,
+        })),
+    ))
+}
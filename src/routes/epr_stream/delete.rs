@@ -0,0 +1,115 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_stream::EprStream, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/epr/stream/{epr_stream_id}",
+    params(("epr_stream_id" = String, Path, description = "The EPR streams id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_stream(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(epr_stream_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to delete EPR streams." }),
+            ),
+        ));
+    }
+
+    let existing_epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream WHERE id = $1
+        "#,
+        epr_stream_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_epr_stream.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Not Found", "reason": "EPR stream not found." })),
+        ));
+    }
+
+    let mapped_products = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "count!" FROM epr_product_stream WHERE stream_id = $1
+        "#,
+        epr_stream_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if mapped_products.count > 0 {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(
+                json!({ "error": "Conflict", "reason": "EPR stream still has products mapped to it." }),
+            ),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM epr_stream WHERE id = $1
+        "#,
+        epr_stream_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true
+        })),
+    ))
+}
@@ -0,0 +1,4 @@
+pub mod add;
+pub mod delete;
+pub mod update;
+pub mod view;
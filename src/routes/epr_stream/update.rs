@@ -0,0 +1,115 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_stream::EprStream, user::User},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateEprStreamPayload {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/epr/stream/{epr_stream_id}",
+    params(("epr_stream_id" = String, Path, description = "The EPR streams id.")),
+    request_body = UpdateEprStreamPayload,
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_stream(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(epr_stream_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateEprStreamPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to update EPR streams."
+            })),
+        ));
+    }
+
+    let existing_epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream WHERE id = $1
+        "#,
+        epr_stream_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_epr_stream.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "EPR stream not found."
+            })),
+        ));
+    }
+
+    let epr_stream = existing_epr_stream.unwrap();
+
+    let name = payload.name.unwrap_or(epr_stream.name);
+    let description = payload.description.unwrap_or(epr_stream.description);
+
+    let epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        UPDATE epr_stream
+        SET name = $1, description = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        RETURNING *
+        "#,
+        name,
+        description,
+        epr_stream.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_stream": epr_stream
+        })),
+    ))
+}
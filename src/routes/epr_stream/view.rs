@@ -0,0 +1,126 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{epr_stream::EprStream, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/epr/stream",
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_streams(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access EPR data."
+            })),
+        ));
+    }
+
+    let epr_streams = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream ORDER BY code
+        "#
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_streams": epr_streams
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/epr/stream/{epr_stream_id}",
+    params(("epr_stream_id" = String, Path, description = "The EPR streams id.")),
+    tag = "EPR",
+    security(("bearer_auth" = [])),
+)]
+pub async fn epr_stream(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(epr_stream_id): extract::Path<Uuid>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access EPR data."
+            })),
+        ));
+    }
+
+    let epr_stream = sqlx::query_as!(
+        EprStream,
+        r#"
+        SELECT * FROM epr_stream WHERE id = $1
+        "#,
+        epr_stream_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if epr_stream.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "EPR stream not found."
+            })),
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "epr_stream": epr_stream.unwrap()
+        })),
+    ))
+}
@@ -1,5 +1,7 @@
 use axum::{extract, http::StatusCode, response::IntoResponse, Json};
 use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
 
 use crate::{authentication::roles::Role, data::entities::user::User, AppState};
 
@@ -23,6 +25,26 @@ pub async fn business(
         ));
     }
 
+    let csv_string = business_csv(&app_state.pool, None).await.map_err(|error| {
+        tracing::error!("🔥 Error while querying businesses: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, csv_string))
+}
+
+/// Renders every business, or only one business, in the business export format.
+pub async fn business_csv(
+    pool: &Pool<Postgres>,
+    business_id: Option<Uuid>,
+) -> Result<String, sqlx::Error> {
     let businesses = sqlx::query!(
         r#"
             SELECT
@@ -38,21 +60,12 @@ pub async fn business(
                 u.updated_at as user_updated_at
             FROM business_profile profile
             LEFT JOIN users u ON profile.user_id = u.id
-        "#
+            WHERE ($1::uuid IS NULL OR profile.id = $1)
+        "#,
+        business_id
     )
-    .fetch_all(&app_state.pool)
-    .await
-    .map_err(|error| {
-        tracing::error!("🔥 Error while querying businesses: {}", error);
-
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": "Internal Server Error",
-                "reason": "Unknown error occured. Please contact the api developer."
-            })),
-        )
-    })?;
+    .fetch_all(pool)
+    .await?;
 
     let mut csv_string =
         format!("\"Id\",\"Email\",\"Name\",\"Type\",\"Description\",\"Phone Number\",\"Address\",\"City\",\"Province\",\"Zip Code\"");
@@ -74,5 +87,5 @@ pub async fn business(
         .as_str();
     }
 
-    Ok((StatusCode::OK, csv_string))
+    Ok(csv_string)
 }
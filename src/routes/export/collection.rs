@@ -1,6 +1,9 @@
 use axum::{extract, http::StatusCode, response::IntoResponse, Json};
 use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
 use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
 
 use crate::{authentication::roles::Role, data::entities::user::User, AppState};
 
@@ -24,13 +27,38 @@ pub async fn collection(
         ));
     }
 
+    let csv_string = collection_csv(&app_state.pool, None, None, None)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Error while querying businesses: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Unknown error occured. Please contact the api developer."
+                })),
+            )
+        })?;
+
+    Ok((StatusCode::OK, csv_string))
+}
+
+/// Renders every collection, or only those of one business, in the collection export format.
+/// Only collections captured from `from` until before `to` are included when given.
+pub async fn collection_csv(
+    pool: &Pool<Postgres>,
+    business_id: Option<Uuid>,
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+) -> Result<String, sqlx::Error> {
     let collections = sqlx::query!(
         r#"
             SELECT 
                 collection.id AS id,
                 collection.weight AS weight,
-                product.price AS price,
-                product.price * collection.weight AS total_price,
+                collection.price AS "price?",
+                collection.price * collection.weight AS total_price,
                 product.name AS product_name,
                 business.business_name AS business_name,
                 business.phone_number AS business_phone_number,
@@ -43,35 +71,34 @@ pub async fn collection(
                 collector.bank_name AS collector_bank_name,
                 collector.bank_account_holder AS collector_bank_account_holder,
                 collector.bank_account_number AS collector_bank_account_number,
-	            collector_user.email AS collector_email
+	            collector_user.email AS collector_email,
+                collection.weight * COALESCE(factor.co2e_per_kg, 0) AS co2e_avoided,
+                collection.weight * COALESCE(factor.landfill_m3_per_kg, 0) AS landfill_diverted,
+                collection.weight * COALESCE(factor.energy_kwh_per_kg, 0) AS energy_saved
             FROM public.collection collection
             LEFT JOIN public.business_profile business ON business.id = collection.business_id
             LEFT JOIN public.users business_user ON business_user.id = business.user_id
             LEFT JOIN public.collector_profile collector ON collector.id = collection.collector_id
             LEFT JOIN public.users collector_user ON collector_user.id = collector.user_id
             LEFT JOIN public.product product ON product.id = collection.product_id
-        "#
+            LEFT JOIN public.impact_factor factor ON factor.product_id = collection.product_id
+            WHERE ($1::uuid IS NULL OR collection.business_id = $1)
+                AND ($2::timestamp IS NULL OR collection.created_at >= $2)
+                AND ($3::timestamp IS NULL OR collection.created_at < $3)
+        "#,
+        business_id,
+        from,
+        to
     )
-    .fetch_all(&app_state.pool)
-    .await
-    .map_err(|error| {
-        tracing::error!("🔥 Error while querying businesses: {}", error);
-
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": "Internal Server Error",
-                "reason": "Unknown error occured. Please contact the api developer."
-            })),
-        )
-    })?;
+    .fetch_all(pool)
+    .await?;
 
     let mut csv_string =
-        format!("\"Id\",\"Product Name\",\"Collection Weight (kg)\",\"Product Price (R)\",\"Collection Total Price (R)\",\"Business Name\",\"Business Phone Number\",\"Business Location\",\"Business Email\",\"Collector Full Name\",\"Collector ID Number\",\"Collector Phone Number\",\"Collector Location\",\"Collector Bank Name\",\"Collector Bank Account Holder\",\"Collector Bank Account Number\",\"Collector Email\"");
+        format!("\"Id\",\"Product Name\",\"Collection Weight (kg)\",\"Product Price (R)\",\"Collection Total Price (R)\",\"Business Name\",\"Business Phone Number\",\"Business Location\",\"Business Email\",\"Collector Full Name\",\"Collector ID Number\",\"Collector Phone Number\",\"Collector Location\",\"Collector Bank Name\",\"Collector Bank Account Holder\",\"Collector Bank Account Number\",\"Collector Email\",\"CO2e Avoided (kg)\",\"Landfill Diverted (m3)\",\"Energy Saved (kWh)\"");
 
     for collection_record in collections {
         csv_string += format!(
-            "\n\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+            "\n\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
             collection_record.id,
             collection_record.product_name.unwrap_or("-".to_string()),
             collection_record.weight,
@@ -89,9 +116,12 @@ pub async fn collection(
             collection_record.collector_bank_account_holder.unwrap_or("-".to_string()),
             collection_record.collector_bank_account_number.unwrap_or("-".to_string()),
             collection_record.collector_email.unwrap_or("-".to_string()),
+            collection_record.co2e_avoided.unwrap_or(BigDecimal::from(0)),
+            collection_record.landfill_diverted.unwrap_or(BigDecimal::from(0)),
+            collection_record.energy_saved.unwrap_or(BigDecimal::from(0)),
         )
         .as_str();
     }
 
-    Ok((StatusCode::OK, csv_string))
+    Ok(csv_string)
 }
@@ -1,5 +1,6 @@
 use axum::{extract, http::StatusCode, response::IntoResponse, Json};
 use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
 
 use crate::{authentication::roles::Role, data::entities::user::User, AppState};
 
@@ -23,6 +24,23 @@ pub async fn collector(
         ));
     }
 
+    let csv_string = collector_csv(&app_state.pool).await.map_err(|error| {
+        tracing::error!("🔥 Error while querying businesses: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, csv_string))
+}
+
+/// Renders every collector in the collector export format.
+pub async fn collector_csv(pool: &Pool<Postgres>) -> Result<String, sqlx::Error> {
     let collectors = sqlx::query!(
         r#"
             SELECT
@@ -40,19 +58,8 @@ pub async fn collector(
             LEFT JOIN users u ON profile.user_id = u.id
         "#
     )
-    .fetch_all(&app_state.pool)
-    .await
-    .map_err(|error| {
-        tracing::error!("🔥 Error while querying businesses: {}", error);
-
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": "Internal Server Error",
-                "reason": "Unknown error occured. Please contact the api developer."
-            })),
-        )
-    })?;
+    .fetch_all(pool)
+    .await?;
 
     let mut csv_string =
         format!("\"Id\",\"Email\",\"First Name\",\"Last Name\",\"ID Number\",\"Phone Number\",\"Address\",\"City\",\"Province\",\"Zip Code\",\"Bank Name\",\"Bank Account Holder\",\"Bank Account Number\"");
@@ -77,5 +84,5 @@ pub async fn collector(
         .as_str();
     }
 
-    Ok((StatusCode::OK, csv_string))
+    Ok(csv_string)
 }
@@ -1,5 +1,7 @@
 use axum::{extract, http::StatusCode, response::IntoResponse, Json};
 use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
 
 use crate::{authentication::roles::Role, data::entities::user::User, AppState};
 
@@ -23,6 +25,26 @@ pub async fn product(
         ));
     }
 
+    let csv_string = product_csv(&app_state.pool, None).await.map_err(|error| {
+        tracing::error!("🔥 Error while querying businesses: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    Ok((StatusCode::OK, csv_string))
+}
+
+/// Renders every product, or only those of one business, in the product export format.
+pub async fn product_csv(
+    pool: &Pool<Postgres>,
+    business_id: Option<Uuid>,
+) -> Result<String, sqlx::Error> {
     let products = sqlx::query!(
         r#"
             SELECT
@@ -33,21 +55,12 @@ pub async fn product(
             FROM public.product product
             LEFT JOIN business_profile business ON business.id = product.business_id
             LEFT JOIN users business_user ON business_user.id = business.user_id
-        "#
+            WHERE ($1::uuid IS NULL OR product.business_id = $1)
+        "#,
+        business_id
     )
-    .fetch_all(&app_state.pool)
-    .await
-    .map_err(|error| {
-        tracing::error!("🔥 Error while querying businesses: {}", error);
-
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "error": "Internal Server Error",
-                "reason": "Unknown error occured. Please contact the api developer."
-            })),
-        )
-    })?;
+    .fetch_all(pool)
+    .await?;
 
     let mut csv_string: String =
         format!("\"Id\",\"Name\",\"Description\",\"Price (R)\",\"Business Name\",\"Business Phone Number\",\"Business Email\"");
@@ -60,11 +73,13 @@ pub async fn product(
             product_record.description,
             product_record.price,
             product_record.business_name.unwrap_or("-".to_string()),
-            product_record.business_phone_number.unwrap_or("-".to_string()),
+            product_record
+                .business_phone_number
+                .unwrap_or("-".to_string()),
             product_record.business_email.unwrap_or("-".to_string())
         )
         .as_str();
     }
 
-    Ok((StatusCode::OK, csv_string))
+    Ok(csv_string)
 }
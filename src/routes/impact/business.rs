@@ -0,0 +1,104 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::ImpactQuery;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BusinessImpact {
+    pub business_id: Uuid,
+    pub business_name: Option<String>,
+    pub collections: i64,
+    pub weight: BigDecimal,
+    pub co2e_avoided: BigDecimal,
+    pub landfill_diverted: BigDecimal,
+    pub energy_saved: BigDecimal,
+}
+
+#[utoipa::path(
+    get,
+    path = "/impact/business",
+    params(
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn business(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<ImpactQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access impact data."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let impact = sqlx::query_as!(
+        BusinessImpact,
+        r#"
+            SELECT
+                collection.business_id AS business_id,
+                business.business_name AS "business_name?",
+                COUNT(collection.id) AS "collections!",
+                SUM(collection.weight) AS "weight!",
+                SUM(collection.weight * COALESCE(factor.co2e_per_kg, 0)) AS "co2e_avoided!",
+                SUM(collection.weight * COALESCE(factor.landfill_m3_per_kg, 0)) AS "landfill_diverted!",
+                SUM(collection.weight * COALESCE(factor.energy_kwh_per_kg, 0)) AS "energy_saved!"
+            FROM public.collection collection
+            LEFT JOIN public.business_profile business ON business.id = collection.business_id
+            LEFT JOIN public.product product ON product.id = collection.product_id
+            LEFT JOIN public.impact_factor factor ON factor.product_id = collection.product_id
+            WHERE ($1::uuid IS NULL OR collection.business_id = $1)
+            AND ($2::timestamp IS NULL OR collection.created_at >= $2)
+            AND ($3::timestamp IS NULL OR collection.created_at < $3)
+            GROUP BY collection.business_id, business.business_name
+            ORDER BY business.business_name
+        "#,
+        business_id,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "impact": impact
+        })),
+    ))
+}
@@ -0,0 +1,108 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CollectionImpact {
+    pub collection_id: Uuid,
+    pub business_id: Uuid,
+    pub collector_id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: Option<String>,
+    pub weight: BigDecimal,
+    pub co2e_avoided: BigDecimal,
+    pub landfill_diverted: BigDecimal,
+    pub energy_saved: BigDecimal,
+}
+
+#[utoipa::path(
+    get,
+    path = "/impact/collection/{collection_id}",
+    params(("collection_id" = String, Path, description = "The collections id.")),
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collection(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(collection_id): extract::Path<Uuid>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access impact data."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let impact = sqlx::query_as!(
+        CollectionImpact,
+        r#"
+            SELECT
+                collection.id AS collection_id,
+                collection.business_id AS business_id,
+                collection.collector_id AS collector_id,
+                collection.product_id AS product_id,
+                product.name AS "product_name?",
+                collection.weight AS weight,
+                collection.weight * COALESCE(factor.co2e_per_kg, 0) AS "co2e_avoided!",
+                collection.weight * COALESCE(factor.landfill_m3_per_kg, 0) AS "landfill_diverted!",
+                collection.weight * COALESCE(factor.energy_kwh_per_kg, 0) AS "energy_saved!"
+            FROM public.collection collection
+            LEFT JOIN public.product product ON product.id = collection.product_id
+            LEFT JOIN public.impact_factor factor ON factor.product_id = collection.product_id
+            WHERE collection.id = $1
+            AND ($2::uuid IS NULL OR collection.business_id = $2)
+        "#,
+        collection_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if impact.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Collection not found."
+            })),
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "impact": impact.unwrap()
+        })),
+    ))
+}
@@ -0,0 +1,30 @@
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+pub mod business;
+pub mod collection;
+pub mod collector;
+pub mod period;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImpactQuery {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub interval: Option<String>,
+}
+
+impl ImpactQuery {
+    /// The inclusive start of the requested period.
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        self.start_date
+            .and_then(|start_date| start_date.and_hms_opt(0, 0, 0))
+    }
+
+    /// The exclusive end of the requested period, which is the start of the day
+    /// after `end_date`.
+    pub fn end(&self) -> Option<NaiveDateTime> {
+        self.end_date
+            .and_then(|end_date| end_date.checked_add_days(Days::new(1)))
+            .and_then(|end_date| end_date.and_hms_opt(0, 0, 0))
+    }
+}
@@ -0,0 +1,118 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::ImpactQuery;
+
+const INTERVALS: [&str; 5] = ["day", "week", "month", "quarter", "year"];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PeriodImpact {
+    pub period: Option<NaiveDateTime>,
+    pub collections: i64,
+    pub weight: BigDecimal,
+    pub co2e_avoided: BigDecimal,
+    pub landfill_diverted: BigDecimal,
+    pub energy_saved: BigDecimal,
+}
+
+#[utoipa::path(
+    get,
+    path = "/impact/period",
+    params(
+        ("interval" = Option<String>, Query, description = "One of day, week, month, quarter or year. Defaults to month."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn period(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<ImpactQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access impact data."
+            })),
+        ));
+    }
+
+    let interval = query.interval.clone().unwrap_or("month".to_string());
+
+    if !INTERVALS.contains(&interval.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "Interval must be one of day, week, month, quarter or year."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let impact = sqlx::query_as!(
+        PeriodImpact,
+        r#"
+            SELECT
+                date_trunc($1, collection.created_at) AS period,
+                COUNT(collection.id) AS "collections!",
+                SUM(collection.weight) AS "weight!",
+                SUM(collection.weight * COALESCE(factor.co2e_per_kg, 0)) AS "co2e_avoided!",
+                SUM(collection.weight * COALESCE(factor.landfill_m3_per_kg, 0)) AS "landfill_diverted!",
+                SUM(collection.weight * COALESCE(factor.energy_kwh_per_kg, 0)) AS "energy_saved!"
+            FROM public.collection collection
+            LEFT JOIN public.product product ON product.id = collection.product_id
+            LEFT JOIN public.impact_factor factor ON factor.product_id = collection.product_id
+            WHERE ($2::uuid IS NULL OR collection.business_id = $2)
+            AND ($3::timestamp IS NULL OR collection.created_at >= $3)
+            AND ($4::timestamp IS NULL OR collection.created_at < $4)
+            GROUP BY period
+            ORDER BY period
+        "#,
+        interval,
+        business_id,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "interval": interval,
+            "impact": impact
+        })),
+    ))
+}
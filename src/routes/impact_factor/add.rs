@@ -0,0 +1,145 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{impact_factor::ImpactFactor, product::Product, user::User},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddImpactFactorPayload {
+    pub product_id: Uuid,
+    pub co2e_per_kg: BigDecimal,
+    pub landfill_m3_per_kg: BigDecimal,
+    pub energy_kwh_per_kg: BigDecimal,
+}
+
+#[utoipa::path(
+    post,
+    path = "/impact/factor/add",
+    request_body = AddImpactFactorPayload,
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn impact_factor(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Json(payload): extract::Json<AddImpactFactorPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to add impact factors." }),
+            ),
+        ));
+    }
+
+    let existing_product = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT * FROM product WHERE id = $1
+        "#,
+        payload.product_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_product.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Not Found", "reason": "Product not found." })),
+        ));
+    }
+
+    let existing_impact_factor = sqlx::query_as!(
+        ImpactFactor,
+        r#"
+        SELECT * FROM impact_factor WHERE product_id = $1
+        "#,
+        payload.product_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_impact_factor.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "Product already has an impact factor.",
+            })),
+        ));
+    }
+
+    let impact_factor = sqlx::query_as!(
+        ImpactFactor,
+        r#"
+        INSERT INTO impact_factor (product_id, co2e_per_kg, landfill_m3_per_kg, energy_kwh_per_kg)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        payload.product_id,
+        payload.co2e_per_kg,
+        payload.landfill_m3_per_kg,
+        payload.energy_kwh_per_kg,
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        // Another request added one since the check above.
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "Product already has an impact factor.",
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(
+                    json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+                ),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "impact_factor": impact_factor,
+        })),
+    ))
+}
@@ -0,0 +1,87 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{impact_factor::ImpactFactor, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/impact/factor/{impact_factor_id}",
+    params(("impact_factor_id" = String, Path, description = "The impact factors id.")),
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn impact_factor(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(impact_factor_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to delete impact factors." }),
+            ),
+        ));
+    }
+
+    let existing_impact_factor = sqlx::query_as!(
+        ImpactFactor,
+        r#"
+        SELECT * FROM impact_factor WHERE id = $1
+        "#,
+        impact_factor_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    if existing_impact_factor.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Not Found", "reason": "Impact factor not found." })),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM impact_factor WHERE id = $1
+        "#,
+        impact_factor_id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(
+                json!({ "error": "Internal Server Error", "reason": "Failed to query database." }),
+            ),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true
+        })),
+    ))
+}
@@ -0,0 +1,123 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{impact_factor::ImpactFactor, user::User},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateImpactFactorPayload {
+    pub co2e_per_kg: Option<BigDecimal>,
+    pub landfill_m3_per_kg: Option<BigDecimal>,
+    pub energy_kwh_per_kg: Option<BigDecimal>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/impact/factor/{impact_factor_id}",
+    params(("impact_factor_id" = String, Path, description = "The impact factors id.")),
+    request_body = UpdateImpactFactorPayload,
+    tag = "Impact",
+    security(("bearer_auth" = [])),
+)]
+pub async fn impact_factor(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(impact_factor_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateImpactFactorPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to update impact factors."
+            })),
+        ));
+    }
+
+    let existing_impact_factor = sqlx::query_as!(
+        ImpactFactor,
+        r#"
+        SELECT * FROM impact_factor WHERE id = $1
+        "#,
+        impact_factor_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_impact_factor.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Impact factor not found."
+            })),
+        ));
+    }
+
+    let impact_factor = existing_impact_factor.unwrap();
+
+    let co2e_per_kg = payload.co2e_per_kg.unwrap_or(impact_factor.co2e_per_kg);
+    let landfill_m3_per_kg = payload
+        .landfill_m3_per_kg
+        .unwrap_or(impact_factor.landfill_m3_per_kg);
+    let energy_kwh_per_kg = payload
+        .energy_kwh_per_kg
+        .unwrap_or(impact_factor.energy_kwh_per_kg);
+
+    let impact_factor = sqlx::query_as!(
+        ImpactFactor,
+        r#"
+        UPDATE impact_factor
+        SET co2e_per_kg = $1, landfill_m3_per_kg = $2, energy_kwh_per_kg = $3, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $4
+        RETURNING *
+        "#,
+        co2e_per_kg,
+        landfill_m3_per_kg,
+        energy_kwh_per_kg,
+        impact_factor.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "impact_factor": impact_factor
+        })),
+    ))
+}
@@ -0,0 +1,200 @@
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use bcrypt::hash;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    authentication::{roles::Role, token::generate_token},
+    data::entities::{business::Business, user::User},
+    routes::users::send_activation,
+    webhooks::{self, BUSINESS_CREATED},
+    AppState,
+};
+
+use super::{
+    check_emails, parse_rows, read_upload, require, validation_response, ImportQuery,
+    ImportRowError,
+};
+
+const COLUMNS: [&str; 9] = [
+    "email",
+    "business_name",
+    "business_type",
+    "business_description",
+    "phone_number",
+    "address",
+    "city",
+    "state",
+    "zip_code",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BusinessImportRow {
+    pub email: String,
+    pub business_name: String,
+    pub business_type: String,
+    pub business_description: String,
+    pub phone_number: String,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+}
+
+/// Creates a user with role "Business" and a business profile for every row
+/// of the uploaded csv. Every row is validated first and nothing is created
+/// unless all rows are valid. The users are created inactive and emailed a
+/// link to choose their password, like invited users.
+#[utoipa::path(
+    post,
+    path = "/import/business",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only validate the upload."),
+        ("report" = Option<String>, Query, description = "Set to csv to download the row errors as a csv."),
+    ),
+    request_body(content = String, description = "A multipart upload with the csv in the \"file\" field. Columns: email, business_name, business_type, business_description, phone_number, address, city, state, zip_code.", content_type = "multipart/form-data"),
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn business(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Query(query): extract::Query<ImportQuery>,
+    multipart: extract::Multipart,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to import businesses."
+            })),
+        ));
+    }
+
+    let upload = read_upload(multipart).await?;
+
+    let mut errors: Vec<ImportRowError> = Vec::new();
+    let rows = parse_rows::<BusinessImportRow>(&upload.bytes, &COLUMNS, &mut errors)?;
+
+    for (row, record) in &rows {
+        require(*row, "email", &record.email, &mut errors);
+        require(*row, "business_name", &record.business_name, &mut errors);
+        require(*row, "business_type", &record.business_type, &mut errors);
+        require(*row, "phone_number", &record.phone_number, &mut errors);
+        require(*row, "address", &record.address, &mut errors);
+        require(*row, "city", &record.city, &mut errors);
+        require(*row, "state", &record.state, &mut errors);
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let emails: Vec<(usize, String)> = rows
+        .iter()
+        .map(|(row, record)| (*row, record.email.clone()))
+        .collect();
+
+    check_emails(&app_state.pool, &emails, &mut errors)
+        .await
+        .map_err(database_error)?;
+
+    if query.dry_run() || !errors.is_empty() {
+        return validation_response(&query, "business-import-errors.csv", rows.len(), errors);
+    }
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+    let mut businesses: Vec<Business> = Vec::new();
+
+    for (_, record) in rows {
+        // The account can not be logged into until the user chooses a
+        // password through the activation link.
+        let hashed_password = hash(generate_token(), 4).map_err(|error| {
+            tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Unknown error occured. Please contact the api developer."
+                })),
+            )
+        })?;
+
+        let user = sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (email, password, role, active) VALUES ($1, $2, $3, FALSE) RETURNING *"#,
+            record.email,
+            hashed_password,
+            Role::Business.to_string()
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        send_activation(&app_state, &mut transaction, &user, &authenticated_user).await?;
+
+        let business = sqlx::query_as!(
+            Business,
+            r#"
+            INSERT INTO business_profile (user_id, business_name, business_type, business_description, phone_number, address, city, state, zip_code)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+            user.id,
+            record.business_name,
+            record.business_type,
+            record.business_description,
+            record.phone_number,
+            record.address,
+            record.city,
+            record.state,
+            record.zip_code
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        businesses.push(business);
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    for business in &businesses {
+        webhooks::emit(
+            &app_state.pool,
+            BUSINESS_CREATED,
+            Some(business.id),
+            json!(business),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "dry_run": false,
+            "total_rows": businesses.len(),
+            "businesses": businesses
+        }))
+        .into_response(),
+    ))
+}
@@ -0,0 +1,528 @@
+use std::{collections::HashSet, str::FromStr};
+
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, collection::Collection,
+        collection_import_row::CollectionImportRow, collector::Collector, product::Product,
+        user::User,
+    },
+    routes::{
+        business::{business_scope, require_business_manager},
+        collector::{normalise_phone_number, STATUS_APPROVED},
+    },
+    webhooks::{self, COLLECTION_CREATED},
+    AppState,
+};
+
+use super::{parse_rows, read_upload, ImportRowError, ParsedRows, Upload};
+
+pub const STATUS_IMPORTED: &str = "Imported";
+pub const STATUS_PENDING: &str = "Pending";
+pub const STATUS_RESOLVED: &str = "Resolved";
+pub const STATUS_DISCARDED: &str = "Discarded";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CollectionImportQuery {
+    /// Required for staff, business users always import into their own business.
+    pub business_id: Option<Uuid>,
+    pub dry_run: Option<bool>,
+}
+
+/// A single weighing as exported by the scale software.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct Weighing {
+    /// The ticket number of the scale, if it has one.
+    #[serde(default)]
+    pub reference: Option<String>,
+    /// The id number, phone number or collector code of the collector.
+    pub collector: String,
+    /// The name or code of the product.
+    pub product: String,
+    pub weight: String,
+    #[serde(default)]
+    pub weighed_at: Option<String>,
+}
+
+impl Weighing {
+    /// Identifies the weighing regardless of the file it was uploaded in, so
+    /// that re-uploading an export does not create the collections again.
+    ///
+    /// Only weighings with a reference or time can be identified, two
+    /// weighings of the same weight are otherwise indistinguishable.
+    pub fn hash(&self, business_id: Uuid) -> Option<String> {
+        let present = |value: &Option<String>| {
+            value
+                .as_deref()
+                .is_some_and(|value| !value.trim().is_empty())
+        };
+
+        if !present(&self.reference) && !present(&self.weighed_at) {
+            return None;
+        }
+
+        let key = format!(
+            "{}|{}|{}|{}|{}|{}",
+            business_id,
+            self.reference.as_deref().unwrap_or("").to_lowercase(),
+            self.collector.to_lowercase(),
+            self.product.to_lowercase(),
+            BigDecimal::from_str(&self.weight)
+                .map(|weight| weight.normalized().to_string())
+                .unwrap_or(self.weight.clone()),
+            self.weighed_at.as_deref().unwrap_or("")
+        );
+
+        Some(format!("{:x}", md5::compute(key)))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WeighingOutcome {
+    pub row: usize,
+    pub status: String,
+    pub reason: Option<String>,
+    pub collection_id: Option<Uuid>,
+}
+
+/// Resolves the business an import or staged row belongs to.
+pub fn import_business(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Option<Uuid>,
+) -> Result<Uuid, (StatusCode, Json<Value>)> {
+    business_scope(authenticated_user, membership)?
+        .or(business_id)
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A business_id is required."
+            })),
+        ))
+}
+
+/// Reads the weighings of a csv or json upload. Json uploads are an array of
+/// objects with the same fields as the csv columns.
+fn parse_weighings(
+    upload: &Upload,
+    errors: &mut Vec<ImportRowError>,
+) -> Result<ParsedRows<Weighing>, (StatusCode, Json<Value>)> {
+    let is_json = upload.file_name.to_lowercase().ends_with(".json")
+        || upload.content_type.as_deref() == Some("application/json");
+
+    if !is_json {
+        return parse_rows::<Weighing>(&upload.bytes, &["collector", "product", "weight"], errors);
+    }
+
+    let objects: Vec<Map<String, Value>> =
+        serde_json::from_slice(&upload.bytes).map_err(|error| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": format!("Invalid json: {}", error)
+                })),
+            )
+        })?;
+
+    let field = |object: &Map<String, Value>, name: &str| match object.get(name) {
+        Some(Value::String(value)) => Some(value.trim().to_string()),
+        Some(Value::Number(value)) => Some(value.to_string()),
+        _ => None,
+    };
+
+    let rows = objects
+        .iter()
+        .enumerate()
+        .map(|(index, object)| {
+            (
+                index + 1,
+                Weighing {
+                    reference: field(object, "reference"),
+                    collector: field(object, "collector").unwrap_or_default(),
+                    product: field(object, "product").unwrap_or_default(),
+                    weight: field(object, "weight").unwrap_or_default(),
+                    weighed_at: field(object, "weighed_at"),
+                },
+            )
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+pub fn parse_weighed_at(weighed_at: &str) -> Option<NaiveDateTime> {
+    if let Ok(weighed_at) = DateTime::parse_from_rfc3339(weighed_at) {
+        return Some(weighed_at.naive_utc());
+    }
+
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(weighed_at, format).ok())
+}
+
+/// Matches a weighing to a collector, product, weight and time. Returns the
+/// reason the weighing cannot be imported when any of them is missing.
+fn match_weighing(
+    weighing: &Weighing,
+    collectors: &[Collector],
+    products: &[Product],
+) -> Result<(Uuid, Uuid, BigDecimal, Option<NaiveDateTime>), String> {
+    let phone_number = normalise_phone_number(&weighing.collector);
+
+    let matched_collectors: Vec<&Collector> = collectors
+        .iter()
+        .filter(|collector| {
+            collector.id_number == weighing.collector
+                || collector.collector_code.as_deref() == Some(weighing.collector.as_str())
+                || (!phone_number.is_empty()
+                    && normalise_phone_number(&collector.phone_number) == phone_number)
+        })
+        .collect();
+
+    let collector_id = match matched_collectors.as_slice() {
+        [collector] if collector.status != STATUS_APPROVED => {
+            return Err(format!(
+                "The collector matching \"{}\" is not approved.",
+                weighing.collector
+            ))
+        }
+        [collector] => collector.id,
+        [] => return Err(format!("No collector matches \"{}\".", weighing.collector)),
+        _ => {
+            return Err(format!(
+                "More than one collector matches \"{}\".",
+                weighing.collector
+            ))
+        }
+    };
+
+    let matched_products: Vec<&Product> = products
+        .iter()
+        .filter(|product| {
+            product.name.eq_ignore_ascii_case(&weighing.product)
+                || product
+                    .code
+                    .as_deref()
+                    .map(|code| code.eq_ignore_ascii_case(&weighing.product))
+                    .unwrap_or(false)
+        })
+        .collect();
+
+    let product_id = match matched_products.as_slice() {
+        [product] => product.id,
+        [] => return Err(format!("No product matches \"{}\".", weighing.product)),
+        _ => {
+            return Err(format!(
+                "More than one product matches \"{}\".",
+                weighing.product
+            ))
+        }
+    };
+
+    let weight = match BigDecimal::from_str(&weighing.weight) {
+        Ok(weight) if weight > BigDecimal::from(0) => weight,
+        _ => return Err(format!("Invalid weight \"{}\".", weighing.weight)),
+    };
+
+    let weighed_at = match weighing.weighed_at.as_deref() {
+        None | Some("") => None,
+        Some(weighed_at) => match parse_weighed_at(weighed_at) {
+            Some(weighed_at) => Some(weighed_at),
+            None => return Err(format!("Invalid weighed_at \"{}\".", weighed_at)),
+        },
+    };
+
+    Ok((collector_id, product_id, weight, weighed_at))
+}
+
+/// Imports the weighings of a scale export as collections. Weighings that
+/// cannot be matched to a collector and product are staged for manual
+/// resolution, and weighings that were imported before are skipped.
+#[utoipa::path(
+    post,
+    path = "/import/collection",
+    params(
+        ("business_id" = Option<String>, Query, description = "The business to import into. Required for staff."),
+        ("dry_run" = Option<bool>, Query, description = "Only match the weighings."),
+    ),
+    request_body(content = String, description = "A multipart upload with a csv or json file in the \"file\" field. Fields: collector (id number, phone number or collector code), product (name or code), weight, and optionally reference and weighed_at.", content_type = "multipart/form-data"),
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collection(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<CollectionImportQuery>,
+    multipart: extract::Multipart,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to import collections."
+            })),
+        ));
+    }
+
+    let business_id = import_business(&authenticated_user, &membership, query.business_id)?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        business_id,
+        "Only owners and managers can import collections.",
+    )?;
+
+    let upload = read_upload(multipart).await?;
+    let mut errors: Vec<ImportRowError> = Vec::new();
+    let rows = parse_weighings(&upload, &mut errors)?;
+
+    if !errors.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The upload contains rows that cannot be read.",
+                "errors": errors
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let products = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT * FROM product WHERE business_id = $1
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let references: Vec<String> = rows
+        .iter()
+        .map(|(_, weighing)| weighing.collector.clone())
+        .collect();
+    let phone_numbers: Vec<String> = references
+        .iter()
+        .map(|reference| normalise_phone_number(reference))
+        .filter(|phone_number| !phone_number.is_empty())
+        .collect();
+
+    let collectors = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile
+        WHERE id_number = ANY($1)
+            OR collector_code = ANY($1)
+            OR REGEXP_REPLACE(phone_number, '[^0-9+]', '', 'g') = ANY($2)
+        "#,
+        &references,
+        &phone_numbers
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let hashes: Vec<Option<String>> = rows
+        .iter()
+        .map(|(_, weighing)| weighing.hash(business_id))
+        .collect();
+    let known_hashes: Vec<String> = hashes.iter().flatten().cloned().collect();
+
+    let existing_hashes = sqlx::query_scalar!(
+        r#"
+        SELECT row_hash AS "row_hash!" FROM collection_import_row WHERE business_id = $1 AND row_hash = ANY($2)
+        "#,
+        business_id,
+        &known_hashes
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let dry_run = query.dry_run.unwrap_or(false);
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut outcomes: Vec<WeighingOutcome> = Vec::new();
+    let mut collections: Vec<Collection> = Vec::new();
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    for ((row, weighing), row_hash) in rows.iter().zip(hashes) {
+        let duplicate = row_hash.as_ref().is_some_and(|row_hash| {
+            existing_hashes.contains(row_hash) || !seen.insert(row_hash.clone())
+        });
+
+        if duplicate {
+            outcomes.push(WeighingOutcome {
+                row: *row,
+                status: "Duplicate".to_string(),
+                reason: Some("The weighing was imported before.".to_string()),
+                collection_id: None,
+            });
+
+            continue;
+        }
+
+        let matched = match_weighing(weighing, &collectors, &products);
+
+        if dry_run {
+            outcomes.push(WeighingOutcome {
+                row: *row,
+                status: match matched {
+                    Ok(_) => STATUS_IMPORTED.to_string(),
+                    Err(_) => STATUS_PENDING.to_string(),
+                },
+                reason: matched.err(),
+                collection_id: None,
+            });
+
+            continue;
+        }
+
+        let import_row = sqlx::query_as!(
+            CollectionImportRow,
+            r#"
+            INSERT INTO collection_import_row (business_id, row_hash, source, row_number, reference, collector_reference, product_reference, weight, weighed_at, reason, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+            business_id,
+            row_hash,
+            upload.file_name,
+            *row as i32,
+            weighing.reference,
+            weighing.collector,
+            weighing.product,
+            weighing.weight,
+            weighing.weighed_at,
+            matched.as_ref().err(),
+            authenticated_user.id
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        let (collector_id, product_id, weight, weighed_at) = match matched {
+            Ok(matched) => matched,
+            Err(reason) => {
+                outcomes.push(WeighingOutcome {
+                    row: *row,
+                    status: STATUS_PENDING.to_string(),
+                    reason: Some(reason),
+                    collection_id: None,
+                });
+
+                continue;
+            }
+        };
+
+        let collection = sqlx::query_as!(
+            Collection,
+            r#"
+            INSERT INTO collection (business_id, collector_id, product_id, weight, created_at)
+            VALUES ($1, $2, $3, $4, COALESCE($5, CURRENT_TIMESTAMP::TIMESTAMP))
+            RETURNING *
+            "#,
+            business_id,
+            collector_id,
+            product_id,
+            weight,
+            weighed_at
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE collection_import_row SET status = $1, collection_id = $2 WHERE id = $3
+            "#,
+            STATUS_IMPORTED,
+            collection.id,
+            import_row.id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        outcomes.push(WeighingOutcome {
+            row: *row,
+            status: STATUS_IMPORTED.to_string(),
+            reason: None,
+            collection_id: Some(collection.id),
+        });
+
+        collections.push(collection);
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    for collection in &collections {
+        webhooks::emit(
+            &app_state.pool,
+            COLLECTION_CREATED,
+            Some(collection.business_id),
+            json!(collection),
+        )
+        .await;
+
+        app_state
+            .collection_feed
+            .publish(&app_state.pool, COLLECTION_CREATED, collection)
+            .await;
+    }
+
+    let count = |status: &str| {
+        outcomes
+            .iter()
+            .filter(|outcome| outcome.status == status)
+            .count()
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "dry_run": dry_run,
+            "total_rows": outcomes.len(),
+            "imported": count(STATUS_IMPORTED),
+            "staged": count(STATUS_PENDING),
+            "duplicates": count("Duplicate"),
+            "rows": outcomes
+        }))
+        .into_response(),
+    ))
+}
@@ -0,0 +1,266 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use bcrypt::hash;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    authentication::{roles::Role, token::generate_token},
+    data::entities::{business_member::BusinessMember, collector::Collector, user::User},
+    routes::{
+        business::{business_scope, require_business_manager},
+        users::send_activation,
+    },
+    webhooks::{self, COLLECTOR_REGISTERED},
+    AppState,
+};
+
+use super::{
+    check_emails, parse_rows, read_upload, require, validation_response, ImportQuery,
+    ImportRowError,
+};
+
+const COLUMNS: [&str; 12] = [
+    "email",
+    "first_name",
+    "last_name",
+    "id_number",
+    "phone_number",
+    "address",
+    "city",
+    "state",
+    "zip_code",
+    "bank_name",
+    "bank_account_holder",
+    "bank_account_number",
+];
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CollectorImportRow {
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub id_number: String,
+    pub phone_number: String,
+    pub address: String,
+    pub city: String,
+    pub state: String,
+    pub zip_code: String,
+    pub bank_name: String,
+    pub bank_account_holder: String,
+    pub bank_account_number: String,
+}
+
+/// Creates a user with role "Collector" and a collector profile for every row
+/// of the uploaded csv. Every row is validated first and nothing is created
+/// unless all rows are valid. The users are created inactive and emailed a
+/// link to choose their password, like invited users.
+#[utoipa::path(
+    post,
+    path = "/import/collector",
+    params(
+        ("dry_run" = Option<bool>, Query, description = "Only validate the upload."),
+        ("report" = Option<String>, Query, description = "Set to csv to download the row errors as a csv."),
+    ),
+    request_body(content = String, description = "A multipart upload with the csv in the \"file\" field. Columns: email, first_name, last_name, id_number, phone_number, address, city, state, zip_code, bank_name, bank_account_holder, bank_account_number.", content_type = "multipart/form-data"),
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collector(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<ImportQuery>,
+    multipart: extract::Multipart,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to import collectors."
+            })),
+        ));
+    }
+
+    if let Some(business_id) = business_scope(&authenticated_user, &membership)? {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "Only owners and managers can import collectors.",
+        )?;
+    }
+
+    let upload = read_upload(multipart).await?;
+
+    let mut errors: Vec<ImportRowError> = Vec::new();
+    let rows = parse_rows::<CollectorImportRow>(&upload.bytes, &COLUMNS, &mut errors)?;
+
+    let mut id_numbers = HashSet::new();
+
+    for (row, record) in &rows {
+        require(*row, "email", &record.email, &mut errors);
+        require(*row, "first_name", &record.first_name, &mut errors);
+        require(*row, "last_name", &record.last_name, &mut errors);
+        require(*row, "id_number", &record.id_number, &mut errors);
+        require(*row, "phone_number", &record.phone_number, &mut errors);
+        require(*row, "bank_name", &record.bank_name, &mut errors);
+        require(
+            *row,
+            "bank_account_holder",
+            &record.bank_account_holder,
+            &mut errors,
+        );
+        require(
+            *row,
+            "bank_account_number",
+            &record.bank_account_number,
+            &mut errors,
+        );
+
+        if !record.id_number.is_empty() && !id_numbers.insert(record.id_number.clone()) {
+            errors.push(ImportRowError::new(
+                *row,
+                "id_number",
+                "The id number appears more than once in the file.",
+            ));
+        }
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let emails: Vec<(usize, String)> = rows
+        .iter()
+        .map(|(row, record)| (*row, record.email.clone()))
+        .collect();
+
+    check_emails(&app_state.pool, &emails, &mut errors)
+        .await
+        .map_err(database_error)?;
+
+    let existing_id_numbers = sqlx::query_scalar!(
+        r#"
+        SELECT id_number FROM collector_profile WHERE id_number = ANY($1)
+        "#,
+        &id_numbers.into_iter().collect::<Vec<String>>()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    for (row, record) in &rows {
+        if existing_id_numbers.contains(&record.id_number) {
+            errors.push(ImportRowError::new(
+                *row,
+                "id_number",
+                "A collector with that id number already exists.",
+            ));
+        }
+    }
+
+    if query.dry_run() || !errors.is_empty() {
+        return validation_response(&query, "collector-import-errors.csv", rows.len(), errors);
+    }
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+    let mut collectors: Vec<Collector> = Vec::new();
+
+    for (_, record) in rows {
+        // The account can not be logged into until the user chooses a
+        // password through the activation link.
+        let hashed_password = hash(generate_token(), 4).map_err(|error| {
+            tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Unknown error occured. Please contact the api developer."
+                })),
+            )
+        })?;
+
+        let user = sqlx::query_as!(
+            User,
+            r#"INSERT INTO users (email, password, role, active) VALUES ($1, $2, $3, FALSE) RETURNING *"#,
+            record.email,
+            hashed_password,
+            Role::Collector.to_string()
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        send_activation(&app_state, &mut transaction, &user, &authenticated_user).await?;
+
+        let collector = sqlx::query_as!(
+            Collector,
+            r#"
+            INSERT INTO collector_profile (user_id, first_name, last_name, id_number, phone_number, address, city, state, zip_code, bank_name, bank_account_holder, bank_account_number)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING *
+            "#,
+            user.id,
+            record.first_name,
+            record.last_name,
+            record.id_number,
+            record.phone_number,
+            record.address,
+            record.city,
+            record.state,
+            record.zip_code,
+            record.bank_name,
+            record.bank_account_holder,
+            record.bank_account_number
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        collectors.push(collector);
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    for collector in &collectors {
+        webhooks::emit(
+            &app_state.pool,
+            COLLECTOR_REGISTERED,
+            None,
+            json!(collector),
+        )
+        .await;
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "dry_run": false,
+            "total_rows": collectors.len(),
+            "collectors": collectors
+        }))
+        .into_response(),
+    ))
+}
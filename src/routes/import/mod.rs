@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::Multipart,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+
+use super::statement::file_response;
+
+pub mod business;
+pub mod collection;
+pub mod collector;
+pub mod staged;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImportQuery {
+    /// Validates the upload without creating anything.
+    pub dry_run: Option<bool>,
+    /// `csv` returns the row errors as a downloadable csv instead of json.
+    pub report: Option<String>,
+}
+
+impl ImportQuery {
+    pub fn dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false)
+    }
+
+    pub fn csv_report(&self) -> bool {
+        self.report
+            .as_ref()
+            .map(|report| report.to_lowercase() == "csv")
+            .unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ImportRowError {
+    /// The line in the uploaded file, the header being line 1.
+    pub row: usize,
+    pub column: String,
+    pub reason: String,
+}
+
+impl ImportRowError {
+    pub fn new(row: usize, column: &str, reason: &str) -> Self {
+        Self {
+            row,
+            column: column.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// The parsed rows of an upload with the line number of every row.
+pub type ParsedRows<T> = Vec<(usize, T)>;
+
+pub struct Upload {
+    pub file_name: String,
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// Reads the `file` field of a multipart upload.
+pub async fn read_upload(mut multipart: Multipart) -> Result<Upload, (StatusCode, Json<Value>)> {
+    let invalid_upload = |reason: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|error| invalid_upload(format!("Invalid multipart upload: {}", error)))?
+    {
+        if field.name() == Some("file") {
+            let file_name = field.file_name().unwrap_or("upload").to_string();
+            let content_type = field
+                .content_type()
+                .map(|content_type| content_type.to_string());
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|error| invalid_upload(format!("Failed to read upload: {}", error)))?;
+
+            return Ok(Upload {
+                file_name,
+                content_type,
+                bytes: bytes.to_vec(),
+            });
+        }
+    }
+
+    Err(invalid_upload(
+        "The upload must contain a \"file\" field.".to_string(),
+    ))
+}
+
+/// Parses the uploaded csv into rows, keeping the line number of every row.
+/// Rows that cannot be parsed are recorded as errors.
+pub fn parse_rows<T: DeserializeOwned>(
+    bytes: &[u8],
+    columns: &[&str],
+    errors: &mut Vec<ImportRowError>,
+) -> Result<ParsedRows<T>, (StatusCode, Json<Value>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(bytes);
+
+    let headers = reader.headers().map_err(|error| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("Failed to read csv header: {}", error)
+            })),
+        )
+    })?;
+
+    let missing_columns: Vec<&str> = columns
+        .iter()
+        .filter(|column| !headers.iter().any(|header| header == **column))
+        .copied()
+        .collect();
+
+    if !missing_columns.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("The csv is missing the columns: {}.", missing_columns.join(", ")),
+                "columns": columns
+            })),
+        ));
+    }
+
+    let mut rows = Vec::new();
+
+    for (index, record) in reader.deserialize::<T>().enumerate() {
+        let row = index + 2;
+
+        match record {
+            Ok(record) => rows.push((row, record)),
+            Err(error) => errors.push(ImportRowError::new(row, "", &error.to_string())),
+        }
+    }
+
+    Ok(rows)
+}
+
+pub fn require(row: usize, column: &str, value: &str, errors: &mut Vec<ImportRowError>) {
+    if value.is_empty() {
+        errors.push(ImportRowError::new(row, column, "A value is required."));
+    }
+}
+
+/// Checks the emails of the upload for duplicates, both within the file and
+/// against the existing users.
+pub async fn check_emails(
+    pool: &Pool<Postgres>,
+    emails: &[(usize, String)],
+    errors: &mut Vec<ImportRowError>,
+) -> Result<(), sqlx::Error> {
+    let mut seen = HashSet::new();
+
+    for (row, email) in emails {
+        if email.is_empty() {
+            continue;
+        }
+
+        if !email.contains('@') {
+            errors.push(ImportRowError::new(*row, "email", "Invalid email address."));
+        }
+
+        if !seen.insert(email.to_lowercase()) {
+            errors.push(ImportRowError::new(
+                *row,
+                "email",
+                "The email appears more than once in the file.",
+            ));
+        }
+    }
+
+    let lowercase_emails: Vec<String> = emails
+        .iter()
+        .map(|(_, email)| email.to_lowercase())
+        .collect();
+
+    let existing_emails = sqlx::query_scalar!(
+        r#"
+        SELECT LOWER(TRIM(email)) AS "email!" FROM users WHERE LOWER(TRIM(email)) = ANY($1)
+        "#,
+        &lowercase_emails
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for (row, email) in emails {
+        if existing_emails.contains(&email.to_lowercase()) {
+            errors.push(ImportRowError::new(
+                *row,
+                "email",
+                "A user with that email already exists.",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn error_report(errors: &[ImportRowError]) -> Result<Vec<u8>, (StatusCode, Json<Value>)> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    for error in errors {
+        writer.serialize(error).map_err(|error| {
+            tracing::error!("🔥 Failed to write error report: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to write error report."
+                })),
+            )
+        })?;
+    }
+
+    writer.into_inner().map_err(|error| {
+        tracing::error!("🔥 Failed to write error report: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to write error report."
+            })),
+        )
+    })
+}
+
+/// Answers an import that either failed validation or was a dry run.
+pub fn validation_response(
+    query: &ImportQuery,
+    file_name: &str,
+    total_rows: usize,
+    mut errors: Vec<ImportRowError>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    errors.sort_by_key(|error| error.row);
+
+    let status = match errors.is_empty() {
+        true => StatusCode::OK,
+        false => StatusCode::BAD_REQUEST,
+    };
+
+    if query.csv_report() {
+        return Ok((
+            status,
+            file_response(file_name.to_string(), "text/csv", error_report(&errors)?),
+        ));
+    }
+
+    Ok((
+        status,
+        Json(json!({
+            "success": errors.is_empty(),
+            "dry_run": query.dry_run(),
+            "total_rows": total_rows,
+            "invalid_rows": errors.iter().map(|error| error.row).collect::<HashSet<usize>>().len(),
+            "errors": errors
+        }))
+        .into_response(),
+    ))
+}
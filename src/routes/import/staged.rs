@@ -0,0 +1,384 @@
+use std::str::FromStr;
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, collection::Collection,
+        collection_import_row::CollectionImportRow, product::Product, user::User,
+    },
+    routes::business::require_business_manager,
+    routes::collector::require_approved_collector,
+    webhooks::{self, COLLECTION_CREATED},
+    AppState,
+};
+
+use super::collection::{
+    import_business, parse_weighed_at, STATUS_DISCARDED, STATUS_PENDING, STATUS_RESOLVED,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StagedQuery {
+    pub business_id: Option<Uuid>,
+    /// Defaults to Pending.
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ResolveStagedPayload {
+    pub collector_id: Uuid,
+    pub product_id: Uuid,
+    /// Overrides the weight of the imported row.
+    pub weight: Option<BigDecimal>,
+    /// Overrides the weighing time of the imported row.
+    pub weighed_at: Option<NaiveDateTime>,
+}
+
+/// Finds a pending staged row the user may resolve or discard.
+async fn pending_row(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    import_row_id: Uuid,
+) -> Result<CollectionImportRow, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to resolve imported collections."
+            })),
+        ));
+    }
+
+    let import_row = sqlx::query_as!(
+        CollectionImportRow,
+        r#"
+        SELECT * FROM collection_import_row WHERE id = $1
+        "#,
+        import_row_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let import_row = match import_row {
+        Some(import_row) => import_row,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Imported row not found."
+                })),
+            ))
+        }
+    };
+
+    let business_id =
+        import_business(authenticated_user, membership, Some(import_row.business_id))?;
+
+    if business_id != import_row.business_id {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to resolve imported collections of other businesses."
+            })),
+        ));
+    }
+
+    require_business_manager(
+        authenticated_user,
+        membership,
+        business_id,
+        "Only owners and managers can resolve imported collections.",
+    )?;
+
+    if import_row.status != STATUS_PENDING {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The imported row is already {}.", import_row.status.to_lowercase())
+            })),
+        ));
+    }
+
+    Ok(import_row)
+}
+
+#[utoipa::path(
+    get,
+    path = "/import/collection/staged",
+    params(
+        ("business_id" = Option<String>, Query, description = "The business of the rows. Required for staff."),
+        ("status" = Option<String>, Query, description = "One of Pending, Imported, Resolved or Discarded. Defaults to Pending."),
+    ),
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn staged(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StagedQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to view imported collections."
+            })),
+        ));
+    }
+
+    let business_id = import_business(&authenticated_user, &membership, query.business_id)?;
+    let status = query.status.unwrap_or(STATUS_PENDING.to_string());
+
+    let import_rows = sqlx::query_as!(
+        CollectionImportRow,
+        r#"
+        SELECT * FROM collection_import_row
+        WHERE business_id = $1 AND status = $2
+        ORDER BY created_at DESC, row_number
+        "#,
+        business_id,
+        status
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "import_rows": import_rows
+        })),
+    ))
+}
+
+/// Creates the collection of a staged row with a collector and product chosen
+/// by hand.
+#[utoipa::path(
+    post,
+    path = "/import/collection/staged/{import_row_id}/resolve",
+    params(("import_row_id" = String, Path, description = "The imported rows id.")),
+    request_body = ResolveStagedPayload,
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn resolve(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(import_row_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<ResolveStagedPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let import_row =
+        pending_row(&app_state, &authenticated_user, &membership, import_row_id).await?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    require_approved_collector(&app_state, payload.collector_id).await?;
+
+    let product = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT * FROM product WHERE id = $1 AND business_id = $2
+        "#,
+        payload.product_id,
+        import_row.business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if product.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let weight = match payload.weight {
+        Some(weight) => Some(weight),
+        None => BigDecimal::from_str(&import_row.weight).ok(),
+    };
+
+    let weight = match weight {
+        Some(weight) if weight > BigDecimal::from(0) => weight,
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "The imported weight is invalid, please provide a weight."
+                })),
+            ))
+        }
+    };
+
+    let weighed_at = payload
+        .weighed_at
+        .or(import_row.weighed_at.as_deref().and_then(parse_weighed_at));
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+        INSERT INTO collection (business_id, collector_id, product_id, weight, created_at)
+        VALUES ($1, $2, $3, $4, COALESCE($5, CURRENT_TIMESTAMP::TIMESTAMP))
+        RETURNING *
+        "#,
+        import_row.business_id,
+        payload.collector_id,
+        payload.product_id,
+        weight,
+        weighed_at
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let import_row = sqlx::query_as!(
+        CollectionImportRow,
+        r#"
+        UPDATE collection_import_row
+        SET status = $1, collection_id = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        RETURNING *
+        "#,
+        STATUS_RESOLVED,
+        collection.id,
+        import_row.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_CREATED,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_CREATED, &collection)
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "import_row": import_row,
+            "collection": collection
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/import/collection/staged/{import_row_id}/discard",
+    params(("import_row_id" = String, Path, description = "The imported rows id.")),
+    tag = "Import",
+    security(("bearer_auth" = [])),
+)]
+pub async fn discard(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(import_row_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let import_row =
+        pending_row(&app_state, &authenticated_user, &membership, import_row_id).await?;
+
+    let import_row = sqlx::query_as!(
+        CollectionImportRow,
+        r#"
+        UPDATE collection_import_row
+        SET status = $1, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $2
+        RETURNING *
+        "#,
+        STATUS_DISCARDED,
+        import_row.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "import_row": import_row
+        })),
+    ))
+}
@@ -0,0 +1,264 @@
+use axum::{
+    extract::{self, Multipart},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::NaiveDate;
+use image::ImageFormat;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, kyc_document::KycDocument, user::User},
+    AppState,
+};
+
+use super::{authorize_owner, DocumentOwner};
+
+const MAX_DOCUMENT_SIZE: usize = 10_000_000;
+
+/// Checks that the file is a PDF, JPEG or PNG of the declared type.
+fn validate_file(bytes: &[u8], content_type: &str) -> Result<&'static str, String> {
+    let (valid, extension) = match content_type {
+        "application/pdf" => (bytes.starts_with(b"%PDF-"), "pdf"),
+        "image/jpeg" => (
+            image::guess_format(bytes).ok() == Some(ImageFormat::Jpeg),
+            "jpg",
+        ),
+        "image/png" => (
+            image::guess_format(bytes).ok() == Some(ImageFormat::Png),
+            "png",
+        ),
+        _ => return Err("Only PDF, JPEG and PNG documents are accepted.".to_string()),
+    };
+
+    if !valid {
+        return Err(format!(
+            "The file is not a valid {} document.",
+            content_type
+        ));
+    }
+
+    Ok(extension)
+}
+
+/// Stores a document uploaded as multipart with a "document_type" field, an
+/// optional "expires_on" field and the document in a "file" field.
+async fn upload_document(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    owner: DocumentOwner,
+    mut multipart: Multipart,
+) -> Result<KycDocument, (StatusCode, Json<Value>)> {
+    authorize_owner(app_state, authenticated_user, membership, owner).await?;
+
+    let invalid_upload = |reason: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    let mut document_type = None;
+    let mut expires_on = None;
+    let mut file = None;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|error| invalid_upload(format!("Invalid multipart upload: {}", error)))?
+    {
+        match field.name() {
+            Some("document_type") => {
+                document_type =
+                    Some(field.text().await.map_err(|error| {
+                        invalid_upload(format!("Failed to read upload: {}", error))
+                    })?)
+            }
+            Some("expires_on") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|error| invalid_upload(format!("Failed to read upload: {}", error)))?;
+
+                if !text.trim().is_empty() {
+                    expires_on = Some(NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d").map_err(
+                        |_| {
+                            invalid_upload("expires_on must be a date like 2030-02-28.".to_string())
+                        },
+                    )?);
+                }
+            }
+            Some("file") => {
+                let file_name = field.file_name().unwrap_or("document").to_string();
+                let content_type = field.content_type().unwrap_or_default().to_string();
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|error| invalid_upload(format!("Failed to read upload: {}", error)))?;
+
+                file = Some((file_name, content_type, bytes.to_vec()));
+            }
+            _ => {}
+        }
+    }
+
+    let document_type = match document_type {
+        Some(document_type) if owner.document_types().contains(&document_type.as_str()) => {
+            document_type
+        }
+        _ => {
+            return Err(invalid_upload(format!(
+                "document_type must be one of: {}.",
+                owner.document_types().join(", ")
+            )))
+        }
+    };
+
+    let (file_name, content_type, bytes) = file
+        .ok_or_else(|| invalid_upload("The upload must contain a \"file\" field.".to_string()))?;
+
+    if bytes.len() > MAX_DOCUMENT_SIZE {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({
+                "error": "Payload Too Large",
+                "reason": format!("Documents may be at most {} MB.", MAX_DOCUMENT_SIZE / 1_000_000)
+            })),
+        ));
+    }
+
+    let extension = validate_file(&bytes, &content_type).map_err(invalid_upload)?;
+
+    let document_id = Uuid::new_v4();
+    let owner_key = match owner {
+        DocumentOwner::Collector(collector_id) => format!("collectors/{}", collector_id),
+        DocumentOwner::Business(business_id) => format!("businesses/{}", business_id),
+    };
+    let storage_key = format!("kyc/{}/{}.{}", owner_key, document_id, extension);
+    let size_bytes = bytes.len() as i64;
+
+    app_state
+        .storage
+        .put(&storage_key, bytes, &content_type)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to store document: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to store document."
+                })),
+            )
+        })?;
+
+    let document = sqlx::query_as!(
+        KycDocument,
+        r#"
+        INSERT INTO kyc_document (id, collector_id, business_id, document_type, storage_key, file_name, content_type, size_bytes, expires_on, uploaded_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        RETURNING *
+        "#,
+        document_id,
+        owner.collector_id(),
+        owner.business_id(),
+        document_type,
+        storage_key,
+        file_name,
+        content_type,
+        size_bytes,
+        expires_on,
+        authenticated_user.id
+    )
+    .fetch_one(&app_state.pool)
+    .await;
+
+    match document {
+        Ok(document) => Ok(document),
+        Err(error) => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+            app_state.storage.delete_all(&[storage_key]).await;
+
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            ))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/kyc/collector/{collector_id}",
+    params(("collector_id" = String, Path, description = "The collectors id.")),
+    request_body(content = String, description = "A multipart upload with a \"document_type\" field (ID Document or Proof of Bank Account), an optional \"expires_on\" date field and the PDF, JPEG or PNG in a \"file\" field.", content_type = "multipart/form-data"),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collector_document(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collector_id): extract::Path<Uuid>,
+    multipart: Multipart,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let document = upload_document(
+        &app_state,
+        &authenticated_user,
+        &membership,
+        DocumentOwner::Collector(collector_id),
+        multipart,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "document": document
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/kyc/business/{business_id}",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    request_body(content = String, description = "A multipart upload with a \"document_type\" field (Registration Certificate), an optional \"expires_on\" date field and the PDF, JPEG or PNG in a \"file\" field.", content_type = "multipart/form-data"),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn business_document(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+    multipart: Multipart,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let document = upload_document(
+        &app_state,
+        &authenticated_user,
+        &membership,
+        DocumentOwner::Business(business_id),
+        multipart,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "document": document
+        })),
+    ))
+}
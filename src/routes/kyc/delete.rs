@@ -0,0 +1,76 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{find_document, STATUS_VERIFIED};
+
+/// Deletes a document. Verified documents can only be deleted by staff, so
+/// that a collector does not lose their verification by accident.
+#[utoipa::path(
+    delete,
+    path = "/kyc/document/{document_id}",
+    params(("document_id" = String, Path, description = "The documents id.")),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn document(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(document_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let document = find_document(&app_state, &authenticated_user, &membership, document_id).await?;
+
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && document.status == STATUS_VERIFIED;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "Only staff can delete verified documents."
+            })),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM kyc_document WHERE id = $1
+        "#,
+        document.id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state
+        .storage
+        .delete_all(std::slice::from_ref(&document.storage_key))
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "document": document
+        })),
+    ))
+}
@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, kyc_document::KycDocument, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+pub mod add;
+pub mod delete;
+pub mod review;
+pub mod view;
+
+pub const STATUS_PENDING: &str = "Pending";
+pub const STATUS_VERIFIED: &str = "Verified";
+pub const STATUS_REJECTED: &str = "Rejected";
+
+pub const ID_DOCUMENT: &str = "ID Document";
+pub const PROOF_OF_BANK_ACCOUNT: &str = "Proof of Bank Account";
+pub const REGISTRATION_CERTIFICATE: &str = "Registration Certificate";
+
+pub const COLLECTOR_DOCUMENT_TYPES: [&str; 2] = [ID_DOCUMENT, PROOF_OF_BANK_ACCOUNT];
+pub const BUSINESS_DOCUMENT_TYPES: [&str; 1] = [REGISTRATION_CERTIFICATE];
+
+/// The documents a collector needs verified, and not expired, before their
+/// collections can be paid.
+pub const REQUIRED_COLLECTOR_DOCUMENTS: [&str; 2] = [ID_DOCUMENT, PROOF_OF_BANK_ACCOUNT];
+
+#[derive(Debug, Clone, Copy)]
+pub enum DocumentOwner {
+    Collector(Uuid),
+    Business(Uuid),
+}
+
+impl DocumentOwner {
+    pub fn document_types(&self) -> &'static [&'static str] {
+        match self {
+            DocumentOwner::Collector(_) => &COLLECTOR_DOCUMENT_TYPES,
+            DocumentOwner::Business(_) => &BUSINESS_DOCUMENT_TYPES,
+        }
+    }
+
+    pub fn collector_id(&self) -> Option<Uuid> {
+        match self {
+            DocumentOwner::Collector(collector_id) => Some(*collector_id),
+            DocumentOwner::Business(_) => None,
+        }
+    }
+
+    pub fn business_id(&self) -> Option<Uuid> {
+        match self {
+            DocumentOwner::Collector(_) => None,
+            DocumentOwner::Business(business_id) => Some(*business_id),
+        }
+    }
+}
+
+/// Checks that the owner exists and that the user may see and upload its
+/// documents. Staff see everyone's documents, businesses also see those of the
+/// collectors that registered at or sold to them, collectors see their own
+/// and the members of a business see those of the business.
+pub async fn authorize_owner(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    owner: DocumentOwner,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    // Collectors are users themselves, businesses are reached through their
+    // members.
+    let owner_user_id = match owner {
+        DocumentOwner::Collector(collector_id) => {
+            sqlx::query_scalar!(
+                r#"
+            SELECT user_id AS "user_id?" FROM collector_profile WHERE id = $1
+            "#,
+                collector_id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+        }
+        DocumentOwner::Business(business_id) => {
+            sqlx::query_scalar!(
+                r#"
+            SELECT NULL::uuid AS "user_id?" FROM business_profile WHERE id = $1
+            "#,
+                business_id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+        }
+    }
+    .map_err(database_error)?;
+
+    let owner_user_id = match owner_user_id {
+        Some(owner_user_id) => owner_user_id,
+        None => {
+            let reason = match owner {
+                DocumentOwner::Collector(_) => "Collector not found.",
+                DocumentOwner::Business(_) => "Business not found.",
+            };
+
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": reason
+                })),
+            ));
+        }
+    };
+
+    let allowed = match (authenticated_user.role(), owner) {
+        (Role::Staff | Role::SystemAdmin, _) => true,
+        (Role::Business, DocumentOwner::Collector(collector_id)) => {
+            match business_scope(authenticated_user, membership)? {
+                Some(business_id) => sqlx::query_scalar!(
+                    r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM collector_profile
+                        WHERE id = $1
+                            AND (
+                                review_business_id = $2
+                                OR EXISTS (
+                                    SELECT 1 FROM collection
+                                    WHERE collection.collector_id = collector_profile.id
+                                        AND collection.business_id = $2
+                                )
+                            )
+                    ) AS "linked!"
+                    "#,
+                    collector_id,
+                    business_id
+                )
+                .fetch_one(&app_state.pool)
+                .await
+                .map_err(database_error)?,
+                None => false,
+            }
+        }
+        (Role::Business, DocumentOwner::Business(business_id)) => {
+            business_scope(authenticated_user, membership)? == Some(business_id)
+        }
+        _ => owner_user_id == Some(authenticated_user.id),
+    };
+
+    if !allowed {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access these documents."
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds a document the user may access.
+pub async fn find_document(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    document_id: Uuid,
+) -> Result<KycDocument, (StatusCode, Json<Value>)> {
+    let document = sqlx::query_as!(
+        KycDocument,
+        r#"
+        SELECT * FROM kyc_document WHERE id = $1
+        "#,
+        document_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Document not found."
+        })),
+    ))?;
+
+    // Every document has exactly one owner, which the table enforces.
+    let owner = match document.collector_id {
+        Some(collector_id) => DocumentOwner::Collector(collector_id),
+        None => DocumentOwner::Business(document.business_id.unwrap_or_default()),
+    };
+
+    authorize_owner(app_state, authenticated_user, membership, owner).await?;
+
+    Ok(document)
+}
+
+/// Lists the required documents the collector has no verified, unexpired copy
+/// of. The collector can only be paid once this is empty.
+pub async fn missing_collector_documents(
+    pool: &Pool<Postgres>,
+    collector_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    let verified: HashSet<String> = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT document_type FROM kyc_document
+        WHERE collector_id = $1
+            AND status = $2
+            AND (expires_on IS NULL OR expires_on >= CURRENT_DATE)
+        "#,
+        collector_id,
+        STATUS_VERIFIED
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .collect();
+
+    Ok(REQUIRED_COLLECTOR_DOCUMENTS
+        .iter()
+        .filter(|document_type| !verified.contains(**document_type))
+        .map(|document_type| document_type.to_string())
+        .collect())
+}
@@ -0,0 +1,115 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, kyc_document::KycDocument, user::User},
+    AppState,
+};
+
+use super::{find_document, STATUS_REJECTED, STATUS_VERIFIED};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct ReviewDocumentPayload {
+    /// Either Verified or Rejected.
+    pub status: String,
+    /// Required when rejecting, shown to the uploader.
+    pub reason: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/kyc/document/{document_id}/review",
+    params(("document_id" = String, Path, description = "The documents id.")),
+    request_body = ReviewDocumentPayload,
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn review(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(document_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<ReviewDocumentPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to review documents."
+            })),
+        ));
+    }
+
+    let reason = payload
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    let rejection_reason = match payload.status.as_str() {
+        STATUS_VERIFIED => None,
+        STATUS_REJECTED if reason.is_some() => reason,
+        STATUS_REJECTED => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "A reason is required when rejecting a document."
+                })),
+            ))
+        }
+        _ => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "The status must be Verified or Rejected."
+                })),
+            ))
+        }
+    };
+
+    let document = find_document(&app_state, &authenticated_user, &membership, document_id).await?;
+
+    let document = sqlx::query_as!(
+        KycDocument,
+        r#"
+        UPDATE kyc_document
+        SET status = $1, rejection_reason = $2, reviewed_by = $3, reviewed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $4
+        RETURNING *
+        "#,
+        payload.status,
+        rejection_reason,
+        authenticated_user.id,
+        document.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "document": document
+        })),
+    ))
+}
@@ -0,0 +1,242 @@
+use axum::{
+    extract,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, kyc_document::KycDocument, user::User},
+    AppState,
+};
+
+use super::{
+    authorize_owner, find_document, missing_collector_documents, DocumentOwner, STATUS_PENDING,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DocumentsQuery {
+    /// Defaults to Pending.
+    pub status: Option<String>,
+}
+
+/// Lists documents by status across all collectors and businesses, by default
+/// the ones waiting to be reviewed.
+#[utoipa::path(
+    get,
+    path = "/kyc",
+    params(("status" = Option<String>, Query, description = "One of Pending, Verified or Rejected. Defaults to Pending.")),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn documents(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Query(query): extract::Query<DocumentsQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to review documents."
+            })),
+        ));
+    }
+
+    let status = query.status.unwrap_or(STATUS_PENDING.to_string());
+
+    let documents = sqlx::query_as!(
+        KycDocument,
+        r#"
+        SELECT * FROM kyc_document WHERE status = $1 ORDER BY created_at
+        "#,
+        status
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "documents": documents
+        })),
+    ))
+}
+
+async fn owner_documents(
+    app_state: &AppState,
+    owner: DocumentOwner,
+) -> Result<Vec<KycDocument>, (StatusCode, Json<Value>)> {
+    sqlx::query_as!(
+        KycDocument,
+        r#"
+        SELECT * FROM kyc_document
+        WHERE ($1::uuid IS NULL OR collector_id = $1) AND ($2::uuid IS NULL OR business_id = $2)
+        ORDER BY created_at DESC
+        "#,
+        owner.collector_id(),
+        owner.business_id()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })
+}
+
+/// Lists the documents of a collector, along with the required documents that
+/// still need to be verified before the collector can be paid.
+#[utoipa::path(
+    get,
+    path = "/kyc/collector/{collector_id}",
+    params(("collector_id" = String, Path, description = "The collectors id.")),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collector_documents(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(collector_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let owner = DocumentOwner::Collector(collector_id);
+
+    authorize_owner(&app_state, &authenticated_user, &membership, owner).await?;
+
+    let documents = owner_documents(&app_state, owner).await?;
+
+    let missing_documents = missing_collector_documents(&app_state.pool, collector_id)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "documents": documents,
+            "missing_documents": missing_documents,
+            "payable": missing_documents.is_empty()
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/kyc/business/{business_id}",
+    params(("business_id" = String, Path, description = "The businesses id.")),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn business_documents(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(business_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let owner = DocumentOwner::Business(business_id);
+
+    authorize_owner(&app_state, &authenticated_user, &membership, owner).await?;
+
+    let documents = owner_documents(&app_state, owner).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "documents": documents
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/kyc/document/{document_id}/download",
+    params(("document_id" = String, Path, description = "The documents id.")),
+    tag = "KYC",
+    security(("bearer_auth" = [])),
+)]
+pub async fn download(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(document_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let document = find_document(&app_state, &authenticated_user, &membership, document_id).await?;
+
+    let body = app_state
+        .storage
+        .get(&document.storage_key)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                "🔥 Failed to read document {}: {}",
+                document.storage_key,
+                error
+            );
+
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Document file not found."
+                })),
+            )
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        (
+            [
+                (header::CONTENT_TYPE, document.content_type),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "inline; filename=\"{}\"",
+                        document.file_name.replace('"', "")
+                    ),
+                ),
+                (header::CACHE_CONTROL, "no-store".to_string()),
+            ],
+            body,
+        )
+            .into_response(),
+    ))
+}
@@ -9,3 +9,20 @@ pub mod product;
 pub mod collection;
 pub mod audit_logs;
 pub mod export;
+pub mod impact;
+pub mod impact_factor;
+pub mod epr;
+pub mod epr_stream;
+pub mod statement;
+pub mod report_job;
+pub mod import;
+pub mod scale;
+pub mod webhook;
+pub mod sync;
+pub mod kyc;
+pub mod pickup;
+pub mod stock;
+pub mod sale;
+pub mod bale;
+pub mod branch;
+pub mod api_key;
@@ -0,0 +1,192 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        business_member::BusinessMember, collector::Collector, pickup_request::PickupRequest,
+        user::User,
+    },
+    geo::validate_coordinates,
+    AppState,
+};
+
+use super::{parse_time, pickup_scope, require_own_business};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddPickupPayload {
+    pub business_id: Uuid,
+    /// The household selling the material. Defaults to the collector making
+    /// the request.
+    pub collector_id: Option<Uuid>,
+    /// The material to pick up, one of the businesses products.
+    pub product_id: Uuid,
+    pub estimated_weight: BigDecimal,
+    /// Defaults to the collectors address.
+    pub address: Option<String>,
+    pub city: Option<String>,
+    /// Needed to plan the pickup into a route, set together with the longitude.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub preferred_date: NaiveDate,
+    /// The start of the preferred window, like 08:00.
+    pub window_start: Option<String>,
+    /// The end of the preferred window, like 12:00.
+    pub window_end: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Requests a pickup. Collectors request pickups for themselves, businesses
+/// and staff on behalf of a collector.
+#[utoipa::path(
+    post,
+    path = "/pickup/add",
+    request_body = AddPickupPayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn pickup(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddPickupPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scope = pickup_scope(&app_state, &authenticated_user, &membership).await?;
+
+    require_own_business(&authenticated_user, &membership, payload.business_id)?;
+
+    let collector_id = match (scope.collector_id, payload.collector_id) {
+        (Some(own_collector_id), Some(collector_id)) if own_collector_id != collector_id => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "You may only request pickups for yourself."
+                })),
+            ))
+        }
+        (_, Some(collector_id)) | (Some(collector_id), None) => collector_id,
+        (None, None) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "A collector_id is required."
+                })),
+            ))
+        }
+    };
+
+    let bad_request = |reason: &str| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    validate_coordinates(payload.latitude, payload.longitude)?;
+
+    if payload.estimated_weight <= BigDecimal::zero() {
+        return Err(bad_request("The estimated weight must be more than 0."));
+    }
+
+    let window_start = parse_time(&payload.window_start)?;
+    let window_end = parse_time(&payload.window_end)?;
+
+    if let (Some(window_start), Some(window_end)) = (window_start, window_end) {
+        if window_start >= window_end {
+            return Err(bad_request("The window must end after it starts."));
+        }
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile WHERE id = $1
+        "#,
+        collector_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Collector not found."
+        })),
+    ))?;
+
+    let product_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM product WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        payload.product_id,
+        payload.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !product_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let pickup = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        INSERT INTO pickup_request (business_id, collector_id, product_id, estimated_weight, address, city, latitude, longitude, preferred_date, window_start, window_end, notes, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING *
+        "#,
+        payload.business_id,
+        collector.id,
+        payload.product_id,
+        payload.estimated_weight,
+        payload.address.unwrap_or(collector.address),
+        payload.city.unwrap_or(collector.city),
+        payload.latitude,
+        payload.longitude,
+        payload.preferred_date,
+        window_start,
+        window_end,
+        payload.notes.unwrap_or_default(),
+        authenticated_user.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickup": pickup
+        })),
+    ))
+}
@@ -0,0 +1,186 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        business_member::BusinessMember, collection::Collection, pickup_request::PickupRequest,
+        user::User,
+    },
+    routes::collector::require_approved_collector,
+    webhooks::{self, COLLECTION_CREATED},
+    AppState,
+};
+
+use super::{
+    find_pickup, require_fleet_manager, STATUS_COMPLETED, STATUS_REQUESTED, STATUS_SCHEDULED,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct CompletePickupPayload {
+    /// The weight collected at the address.
+    pub weight: BigDecimal,
+    /// The material collected, when it differs from the one requested.
+    pub product_id: Option<Uuid>,
+}
+
+/// Completes the pickup, recording what was collected as a collection of the
+/// household's collector.
+#[utoipa::path(
+    post,
+    path = "/pickup/{pickup_id}/complete",
+    params(("pickup_id" = String, Path, description = "The pickups id.")),
+    request_body = CompletePickupPayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn complete(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(pickup_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<CompletePickupPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to complete pickups.",
+    )?;
+
+    let pickup = find_pickup(&app_state, &authenticated_user, &membership, pickup_id).await?;
+
+    if payload.weight <= BigDecimal::zero() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The weight must be more than 0."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    require_approved_collector(&app_state, pickup.collector_id).await?;
+
+    let product_id = payload.product_id.unwrap_or(pickup.product_id);
+
+    let product_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM product WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        product_id,
+        pickup.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !product_exists {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    // Claims the pickup first, so that it is only ever completed once.
+    let claimed = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM pickup_request WHERE id = $1 AND status IN ($2, $3) FOR UPDATE
+        "#,
+        pickup.id,
+        STATUS_REQUESTED,
+        STATUS_SCHEDULED
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    if claimed.is_none() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The pickup is already {}.", pickup.status.to_lowercase())
+            })),
+        ));
+    }
+
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+        INSERT INTO collection (business_id, collector_id, product_id, weight, latitude, longitude)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+        pickup.business_id,
+        pickup.collector_id,
+        product_id,
+        payload.weight,
+        pickup.latitude,
+        pickup.longitude
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let pickup = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        UPDATE pickup_request SET
+            status = $2,
+            collection_id = $3,
+            completed_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        pickup.id,
+        STATUS_COMPLETED,
+        collection.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    webhooks::emit(
+        &app_state.pool,
+        COLLECTION_CREATED,
+        Some(collection.business_id),
+        json!(collection),
+    )
+    .await;
+
+    app_state
+        .collection_feed
+        .publish(&app_state.pool, COLLECTION_CREATED, &collection)
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickup": pickup,
+            "collection": collection
+        })),
+    ))
+}
@@ -0,0 +1,212 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, driver::Driver, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::{require_fleet_manager, require_own_business};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddDriverPayload {
+    pub business_id: Uuid,
+    pub first_name: String,
+    pub last_name: String,
+    pub phone_number: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateDriverPayload {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub phone_number: Option<String>,
+    /// Inactive drivers can not be assigned to pickups.
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/pickup/driver",
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn drivers(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to access drivers.",
+    )?;
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let drivers = sqlx::query_as!(
+        Driver,
+        r#"
+        SELECT * FROM driver WHERE ($1::uuid IS NULL OR business_id = $1) ORDER BY last_name, first_name
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "drivers": drivers
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pickup/driver",
+    request_body = AddDriverPayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn add(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddDriverPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to add drivers.",
+    )?;
+    require_own_business(&authenticated_user, &membership, payload.business_id)?;
+
+    let driver = sqlx::query_as!(
+        Driver,
+        r#"
+        INSERT INTO driver (business_id, first_name, last_name, phone_number)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.first_name,
+        payload.last_name,
+        payload.phone_number
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "driver": driver
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pickup/driver/{driver_id}",
+    params(("driver_id" = String, Path, description = "The drivers id.")),
+    request_body = UpdateDriverPayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn update(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(driver_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateDriverPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to update drivers.",
+    )?;
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let driver = sqlx::query_as!(
+        Driver,
+        r#"
+        UPDATE driver SET
+            first_name = COALESCE($3, first_name),
+            last_name = COALESCE($4, last_name),
+            phone_number = COALESCE($5, phone_number),
+            active = COALESCE($6, active),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        RETURNING *
+        "#,
+        driver_id,
+        business_id,
+        payload.first_name,
+        payload.last_name,
+        payload.phone_number,
+        payload.active
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Driver not found."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "driver": driver
+        })),
+    ))
+}
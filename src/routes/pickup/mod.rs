@@ -0,0 +1,180 @@
+use axum::{http::StatusCode, Json};
+use chrono::NaiveTime;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, pickup_request::PickupRequest, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+pub mod add;
+pub mod complete;
+pub mod driver;
+pub mod route;
+pub mod update;
+pub mod vehicle;
+pub mod view;
+
+pub const STATUS_REQUESTED: &str = "Requested";
+pub const STATUS_SCHEDULED: &str = "Scheduled";
+pub const STATUS_COMPLETED: &str = "Completed";
+pub const STATUS_CANCELLED: &str = "Cancelled";
+
+/// The pickups a user may see. Staff see every pickup, businesses those of
+/// their own business and collectors the ones at their own address.
+#[derive(Debug, Clone, Copy)]
+pub struct PickupScope {
+    pub business_id: Option<Uuid>,
+    pub collector_id: Option<Uuid>,
+}
+
+pub async fn pickup_scope(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+) -> Result<PickupScope, (StatusCode, Json<Value>)> {
+    match authenticated_user.role() {
+        Role::Staff | Role::SystemAdmin | Role::Business => Ok(PickupScope {
+            business_id: business_scope(authenticated_user, membership)?,
+            collector_id: None,
+        }),
+        Role::Collector => {
+            let collector_id = sqlx::query_scalar!(
+                r#"
+                SELECT id FROM collector_profile WHERE user_id = $1
+                "#,
+                authenticated_user.id
+            )
+            .fetch_optional(&app_state.pool)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                )
+            })?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Collector not found."
+                })),
+            ))?;
+
+            Ok(PickupScope {
+                business_id: None,
+                collector_id: Some(collector_id),
+            })
+        }
+    }
+}
+
+/// Only staff and businesses run trucks and plan routes.
+pub fn require_fleet_manager(
+    authenticated_user: &User,
+    reason: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": reason
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that a business user only manages their own business.
+pub fn require_own_business(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    if let Some(own_business_id) = business_scope(authenticated_user, membership)? {
+        if own_business_id != business_id {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "You may only manage pickups of your own business."
+                })),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds a pickup the user may see.
+pub async fn find_pickup(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    pickup_id: Uuid,
+) -> Result<PickupRequest, (StatusCode, Json<Value>)> {
+    let scope = pickup_scope(app_state, authenticated_user, membership).await?;
+
+    sqlx::query_as!(
+        PickupRequest,
+        r#"
+        SELECT * FROM pickup_request
+        WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2) AND ($3::uuid IS NULL OR collector_id = $3)
+        "#,
+        pickup_id,
+        scope.business_id,
+        scope.collector_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Pickup not found."
+        })),
+    ))
+}
+
+/// Parses an optional time of day like 08:00.
+pub fn parse_time(time: &Option<String>) -> Result<Option<NaiveTime>, (StatusCode, Json<Value>)> {
+    match time {
+        Some(time) => NaiveTime::parse_from_str(time.trim(), "%H:%M")
+            .map(Some)
+            .map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "Bad Request",
+                        "reason": format!("{} is not a time like 08:00.", time)
+                    })),
+                )
+            }),
+        None => Ok(None),
+    }
+}
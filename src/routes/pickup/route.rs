@@ -0,0 +1,203 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        business_member::BusinessMember, driver::Driver, pickup_request::PickupRequest, user::User,
+    },
+    geo::{haversine_km, plan_route},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::{require_fleet_manager, STATUS_SCHEDULED};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RouteQuery {
+    pub driver_id: Uuid,
+    /// Defaults to today.
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteStop {
+    pub stop_order: i32,
+    /// The distance from the previous stop, or from the depot for the first
+    /// stop. Empty for pickups without coordinates.
+    pub distance_km: Option<f64>,
+    pub pickup: PickupRequest,
+}
+
+fn round_km(distance_km: f64) -> f64 {
+    (distance_km * 100.0).round() / 100.0
+}
+
+/// Plans the route sheet of a driver for a day. The scheduled pickups are
+/// ordered into a short route starting at the business, and the order is
+/// saved on the pickups. Pickups without coordinates go last, by their window.
+#[utoipa::path(
+    get,
+    path = "/pickup/route",
+    params(
+        ("driver_id" = String, Query, description = "The drivers id."),
+        ("date" = Option<String>, Query, description = "The day of the route, like 2024-03-01. Defaults to today."),
+    ),
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn route(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<RouteQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to plan routes.",
+    )?;
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+    let date = query.date.unwrap_or(Utc::now().date_naive());
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let driver = sqlx::query_as!(
+        Driver,
+        r#"
+        SELECT * FROM driver WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        query.driver_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Driver not found."
+        })),
+    ))?;
+
+    let depot = sqlx::query!(
+        r#"
+        SELECT latitude, longitude FROM business_profile WHERE id = $1
+        "#,
+        driver.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let pickups = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        SELECT * FROM pickup_request
+        WHERE driver_id = $1 AND scheduled_date = $2 AND status = $3
+        ORDER BY window_start NULLS LAST, created_at
+        "#,
+        driver.id,
+        date,
+        STATUS_SCHEDULED
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let (located, unlocated): (Vec<PickupRequest>, Vec<PickupRequest>) = pickups
+        .into_iter()
+        .partition(|pickup| pickup.latitude.is_some() && pickup.longitude.is_some());
+
+    let coordinates: Vec<(f64, f64)> = located
+        .iter()
+        .map(|pickup| {
+            (
+                pickup.latitude.unwrap_or_default(),
+                pickup.longitude.unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    // Without a location for the business the route starts at the earliest
+    // stop that has one.
+    let depot = match (depot.latitude, depot.longitude) {
+        (Some(latitude), Some(longitude)) => Some((latitude, longitude)),
+        _ => coordinates.first().copied(),
+    };
+
+    let mut located: Vec<Option<PickupRequest>> = located.into_iter().map(Some).collect();
+    let mut stops = Vec::new();
+    let mut total_distance_km = 0.0;
+
+    if let Some(depot) = depot {
+        let mut position = depot;
+
+        for index in plan_route(depot, &coordinates) {
+            let distance_km = haversine_km(position, coordinates[index]);
+            position = coordinates[index];
+            total_distance_km += distance_km;
+
+            if let Some(pickup) = located[index].take() {
+                stops.push((Some(round_km(distance_km)), pickup));
+            }
+        }
+    }
+
+    stops.extend(unlocated.into_iter().map(|pickup| (None, pickup)));
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+    let mut route = Vec::with_capacity(stops.len());
+
+    for (index, (distance_km, pickup)) in stops.into_iter().enumerate() {
+        let pickup = sqlx::query_as!(
+            PickupRequest,
+            r#"
+            UPDATE pickup_request SET stop_order = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING *
+            "#,
+            pickup.id,
+            index as i32 + 1
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        route.push(RouteStop {
+            stop_order: index as i32 + 1,
+            distance_km,
+            pickup,
+        });
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "driver": driver,
+            "date": date,
+            "depot": depot.map(|(latitude, longitude)| json!({
+                "latitude": latitude,
+                "longitude": longitude
+            })),
+            "total_distance_km": round_km(total_distance_km),
+            "stops": route
+        })),
+    ))
+}
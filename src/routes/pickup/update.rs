@@ -0,0 +1,201 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, pickup_request::PickupRequest, user::User},
+    AppState,
+};
+
+use super::{
+    find_pickup, require_fleet_manager, STATUS_CANCELLED, STATUS_REQUESTED, STATUS_SCHEDULED,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AssignPickupPayload {
+    pub driver_id: Uuid,
+    pub vehicle_id: Uuid,
+    /// Defaults to the preferred date.
+    pub scheduled_date: Option<NaiveDate>,
+}
+
+fn require_open(pickup: &PickupRequest) -> Result<(), (StatusCode, Json<Value>)> {
+    if pickup.status != STATUS_REQUESTED && pickup.status != STATUS_SCHEDULED {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("The pickup is already {}.", pickup.status.to_lowercase())
+            })),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Schedules the pickup with a driver and vehicle of the business. The stop is
+/// placed in the route the next time the route sheet is planned.
+#[utoipa::path(
+    post,
+    path = "/pickup/{pickup_id}/assign",
+    params(("pickup_id" = String, Path, description = "The pickups id.")),
+    request_body = AssignPickupPayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn assign(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(pickup_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<AssignPickupPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to assign pickups.",
+    )?;
+
+    let pickup = find_pickup(&app_state, &authenticated_user, &membership, pickup_id).await?;
+
+    require_open(&pickup)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let driver_available = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM driver WHERE id = $1 AND business_id = $2 AND active) AS "exists!"
+        "#,
+        payload.driver_id,
+        pickup.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !driver_available {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "No active driver found."
+            })),
+        ));
+    }
+
+    let vehicle_available = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM vehicle WHERE id = $1 AND business_id = $2 AND active) AS "exists!"
+        "#,
+        payload.vehicle_id,
+        pickup.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !vehicle_available {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "No active vehicle found."
+            })),
+        ));
+    }
+
+    let pickup = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        UPDATE pickup_request SET
+            status = $2,
+            driver_id = $3,
+            vehicle_id = $4,
+            scheduled_date = $5,
+            stop_order = NULL,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        pickup.id,
+        STATUS_SCHEDULED,
+        payload.driver_id,
+        payload.vehicle_id,
+        payload.scheduled_date.unwrap_or(pickup.preferred_date)
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickup": pickup
+        })),
+    ))
+}
+
+/// Cancels a pickup that has not been completed. Collectors may cancel their
+/// own pickups.
+#[utoipa::path(
+    post,
+    path = "/pickup/{pickup_id}/cancel",
+    params(("pickup_id" = String, Path, description = "The pickups id.")),
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn cancel(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(pickup_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let pickup = find_pickup(&app_state, &authenticated_user, &membership, pickup_id).await?;
+
+    require_open(&pickup)?;
+
+    let pickup = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        UPDATE pickup_request SET status = $2, stop_order = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1
+        RETURNING *
+        "#,
+        pickup.id,
+        STATUS_CANCELLED
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickup": pickup
+        })),
+    ))
+}
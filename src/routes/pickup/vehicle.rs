@@ -0,0 +1,229 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User, vehicle::Vehicle},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::{require_fleet_manager, require_own_business};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddVehiclePayload {
+    pub business_id: Uuid,
+    pub registration: String,
+    pub description: Option<String>,
+    pub capacity_kg: Option<BigDecimal>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateVehiclePayload {
+    pub registration: Option<String>,
+    pub description: Option<String>,
+    pub capacity_kg: Option<BigDecimal>,
+    /// Inactive vehicles can not be assigned to pickups.
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/pickup/vehicle",
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn vehicles(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to access vehicles.",
+    )?;
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let vehicles = sqlx::query_as!(
+        Vehicle,
+        r#"
+        SELECT * FROM vehicle WHERE ($1::uuid IS NULL OR business_id = $1) ORDER BY registration
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "vehicles": vehicles
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pickup/vehicle",
+    request_body = AddVehiclePayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn add(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddVehiclePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to add vehicles.",
+    )?;
+    require_own_business(&authenticated_user, &membership, payload.business_id)?;
+
+    let vehicle = sqlx::query_as!(
+        Vehicle,
+        r#"
+        INSERT INTO vehicle (business_id, registration, description, capacity_kg)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.registration.trim(),
+        payload.description.unwrap_or_default(),
+        payload.capacity_kg
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A vehicle with this registration already exists."
+            })),
+        ),
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "vehicle": vehicle
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pickup/vehicle/{vehicle_id}",
+    params(("vehicle_id" = String, Path, description = "The vehicles id.")),
+    request_body = UpdateVehiclePayload,
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn update(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(vehicle_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateVehiclePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_fleet_manager(
+        &authenticated_user,
+        "You do not have permission to update vehicles.",
+    )?;
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let vehicle = sqlx::query_as!(
+        Vehicle,
+        r#"
+        UPDATE vehicle SET
+            registration = COALESCE($3, registration),
+            description = COALESCE($4, description),
+            capacity_kg = COALESCE($5, capacity_kg),
+            active = COALESCE($6, active),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        RETURNING *
+        "#,
+        vehicle_id,
+        business_id,
+        payload.registration.as_deref().map(str::trim),
+        payload.description,
+        payload.capacity_kg,
+        payload.active
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A vehicle with this registration already exists."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Vehicle not found."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "vehicle": vehicle
+        })),
+    ))
+}
@@ -0,0 +1,103 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, pickup_request::PickupRequest, user::User},
+    AppState,
+};
+
+use super::{find_pickup, pickup_scope};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PickupsQuery {
+    pub status: Option<String>,
+    /// The scheduled date, or the preferred date of unscheduled pickups.
+    pub date: Option<NaiveDate>,
+    pub driver_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/pickup",
+    params(
+        ("status" = Option<String>, Query, description = "One of Requested, Scheduled, Completed or Cancelled."),
+        ("date" = Option<String>, Query, description = "The scheduled date, or the preferred date of unscheduled pickups, like 2024-03-01."),
+        ("driver_id" = Option<String>, Query, description = "Only pickups assigned to this driver."),
+    ),
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn pickups(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<PickupsQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scope = pickup_scope(&app_state, &authenticated_user, &membership).await?;
+
+    let pickups = sqlx::query_as!(
+        PickupRequest,
+        r#"
+        SELECT * FROM pickup_request
+        WHERE ($1::uuid IS NULL OR business_id = $1)
+            AND ($2::uuid IS NULL OR collector_id = $2)
+            AND ($3::text IS NULL OR status = $3)
+            AND ($4::date IS NULL OR COALESCE(scheduled_date, preferred_date) = $4)
+            AND ($5::uuid IS NULL OR driver_id = $5)
+        ORDER BY COALESCE(scheduled_date, preferred_date), stop_order NULLS LAST, window_start NULLS LAST
+        "#,
+        scope.business_id,
+        scope.collector_id,
+        query.status,
+        query.date,
+        query.driver_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickups": pickups
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/pickup/{pickup_id}",
+    params(("pickup_id" = String, Path, description = "The pickups id.")),
+    tag = "Pickup",
+    security(("bearer_auth" = [])),
+)]
+pub async fn pickup(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(pickup_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let pickup = find_pickup(&app_state, &authenticated_user, &membership, pickup_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "pickup": pickup
+        })),
+    ))
+}
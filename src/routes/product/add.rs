@@ -7,7 +7,10 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{business::Business, product::Product, user::User},
+    data::entities::{
+        business::Business, business_member::BusinessMember, product::Product, user::User,
+    },
+    routes::business::require_business_manager,
     AppState,
 };
 
@@ -17,6 +20,7 @@ pub struct AddProductPayload {
     pub name: String,
     pub description: String,
     pub price: BigDecimal,
+    pub code: Option<String>,
 }
 
 #[utoipa::path(
@@ -29,6 +33,7 @@ pub struct AddProductPayload {
 pub async fn product(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Json(payload): extract::Json<AddProductPayload>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
     let requirement_a = authenticated_user.role() != Role::Staff
@@ -44,6 +49,13 @@ pub async fn product(
         ));
     }
 
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        payload.business_id,
+        "Only owners and managers can add products.",
+    )?;
+
     let existing_business = sqlx::query_as!(
         Business,
         r#"
@@ -74,14 +86,15 @@ pub async fn product(
     let product = sqlx::query_as!(
         Product,
         r#"
-        INSERT INTO product (business_id, name, description, price)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO product (business_id, name, description, price, code)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING *
         "#,
         payload.business_id,
         payload.name,
         payload.description,
-        payload.price
+        payload.price,
+        payload.code
     )
     .fetch_one(&app_state.pool)
     .await
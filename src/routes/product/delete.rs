@@ -5,7 +5,8 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{product::Product, user::User},
+    data::entities::{business_member::BusinessMember, product::Product, user::User},
+    routes::business::require_business_manager,
     AppState,
 };
 
@@ -19,6 +20,7 @@ use crate::{
 pub async fn product(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Path(product_id): extract::Path<Uuid>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
     let requirement_a = authenticated_user.role() != Role::SystemAdmin
@@ -62,6 +64,13 @@ pub async fn product(
 
     let product = existing_product.unwrap();
 
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        product.business_id,
+        "Only owners and managers can remove products.",
+    )?;
+
     sqlx::query!(
         r#"
         DELETE FROM product WHERE id = $1
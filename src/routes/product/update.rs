@@ -7,7 +7,8 @@ use uuid::Uuid;
 
 use crate::{
     authentication::roles::Role,
-    data::entities::{product::Product, user::User},
+    data::entities::{business_member::BusinessMember, product::Product, user::User},
+    routes::business::require_business_manager,
     AppState,
 };
 
@@ -16,6 +17,7 @@ pub struct UpdateProductPayload {
     pub name: Option<String>,
     pub description: Option<String>,
     pub price: Option<BigDecimal>,
+    pub code: Option<String>,
 }
 
 #[utoipa::path(
@@ -29,6 +31,7 @@ pub struct UpdateProductPayload {
 pub async fn product(
     extract::State(app_state): extract::State<AppState>,
     extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
     extract::Path(product_id): extract::Path<Uuid>,
     extract::Json(payload): extract::Json<UpdateProductPayload>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
@@ -79,21 +82,30 @@ pub async fn product(
 
     let product = existing_product.unwrap();
 
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        product.business_id,
+        "Only owners and managers can change products.",
+    )?;
+
     let name = payload.name.unwrap_or(product.name);
     let description = payload.description.unwrap_or(product.description);
     let price = payload.price.unwrap_or(product.price);
+    let code = payload.code.or(product.code);
 
     let product = sqlx::query_as!(
         Product,
         r#"
         UPDATE product
-        SET name = $1, description = $2, price = $3
-        WHERE id = $4
+        SET name = $1, description = $2, price = $3, code = $4
+        WHERE id = $5
         RETURNING *
         "#,
         name,
         description,
         price,
+        code,
         product.id
     )
     .fetch_one(&app_state.pool)
@@ -36,7 +36,9 @@ pub async fn products(
             let business = sqlx::query_as!(
                 Business,
                 r#"
-                SELECT * FROM business_profile WHERE user_id = $1
+                SELECT business_profile.* FROM business_profile
+                INNER JOIN business_member ON business_member.business_id = business_profile.id
+                WHERE business_member.user_id = $1
                 "#,
                 authenticated_user.id
             )
@@ -0,0 +1,158 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business::Business, report_job::ReportJob, user::User},
+    scheduler::{parse_schedule, parse_utc_offset, report_type::ReportType},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddReportJobPayload {
+    pub name: String,
+    pub report_type: ReportType,
+    pub business_id: Option<Uuid>,
+    /// `sec min hour day-of-month month day-of-week`, e.g. `0 0 6 * * Mon`.
+    pub cron_expression: String,
+    /// Defaults to 120 (SAST).
+    pub utc_offset_minutes: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/report/job/add",
+    request_body = AddReportJobPayload,
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_job(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Json(payload): extract::Json<AddReportJobPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to add report jobs."
+            })),
+        ));
+    }
+
+    if let Err(error) = parse_schedule(&payload.cron_expression) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("Invalid cron expression: {}", error)
+            })),
+        ));
+    }
+
+    let utc_offset_minutes = payload.utc_offset_minutes.unwrap_or(120);
+
+    if let Err(error) = parse_utc_offset(utc_offset_minutes) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": error.to_string()
+            })),
+        ));
+    }
+
+    if let Some(business_id) = payload.business_id {
+        let existing_business = sqlx::query_as!(
+            Business,
+            r#"
+            SELECT * FROM business_profile WHERE id = $1
+            "#,
+            business_id
+        )
+        .fetch_optional(&app_state.pool)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+        if existing_business.is_none() {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Business not found."
+                })),
+            ));
+        }
+    }
+
+    let report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        INSERT INTO report_job (name, report_type, business_id, cron_expression, utc_offset_minutes, active, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        payload.name,
+        payload.report_type.to_string(),
+        payload.business_id,
+        payload.cron_expression,
+        utc_offset_minutes,
+        payload.active.unwrap_or(true),
+        authenticated_user.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state
+        .scheduler
+        .schedule(&report_job)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to schedule report job: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to schedule report job."
+                })),
+            )
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "report_job": report_job
+        })),
+    ))
+}
@@ -0,0 +1,79 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{report_job::ReportJob, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/report/job/{report_job_id}",
+    params(("report_job_id" = String, Path, description = "The report jobs id.")),
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_job(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to delete report jobs."
+            })),
+        ));
+    }
+
+    let deleted_report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        DELETE FROM report_job WHERE id = $1
+        RETURNING *
+        "#,
+        report_job_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if deleted_report_job.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Report job not found."
+            })),
+        ));
+    }
+
+    if let Err(error) = app_state.scheduler.unschedule(report_job_id).await {
+        tracing::error!("🔥 Failed to unschedule report job: {}", error);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_job": deleted_report_job.unwrap()
+        })),
+    ))
+}
@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use axum::{extract, http::StatusCode, response::Response, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{report_job_run::ReportJobRun, user::User},
+    routes::statement::file_response,
+    AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/report/run/{report_job_run_id}/download",
+    params(("report_job_run_id" = String, Path, description = "The report job runs id.")),
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn download(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_run_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to download reports."
+            })),
+        ));
+    }
+
+    let report_job_run = sqlx::query_as!(
+        ReportJobRun,
+        r#"
+        SELECT * FROM report_job_run WHERE id = $1
+        "#,
+        report_job_run_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let output_path = match report_job_run.and_then(|run| run.output_path) {
+        Some(output_path) => output_path,
+        None => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "error": "Not Found",
+                    "reason": "Report not found."
+                })),
+            ))
+        }
+    };
+
+    let body = tokio::fs::read(&output_path).await.map_err(|error| {
+        tracing::error!("🔥 Failed to read report {}: {}", output_path, error);
+
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "The report is no longer in the reports store."
+            })),
+        )
+    })?;
+
+    let file_name = Path::new(&output_path)
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_else(|| format!("{}.csv", report_job_run_id));
+
+    Ok((StatusCode::OK, file_response(file_name, "text/csv", body)))
+}
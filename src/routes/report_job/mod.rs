@@ -0,0 +1,6 @@
+pub mod add;
+pub mod delete;
+pub mod download;
+pub mod run;
+pub mod update;
+pub mod view;
@@ -0,0 +1,91 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{report_job::ReportJob, user::User},
+    scheduler::reports::run_report_job,
+    AppState,
+};
+
+/// Runs a report job immediately, outside of its schedule.
+#[utoipa::path(
+    post,
+    path = "/report/job/{report_job_id}/run",
+    params(("report_job_id" = String, Path, description = "The report jobs id.")),
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn run(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to run report jobs."
+            })),
+        ));
+    }
+
+    let existing_report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        SELECT * FROM report_job WHERE id = $1
+        "#,
+        report_job_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_report_job.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Report job not found."
+            })),
+        ));
+    }
+
+    let report_job_run = run_report_job(
+        &app_state.pool,
+        &app_state.config.reports_directory,
+        report_job_id,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to run report job: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to run report job."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_job_run": report_job_run
+        })),
+    ))
+}
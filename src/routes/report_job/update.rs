@@ -0,0 +1,168 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{report_job::ReportJob, user::User},
+    scheduler::{parse_schedule, parse_utc_offset, report_type::ReportType},
+    AppState,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateReportJobPayload {
+    pub name: Option<String>,
+    pub report_type: Option<ReportType>,
+    pub cron_expression: Option<String>,
+    pub utc_offset_minutes: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/report/job/{report_job_id}",
+    params(("report_job_id" = String, Path, description = "The report jobs id.")),
+    request_body = UpdateReportJobPayload,
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_job(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateReportJobPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a =
+        authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to update report jobs."
+            })),
+        ));
+    }
+
+    let existing_report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        SELECT * FROM report_job WHERE id = $1
+        "#,
+        report_job_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_report_job.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Report job not found."
+            })),
+        ));
+    }
+
+    let report_job = existing_report_job.unwrap();
+
+    let name = payload.name.unwrap_or(report_job.name);
+    let report_type = payload
+        .report_type
+        .map(|report_type| report_type.to_string())
+        .unwrap_or(report_job.report_type);
+    let cron_expression = payload
+        .cron_expression
+        .unwrap_or(report_job.cron_expression);
+    let utc_offset_minutes = payload
+        .utc_offset_minutes
+        .unwrap_or(report_job.utc_offset_minutes);
+    let active = payload.active.unwrap_or(report_job.active);
+
+    if let Err(error) = parse_schedule(&cron_expression) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": format!("Invalid cron expression: {}", error)
+            })),
+        ));
+    }
+
+    if let Err(error) = parse_utc_offset(utc_offset_minutes) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": error.to_string()
+            })),
+        ));
+    }
+
+    let report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        UPDATE report_job
+        SET name = $1, report_type = $2, cron_expression = $3, utc_offset_minutes = $4, active = $5, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $6
+        RETURNING *
+        "#,
+        name,
+        report_type,
+        cron_expression,
+        utc_offset_minutes,
+        active,
+        report_job.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state
+        .scheduler
+        .schedule(&report_job)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to schedule report job: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to schedule report job."
+                })),
+            )
+        })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_job": report_job
+        })),
+    ))
+}
@@ -0,0 +1,172 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{report_job::ReportJob, report_job_run::ReportJobRun, user::User},
+    AppState,
+};
+
+#[utoipa::path(
+    get,
+    path = "/report/job",
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_jobs(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to view report jobs."
+            })),
+        ));
+    }
+
+    let report_jobs = sqlx::query_as!(
+        ReportJob,
+        r#"
+        SELECT * FROM report_job ORDER BY created_at DESC
+        "#
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_jobs": report_jobs
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/report/job/{report_job_id}",
+    params(("report_job_id" = String, Path, description = "The report jobs id.")),
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_job(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to view report jobs."
+            })),
+        ));
+    }
+
+    let report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+        SELECT * FROM report_job WHERE id = $1
+        "#,
+        report_job_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if report_job.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Report job not found."
+            })),
+        ));
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_job": report_job.unwrap()
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/report/job/{report_job_id}/runs",
+    params(("report_job_id" = String, Path, description = "The report jobs id.")),
+    tag = "Report",
+    security(("bearer_auth" = [])),
+)]
+pub async fn report_job_runs(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(report_job_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to view report jobs."
+            })),
+        ));
+    }
+
+    let report_job_runs = sqlx::query_as!(
+        ReportJobRun,
+        r#"
+        SELECT * FROM report_job_run WHERE report_job_id = $1 ORDER BY started_at DESC
+        "#,
+        report_job_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "report_job_runs": report_job_runs
+        })),
+    ))
+}
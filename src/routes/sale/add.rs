@@ -0,0 +1,194 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, material_sale::MaterialSale, user::User},
+    routes::stock::{lock_stock, MOVEMENT_SALE},
+    AppState,
+};
+
+use super::require_sale_business;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddSalePayload {
+    pub business_id: Uuid,
+    pub offtaker_id: Uuid,
+    /// The material sold, taken out of stock.
+    pub product_id: Uuid,
+    pub weight: BigDecimal,
+    pub price_per_kg: BigDecimal,
+    pub weighbridge_ticket: Option<String>,
+    /// Unique per business when given.
+    pub invoice_number: Option<String>,
+    /// Defaults to now.
+    pub sold_at: Option<NaiveDateTime>,
+}
+
+/// Records a sale to an off-taker and takes the weight sold out of stock.
+#[utoipa::path(
+    post,
+    path = "/sale/add",
+    request_body = AddSalePayload,
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddSalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_sale_business(&authenticated_user, &membership, payload.business_id)?;
+
+    if payload.weight <= BigDecimal::zero() || payload.price_per_kg < BigDecimal::zero() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "The weight must be more than 0 and the price may not be negative."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let not_found = |reason: &str| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": reason
+            })),
+        )
+    };
+
+    let offtaker_active = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM offtaker WHERE id = $1 AND business_id = $2 AND active) AS "exists!"
+        "#,
+        payload.offtaker_id,
+        payload.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !offtaker_active {
+        return Err(not_found("No active off-taker found."));
+    }
+
+    let product_exists = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM product WHERE id = $1 AND business_id = $2) AS "exists!"
+        "#,
+        payload.product_id,
+        payload.business_id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if !product_exists {
+        return Err(not_found("Product not found."));
+    }
+
+    let weighbridge_ticket = payload
+        .weighbridge_ticket
+        .map(|ticket| ticket.trim().to_string())
+        .unwrap_or_default();
+    let invoice_number = payload
+        .invoice_number
+        .map(|invoice_number| invoice_number.trim().to_string())
+        .unwrap_or_default();
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let on_hand = lock_stock(&mut transaction, payload.product_id)
+        .await
+        .map_err(database_error)?;
+
+    if on_hand < payload.weight {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("Only {} is in stock.", on_hand),
+                "on_hand": on_hand
+            })),
+        ));
+    }
+
+    let stock_movement_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO stock_movement (business_id, product_id, movement_type, quantity, reference, created_by, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, CURRENT_TIMESTAMP::TIMESTAMP))
+        RETURNING id
+        "#,
+        payload.business_id,
+        payload.product_id,
+        MOVEMENT_SALE,
+        -payload.weight.clone(),
+        invoice_number,
+        authenticated_user.id,
+        payload.sold_at
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let sale = sqlx::query_as!(
+        MaterialSale,
+        r#"
+        INSERT INTO material_sale (business_id, offtaker_id, product_id, weight, price_per_kg, weighbridge_ticket, invoice_number, stock_movement_id, sold_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($9, CURRENT_TIMESTAMP::TIMESTAMP), $10)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.offtaker_id,
+        payload.product_id,
+        payload.weight,
+        payload.price_per_kg,
+        weighbridge_ticket,
+        invoice_number,
+        stock_movement_id,
+        payload.sold_at,
+        authenticated_user.id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_unique_violation() => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": "A sale with this invoice number already exists."
+            })),
+        ),
+        error => database_error(error),
+    })?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "sale": sale
+        })),
+    ))
+}
@@ -0,0 +1,96 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, material_sale::MaterialSale, user::User},
+    routes::bale::STATUS_CLOSED,
+    AppState,
+};
+
+use super::sale_scope;
+
+/// Deletes a sale recorded in error and puts its weight back into stock. Bales
+/// sold with it are closed again.
+#[utoipa::path(
+    delete,
+    path = "/sale/{sale_id}",
+    params(("sale_id" = String, Path, description = "The sales id.")),
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(sale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = sale_scope(&authenticated_user, &membership, None)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    sqlx::query!(
+        r#"
+        UPDATE bale SET status = $2, sale_id = NULL
+        WHERE sale_id = $1 AND ($3::uuid IS NULL OR business_id = $3)
+        "#,
+        sale_id,
+        STATUS_CLOSED,
+        business_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let sale = sqlx::query_as!(
+        MaterialSale,
+        r#"
+        DELETE FROM material_sale WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        RETURNING *
+        "#,
+        sale_id,
+        business_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Sale not found."
+        })),
+    ))?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM stock_movement WHERE id = $1
+        "#,
+        sale.stock_movement_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "sale": sale
+        })),
+    ))
+}
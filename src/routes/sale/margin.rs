@@ -0,0 +1,129 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::stock::StockQuery,
+    AppState,
+};
+
+use super::sale_scope;
+
+/// What a business paid collectors for a material against what it sold the
+/// material for.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MaterialMargin {
+    pub business_id: Uuid,
+    pub material: String,
+    pub purchased_weight: BigDecimal,
+    pub purchase_cost: BigDecimal,
+    pub sold_weight: BigDecimal,
+    pub revenue: BigDecimal,
+    pub margin: BigDecimal,
+}
+
+/// Compares the purchase cost of collections with the revenue of sales per
+/// material over the period. Products in an EPR stream are one material, so
+/// that loose material bought and the baled material sold line up. Purchase
+/// costs use the price each collection was bought at, the same as collector
+/// statements.
+#[utoipa::path(
+    get,
+    path = "/sale/margin",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn margin(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = sale_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let materials = sqlx::query_as!(
+        MaterialMargin,
+        r#"
+        WITH material AS (
+            SELECT product.id AS product_id, product.business_id, COALESCE(epr_stream.name, product.name) AS name
+            FROM product
+            LEFT JOIN epr_product_stream ON epr_product_stream.product_id = product.id
+            LEFT JOIN epr_stream ON epr_stream.id = epr_product_stream.stream_id
+            WHERE ($1::uuid IS NULL OR product.business_id = $1)
+        ),
+        purchase AS (
+            SELECT material.business_id, material.name, SUM(collection.weight) AS weight, SUM(ROUND(collection.weight * collection.price, 2)) AS cost
+            FROM collection
+            INNER JOIN material ON material.product_id = collection.product_id
+            WHERE ($2::timestamp IS NULL OR collection.created_at >= $2)
+                AND ($3::timestamp IS NULL OR collection.created_at < $3)
+            GROUP BY material.business_id, material.name
+        ),
+        sale AS (
+            SELECT material.business_id, material.name, SUM(material_sale.weight) AS weight, SUM(material_sale.amount) AS revenue
+            FROM material_sale
+            INNER JOIN material ON material.product_id = material_sale.product_id
+            WHERE ($2::timestamp IS NULL OR material_sale.sold_at >= $2)
+                AND ($3::timestamp IS NULL OR material_sale.sold_at < $3)
+            GROUP BY material.business_id, material.name
+        )
+        SELECT
+            COALESCE(purchase.business_id, sale.business_id) AS "business_id!",
+            COALESCE(purchase.name, sale.name) AS "material!",
+            COALESCE(purchase.weight, 0) AS "purchased_weight!",
+            COALESCE(purchase.cost, 0) AS "purchase_cost!",
+            COALESCE(sale.weight, 0) AS "sold_weight!",
+            COALESCE(sale.revenue, 0) AS "revenue!",
+            COALESCE(sale.revenue, 0) - COALESCE(purchase.cost, 0) AS "margin!"
+        FROM purchase
+        FULL OUTER JOIN sale ON sale.business_id = purchase.business_id AND sale.name = purchase.name
+        ORDER BY 1, 2
+        "#,
+        business_id,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let purchase_cost = materials
+        .iter()
+        .fold(BigDecimal::zero(), |total, material| {
+            total + &material.purchase_cost
+        });
+    let revenue = materials
+        .iter()
+        .fold(BigDecimal::zero(), |total, material| {
+            total + &material.revenue
+        });
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "purchase_cost": purchase_cost,
+            "revenue": revenue,
+            "margin": &revenue - &purchase_cost,
+            "materials": materials
+        })),
+    ))
+}
@@ -0,0 +1,58 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+};
+
+pub mod add;
+pub mod delete;
+pub mod margin;
+pub mod offtaker;
+pub mod view;
+
+/// The business whose sales the user asked for. Business users always get
+/// their own business, staff any business or all of them.
+pub fn sale_scope(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Option<Uuid>,
+) -> Result<Option<Uuid>, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access sales."
+            })),
+        ));
+    }
+
+    Ok(business_scope(authenticated_user, membership)?.or(business_id))
+}
+
+/// Checks that the user may record sales for the business.
+pub fn require_sale_business(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Uuid,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    if sale_scope(authenticated_user, membership, Some(business_id))? != Some(business_id) {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You may only manage the sales of your own business."
+            })),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, offtaker::Offtaker, user::User},
+    AppState,
+};
+
+use super::{require_sale_business, sale_scope};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OfftakersQuery {
+    /// Ignored for business users, who only see their own off-takers.
+    pub business_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddOfftakerPayload {
+    pub business_id: Uuid,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub address: Option<String>,
+    pub registration_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateOfftakerPayload {
+    pub name: Option<String>,
+    pub contact_name: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<String>,
+    pub address: Option<String>,
+    pub registration_number: Option<String>,
+    /// Inactive off-takers can not be sold to.
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/sale/offtaker",
+    params(("business_id" = Option<String>, Query, description = "The businesses id, for staff.")),
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn offtakers(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<OfftakersQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = sale_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let offtakers = sqlx::query_as!(
+        Offtaker,
+        r#"
+        SELECT * FROM offtaker WHERE ($1::uuid IS NULL OR business_id = $1) ORDER BY name
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "offtakers": offtakers
+        })),
+    ))
+}
+
+/// Adds a recycler or converter the business sells material to.
+#[utoipa::path(
+    post,
+    path = "/sale/offtaker",
+    request_body = AddOfftakerPayload,
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn add(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddOfftakerPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_sale_business(&authenticated_user, &membership, payload.business_id)?;
+
+    let offtaker = sqlx::query_as!(
+        Offtaker,
+        r#"
+        INSERT INTO offtaker (business_id, name, contact_name, email, phone_number, address, registration_number)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.name,
+        payload.contact_name.unwrap_or_default(),
+        payload.email.unwrap_or_default(),
+        payload.phone_number.unwrap_or_default(),
+        payload.address.unwrap_or_default(),
+        payload.registration_number.unwrap_or_default()
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| match error {
+        sqlx::Error::Database(error) if error.is_foreign_key_violation() => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ),
+        error => {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        }
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "offtaker": offtaker
+        })),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/sale/offtaker/{offtaker_id}",
+    params(("offtaker_id" = String, Path, description = "The off-takers id.")),
+    request_body = UpdateOfftakerPayload,
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn update(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(offtaker_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateOfftakerPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = sale_scope(&authenticated_user, &membership, None)?;
+
+    let offtaker = sqlx::query_as!(
+        Offtaker,
+        r#"
+        UPDATE offtaker SET
+            name = COALESCE($3, name),
+            contact_name = COALESCE($4, contact_name),
+            email = COALESCE($5, email),
+            phone_number = COALESCE($6, phone_number),
+            address = COALESCE($7, address),
+            registration_number = COALESCE($8, registration_number),
+            active = COALESCE($9, active),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        RETURNING *
+        "#,
+        offtaker_id,
+        business_id,
+        payload.name,
+        payload.contact_name,
+        payload.email,
+        payload.phone_number,
+        payload.address,
+        payload.registration_number,
+        payload.active
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Off-taker not found."
+        })),
+    ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "offtaker": offtaker
+        })),
+    ))
+}
@@ -0,0 +1,79 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, material_sale::MaterialSale, user::User},
+    routes::stock::StockQuery,
+    AppState,
+};
+
+use super::sale_scope;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SalesQuery {
+    pub offtaker_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/sale",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("offtaker_id" = Option<String>, Query, description = "Only sales to this off-taker."),
+        ("product_id" = Option<String>, Query, description = "Only sales of this product."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Sale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sales(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+    extract::Query(sales_query): extract::Query<SalesQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = sale_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let sales = sqlx::query_as!(
+        MaterialSale,
+        r#"
+        SELECT * FROM material_sale
+        WHERE ($1::uuid IS NULL OR business_id = $1)
+            AND ($2::uuid IS NULL OR offtaker_id = $2)
+            AND ($3::uuid IS NULL OR product_id = $3)
+            AND ($4::timestamp IS NULL OR sold_at >= $4)
+            AND ($5::timestamp IS NULL OR sold_at < $5)
+        ORDER BY sold_at DESC
+        "#,
+        business_id,
+        sales_query.offtaker_id,
+        query.product_id,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "sales": sales
+        })),
+    ))
+}
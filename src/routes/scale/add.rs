@@ -0,0 +1,145 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business::Business, business_member::BusinessMember, scale::Scale, user::User,
+    },
+    routes::business::{business_scope, require_business_manager},
+    AppState,
+};
+
+use super::validate_connection;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddScalePayload {
+    pub business_id: Uuid,
+    pub name: String,
+    /// Tcp or Simulated.
+    pub connection_type: String,
+    pub host: Option<String>,
+    pub port: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/scale/add",
+    request_body = AddScalePayload,
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn scale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddScalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to add scales."
+            })),
+        ));
+    }
+
+    if let Some(business_id) = business_scope(&authenticated_user, &membership)? {
+        if business_id != payload.business_id {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized",
+                    "reason": "You may only add scales to your own business."
+                })),
+            ));
+        }
+    }
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        payload.business_id,
+        "Only owners and managers can add scales.",
+    )?;
+
+    validate_connection(&payload.connection_type, &payload.host, payload.port).await?;
+
+    let existing_business = sqlx::query_as!(
+        Business,
+        r#"
+        SELECT * FROM business_profile WHERE id = $1
+        "#,
+        payload.business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    if existing_business.is_none() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Business not found."
+            })),
+        ));
+    }
+
+    let scale = sqlx::query_as!(
+        Scale,
+        r#"
+        INSERT INTO scale (business_id, name, connection_type, host, port, active)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.name,
+        payload.connection_type,
+        payload.host,
+        payload.port,
+        payload.active.unwrap_or(true)
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state.scales.start(&scale).await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(json!({
+            "success": true,
+            "scale": scale
+        })),
+    ))
+}
@@ -0,0 +1,64 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_scale;
+
+#[utoipa::path(
+    delete,
+    path = "/scale/{scale_id}",
+    params(("scale_id" = String, Path, description = "The scales id.")),
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn scale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(scale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scale = find_scale(&app_state, &authenticated_user, &membership, scale_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        scale.business_id,
+        "Only owners and managers can remove scales.",
+    )?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM scale WHERE id = $1
+        "#,
+        scale.id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state.scales.stop(scale.id).await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "scale": scale
+        })),
+    ))
+}
@@ -0,0 +1,105 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, scale::Scale, user::User},
+    network::resolve_public,
+    routes::business::business_scope,
+    scales::{CONNECTION_TYPE_SIMULATED, CONNECTION_TYPE_TCP},
+    AppState,
+};
+
+pub mod add;
+pub mod delete;
+pub mod reading;
+pub mod update;
+pub mod view;
+
+/// Checks the connection settings of a scale. Tcp scales must be on a public
+/// address, the server connects to them.
+pub async fn validate_connection(
+    connection_type: &str,
+    host: &Option<String>,
+    port: Option<i32>,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let invalid_connection = |reason: &str| {
+        Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        ))
+    };
+
+    match connection_type {
+        CONNECTION_TYPE_SIMULATED => Ok(()),
+        CONNECTION_TYPE_TCP => match (host, port) {
+            (Some(host), Some(port)) if !host.is_empty() && (1..=65535).contains(&port) => {
+                match resolve_public(host, port as u16).await {
+                    Ok(_) => Ok(()),
+                    Err(reason) => invalid_connection(&reason),
+                }
+            }
+            _ => invalid_connection("A Tcp scale needs a host and a port."),
+        },
+        _ => invalid_connection("The connection type must be Tcp or Simulated."),
+    }
+}
+
+/// Finds a scale the user may use. Business users may only use the scales of
+/// their own business.
+pub async fn find_scale(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    scale_id: Uuid,
+) -> Result<Scale, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access scales."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(authenticated_user, membership)?;
+
+    let scale = sqlx::query_as!(
+        Scale,
+        r#"
+        SELECT * FROM scale WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        scale_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    scale.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Scale not found."
+        })),
+    ))
+}
@@ -0,0 +1,101 @@
+use std::{convert::Infallible, time::Duration};
+
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    Json,
+};
+use futures::{stream, Stream};
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    scales::ScaleReading,
+    AppState,
+};
+
+use super::find_scale;
+
+#[utoipa::path(
+    get,
+    path = "/scale/{scale_id}/reading",
+    params(("scale_id" = String, Path, description = "The scales id.")),
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn reading(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(scale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scale = find_scale(&app_state, &authenticated_user, &membership, scale_id).await?;
+
+    let reading = app_state.scales.reading(scale.id).await;
+    let stable_weight = app_state.scales.stable_weight(scale.id).await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "reading": reading,
+            "stable_weight": stable_weight.as_ref().ok(),
+            "reason": stable_weight.err()
+        })),
+    ))
+}
+
+/// Streams the readings of a scale as server-sent events, starting with the
+/// latest reading, so that the capture screen can show the live weight.
+#[utoipa::path(
+    get,
+    path = "/scale/{scale_id}/feed",
+    params(("scale_id" = String, Path, description = "The scales id.")),
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn feed(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(scale_id): extract::Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<Value>)> {
+    let scale = find_scale(&app_state, &authenticated_user, &membership, scale_id).await?;
+
+    let receiver = app_state.scales.subscribe();
+    let latest = app_state.scales.reading(scale.id).await;
+
+    let reading_event = |reading: &ScaleReading| {
+        Event::default()
+            .event("reading")
+            .json_data(reading)
+            .unwrap_or_else(|_| Event::default().comment("invalid reading"))
+    };
+
+    let readings = stream::unfold(
+        (receiver, latest),
+        move |(mut receiver, latest)| async move {
+            if let Some(latest) = latest {
+                return Some((Ok(reading_event(&latest)), (receiver, None)));
+            }
+
+            loop {
+                match receiver.recv().await {
+                    Ok(reading) if reading.scale_id == scale.id => {
+                        return Some((Ok(reading_event(&reading)), (receiver, None)))
+                    }
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    Ok(Sse::new(readings).keep_alive(KeepAlive::new().interval(Duration::from_secs(15))))
+}
@@ -0,0 +1,94 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, scale::Scale, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::{find_scale, validate_connection};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateScalePayload {
+    pub name: Option<String>,
+    pub connection_type: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/scale/{scale_id}",
+    params(("scale_id" = String, Path, description = "The scales id.")),
+    request_body = UpdateScalePayload,
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn scale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(scale_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateScalePayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scale = find_scale(&app_state, &authenticated_user, &membership, scale_id).await?;
+
+    require_business_manager(
+        &authenticated_user,
+        &membership,
+        scale.business_id,
+        "Only owners and managers can change scales.",
+    )?;
+
+    let name = payload.name.unwrap_or(scale.name);
+    let connection_type = payload.connection_type.unwrap_or(scale.connection_type);
+    let host = payload.host.or(scale.host);
+    let port = payload.port.or(scale.port);
+    let active = payload.active.unwrap_or(scale.active);
+
+    validate_connection(&connection_type, &host, port).await?;
+
+    let scale = sqlx::query_as!(
+        Scale,
+        r#"
+        UPDATE scale
+        SET name = $1, connection_type = $2, host = $3, port = $4, active = $5, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $6
+        RETURNING *
+        "#,
+        name,
+        connection_type,
+        host,
+        port,
+        active,
+        scale.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    app_state.scales.start(&scale).await;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "scale": scale
+        })),
+    ))
+}
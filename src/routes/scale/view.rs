@@ -0,0 +1,92 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, scale::Scale, user::User},
+    routes::business::business_scope,
+    AppState,
+};
+
+use super::find_scale;
+
+#[utoipa::path(
+    get,
+    path = "/scale",
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn scales(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access scales."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let scales = sqlx::query_as!(
+        Scale,
+        r#"
+        SELECT * FROM scale WHERE ($1::uuid IS NULL OR business_id = $1) ORDER BY name
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "scales": scales
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/scale/{scale_id}",
+    params(("scale_id" = String, Path, description = "The scales id.")),
+    tag = "Scale",
+    security(("bearer_auth" = [])),
+)]
+pub async fn scale(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(scale_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let scale = find_scale(&app_state, &authenticated_user, &membership, scale_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "scale": scale
+        })),
+    ))
+}
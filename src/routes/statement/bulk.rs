@@ -0,0 +1,142 @@
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Days;
+use serde_json::{json, Value};
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{collector::Collector, user::User},
+    pdf::PdfDocument,
+    AppState,
+};
+
+use super::{build_statement, file_response, render_csv, render_pdf, StatementQuery};
+
+/// Generates the statements of every collector with collections in the
+/// period. As a pdf every statement starts on a new page, as a csv the
+/// statements follow each other separated by blank lines.
+#[utoipa::path(
+    get,
+    path = "/statement/bulk",
+    params(
+        ("start_date" = Option<String>, Query, description = "The first day of the statements (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the statements (YYYY-MM-DD)."),
+        ("tax_year" = Option<i32>, Query, description = "The tax year, e.g. 2025 for March 2024 to February 2025."),
+        ("format" = Option<String>, Query, description = "One of json, csv or pdf."),
+    ),
+    tag = "Statement",
+    security(("bearer_auth" = [])),
+)]
+pub async fn bulk(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<StatementQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to generate statements."
+            })),
+        ));
+    }
+
+    let (period_start, period_end) = query.period()?;
+    let format = query.format()?;
+
+    let collectors = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile
+        WHERE id IN (
+            SELECT collector_id FROM collection
+            WHERE created_at >= $1 AND created_at < $2
+        )
+        ORDER BY last_name, first_name
+        "#,
+        period_start.and_hms_opt(0, 0, 0),
+        period_end
+            .checked_add_days(Days::new(1))
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let mut statements = Vec::new();
+
+    for collector in collectors {
+        let statement = build_statement(&app_state.pool, collector, period_start, period_end)
+            .await
+            .map_err(|error| {
+                tracing::error!("🔥 Failed to query database: {}", error);
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(json!({
+                        "error": "Internal Server Error",
+                        "reason": "Failed to query database."
+                    })),
+                )
+            })?;
+
+        statements.push(statement);
+    }
+
+    let file_name = format!("statements-{}-{}", period_start, period_end);
+
+    match format.as_str() {
+        "csv" => Ok((
+            StatusCode::OK,
+            file_response(
+                format!("{}.csv", file_name),
+                "text/csv",
+                statements
+                    .iter()
+                    .map(render_csv)
+                    .collect::<Vec<String>>()
+                    .join("\n\n\n")
+                    .into_bytes(),
+            ),
+        )),
+        "pdf" => {
+            let mut document = PdfDocument::new();
+
+            for statement in &statements {
+                render_pdf(&mut document, statement);
+            }
+
+            Ok((
+                StatusCode::OK,
+                file_response(
+                    format!("{}.pdf", file_name),
+                    "application/pdf",
+                    document.render(),
+                ),
+            ))
+        }
+        _ => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "success": true,
+                "statements": statements
+            }))
+            .into_response(),
+        )),
+    }
+}
@@ -0,0 +1,190 @@
+use axum::{
+    extract,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{collector::Collector, user::User},
+    pdf::PdfDocument,
+    AppState,
+};
+
+use super::{build_statement, file_response, render_csv, render_pdf, StatementQuery};
+
+#[utoipa::path(
+    get,
+    path = "/statement/collector/{collector_id}",
+    params(
+        ("collector_id" = String, Path, description = "The collectors id."),
+        ("start_date" = Option<String>, Query, description = "The first day of the statement (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the statement (YYYY-MM-DD)."),
+        ("tax_year" = Option<i32>, Query, description = "The tax year, e.g. 2025 for March 2024 to February 2025."),
+        ("format" = Option<String>, Query, description = "One of json, csv or pdf."),
+    ),
+    tag = "Statement",
+    security(("bearer_auth" = [])),
+)]
+pub async fn collector(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Path(collector_id): extract::Path<Uuid>,
+    extract::Query(query): extract::Query<StatementQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff && authenticated_user.role() != Role::SystemAdmin {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access statements."
+            })),
+        ));
+    }
+
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile WHERE id = $1
+        "#,
+        collector_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    match collector {
+        Some(collector) => statement(&app_state, collector, &query).await,
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Collector not found."
+            })),
+        )),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/statement/me",
+    params(
+        ("start_date" = Option<String>, Query, description = "The first day of the statement (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the statement (YYYY-MM-DD)."),
+        ("tax_year" = Option<i32>, Query, description = "The tax year, e.g. 2025 for March 2024 to February 2025."),
+        ("format" = Option<String>, Query, description = "One of json, csv or pdf."),
+    ),
+    tag = "Statement",
+    security(("bearer_auth" = [])),
+)]
+pub async fn me(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Query(query): extract::Query<StatementQuery>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let collector = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile WHERE user_id = $1
+        "#,
+        authenticated_user.id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    match collector {
+        Some(collector) => statement(&app_state, collector, &query).await,
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "You do not have a collector profile."
+            })),
+        )),
+    }
+}
+
+async fn statement(
+    app_state: &AppState,
+    collector: Collector,
+    query: &StatementQuery,
+) -> Result<(StatusCode, Response), (StatusCode, Json<Value>)> {
+    let (period_start, period_end) = query.period()?;
+    let format = query.format()?;
+
+    let statement = build_statement(&app_state.pool, collector, period_start, period_end)
+        .await
+        .map_err(|error| {
+            tracing::error!("🔥 Failed to query database: {}", error);
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "error": "Internal Server Error",
+                    "reason": "Failed to query database."
+                })),
+            )
+        })?;
+
+    let file_name = format!(
+        "statement-{}-{}-{}",
+        statement.collector.id_number, period_start, period_end
+    );
+
+    match format.as_str() {
+        "csv" => Ok((
+            StatusCode::OK,
+            file_response(
+                format!("{}.csv", file_name),
+                "text/csv",
+                render_csv(&statement).into_bytes(),
+            ),
+        )),
+        "pdf" => {
+            let mut document = PdfDocument::new();
+
+            render_pdf(&mut document, &statement);
+
+            Ok((
+                StatusCode::OK,
+                file_response(
+                    format!("{}.pdf", file_name),
+                    "application/pdf",
+                    document.render(),
+                ),
+            ))
+        }
+        _ => Ok((
+            StatusCode::OK,
+            Json(json!({
+                "success": true,
+                "statement": statement
+            }))
+            .into_response(),
+        )),
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::{data::entities::collector::Collector, pdf::PdfDocument};
+
+pub mod bulk;
+pub mod collector;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatementQuery {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// South African tax year, which runs from 1 March of the previous year
+    /// to the end of February of the given year.
+    pub tax_year: Option<i32>,
+    /// One of json, csv or pdf. Defaults to json.
+    pub format: Option<String>,
+}
+
+impl StatementQuery {
+    /// Resolves the inclusive first and last day of the statement. Without any
+    /// dates the current tax year is used.
+    pub fn period(&self) -> Result<(NaiveDate, NaiveDate), (StatusCode, Json<Value>)> {
+        let invalid_period = |reason: &str| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": reason
+                })),
+            )
+        };
+
+        let tax_year = match (self.tax_year, self.start_date, self.end_date) {
+            (Some(tax_year), None, None) => Some(tax_year),
+            (Some(_), _, _) => {
+                return Err(invalid_period(
+                    "Use either a tax year or a start and end date, not both.",
+                ))
+            }
+            (None, None, None) => {
+                let today = Utc::now().date_naive();
+
+                match today.month() >= 3 {
+                    true => Some(today.year() + 1),
+                    false => Some(today.year()),
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(tax_year) = tax_year {
+            let start = tax_year
+                .checked_sub(1)
+                .and_then(|year| NaiveDate::from_ymd_opt(year, 3, 1))
+                .ok_or_else(|| invalid_period("Invalid tax year."))?;
+            let end = NaiveDate::from_ymd_opt(tax_year, 3, 1)
+                .and_then(|date| date.pred_opt())
+                .ok_or_else(|| invalid_period("Invalid tax year."))?;
+
+            return Ok((start, end));
+        }
+
+        match (self.start_date, self.end_date) {
+            (Some(start), Some(end)) if start <= end => Ok((start, end)),
+            (Some(_), Some(_)) => Err(invalid_period(
+                "The start date must be on or before the end date.",
+            )),
+            _ => Err(invalid_period(
+                "Both a start date and an end date are required.",
+            )),
+        }
+    }
+
+    pub fn format(&self) -> Result<String, (StatusCode, Json<Value>)> {
+        let format = self
+            .format
+            .clone()
+            .unwrap_or("json".to_string())
+            .to_lowercase();
+
+        match format.as_str() {
+            "json" | "csv" | "pdf" => Ok(format),
+            _ => Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Bad Request",
+                    "reason": "Format must be one of json, csv or pdf."
+                })),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatementLine {
+    pub collection_id: Uuid,
+    pub created_at: Option<NaiveDateTime>,
+    pub business_name: Option<String>,
+    pub product_name: Option<String>,
+    pub weight: BigDecimal,
+    pub price: BigDecimal,
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StatementTotal {
+    pub label: String,
+    pub collections: i64,
+    pub weight: BigDecimal,
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Statement {
+    pub collector: Collector,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub lines: Vec<StatementLine>,
+    pub monthly_totals: Vec<StatementTotal>,
+    pub material_totals: Vec<StatementTotal>,
+    pub total: StatementTotal,
+}
+
+/// Builds the earnings statement of a collector for the inclusive period.
+///
+/// Amounts use the price each collection was bought at, so an issued statement
+/// does not change when prices do.
+pub async fn build_statement(
+    pool: &Pool<Postgres>,
+    collector: Collector,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+) -> Result<Statement, sqlx::Error> {
+    let start = period_start.and_hms_opt(0, 0, 0);
+    let end = period_end
+        .checked_add_days(Days::new(1))
+        .and_then(|date| date.and_hms_opt(0, 0, 0));
+
+    let lines = sqlx::query_as!(
+        StatementLine,
+        r#"
+            SELECT
+                collection.id AS collection_id,
+                collection.created_at AS created_at,
+                business.business_name AS "business_name?",
+                product.name AS "product_name?",
+                collection.weight AS weight,
+                collection.price AS price,
+                ROUND(collection.weight * collection.price, 2) AS "amount!"
+            FROM public.collection collection
+            LEFT JOIN public.business_profile business ON business.id = collection.business_id
+            LEFT JOIN public.product product ON product.id = collection.product_id
+            WHERE collection.collector_id = $1
+            AND collection.created_at >= $2
+            AND collection.created_at < $3
+            ORDER BY collection.created_at
+        "#,
+        collector.id,
+        start,
+        end
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut monthly_totals: BTreeMap<String, StatementTotal> = BTreeMap::new();
+    let mut material_totals: BTreeMap<String, StatementTotal> = BTreeMap::new();
+    let mut total = empty_total("Total");
+
+    for line in &lines {
+        let month = line
+            .created_at
+            .map(|created_at| created_at.format("%Y-%m").to_string())
+            .unwrap_or("-".to_string());
+        let material = line.product_name.clone().unwrap_or("-".to_string());
+
+        add_to_total(
+            monthly_totals
+                .entry(month.clone())
+                .or_insert_with(|| empty_total(&month)),
+            line,
+        );
+        add_to_total(
+            material_totals
+                .entry(material.clone())
+                .or_insert_with(|| empty_total(&material)),
+            line,
+        );
+        add_to_total(&mut total, line);
+    }
+
+    Ok(Statement {
+        collector,
+        period_start,
+        period_end,
+        lines,
+        monthly_totals: monthly_totals.into_values().collect(),
+        material_totals: material_totals.into_values().collect(),
+        total,
+    })
+}
+
+fn empty_total(label: &str) -> StatementTotal {
+    StatementTotal {
+        label: label.to_string(),
+        collections: 0,
+        weight: BigDecimal::from(0),
+        amount: BigDecimal::from(0),
+    }
+}
+
+fn add_to_total(total: &mut StatementTotal, line: &StatementLine) {
+    total.collections += 1;
+    total.weight += &line.weight;
+    total.amount += &line.amount;
+}
+
+/// Renders a statement as csv. The collection lines come first, followed by the
+/// monthly and per material totals.
+pub fn render_csv(statement: &Statement) -> String {
+    let collector = &statement.collector;
+
+    let mut csv_string = format!(
+        "\"Collector\",\"{} {}\"\n\"ID Number\",\"{}\"\n\"Period\",\"{} to {}\"\n\n\"Date\",\"Business\",\"Material\",\"Weight (kg)\",\"Price (R/kg)\",\"Amount (R)\"",
+        collector.first_name,
+        collector.last_name,
+        collector.id_number,
+        statement.period_start,
+        statement.period_end,
+    );
+
+    for line in &statement.lines {
+        csv_string += format!(
+            "\n\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+            line.created_at
+                .map(|created_at| created_at.format("%Y-%m-%d").to_string())
+                .unwrap_or("-".to_string()),
+            line.business_name.clone().unwrap_or("-".to_string()),
+            line.product_name.clone().unwrap_or("-".to_string()),
+            line.weight,
+            line.price,
+            line.amount,
+        )
+        .as_str();
+    }
+
+    for (title, totals) in [
+        ("Month", &statement.monthly_totals),
+        ("Material", &statement.material_totals),
+    ] {
+        csv_string += format!(
+            "\n\n\"{}\",\"Collections\",\"Weight (kg)\",\"Amount (R)\"",
+            title
+        )
+        .as_str();
+
+        for total in totals.iter().chain([&statement.total]) {
+            csv_string += format!(
+                "\n\"{}\",\"{}\",\"{}\",\"{}\"",
+                total.label, total.collections, total.weight, total.amount
+            )
+            .as_str();
+        }
+    }
+
+    csv_string
+}
+
+/// Adds a statement to a pdf document, starting on a new page.
+pub fn render_pdf(document: &mut PdfDocument, statement: &Statement) {
+    let collector = &statement.collector;
+
+    document.page_break();
+
+    document.line("3rEco - Collector Earnings Statement");
+    document.line("");
+    document.line(format!(
+        "Collector:    {} {}",
+        collector.first_name, collector.last_name
+    ));
+    document.line(format!("ID Number:    {}", collector.id_number));
+    document.line(format!("Phone Number: {}", collector.phone_number));
+    document.line(format!(
+        "Bank:         {} ({})",
+        collector.bank_name, collector.bank_account_holder
+    ));
+    document.line(format!(
+        "Period:       {} to {}",
+        statement.period_start, statement.period_end
+    ));
+    document.line(format!(
+        "Generated:    {}",
+        Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+    document.line("");
+    document.line(format!(
+        "{:<10}  {:<24}  {:<20}  {:>10}  {:>8}  {:>11}",
+        "Date", "Business", "Material", "Weight kg", "R/kg", "Amount R"
+    ));
+    document.line("-".repeat(94));
+
+    for line in &statement.lines {
+        document.line(format!(
+            "{:<10}  {:<24}  {:<20}  {:>10}  {:>8}  {:>11}",
+            line.created_at
+                .map(|created_at| created_at.format("%Y-%m-%d").to_string())
+                .unwrap_or("-".to_string()),
+            truncate(&line.business_name.clone().unwrap_or("-".to_string()), 24),
+            truncate(&line.product_name.clone().unwrap_or("-".to_string()), 20),
+            line.weight.to_string(),
+            line.price.to_string(),
+            line.amount.to_string(),
+        ));
+    }
+
+    for (title, totals) in [
+        ("Month", &statement.monthly_totals),
+        ("Material", &statement.material_totals),
+    ] {
+        document.line("");
+        document.line(format!(
+            "{:<30}  {:>11}  {:>12}  {:>14}",
+            title, "Collections", "Weight kg", "Amount R"
+        ));
+        document.line("-".repeat(73));
+
+        for total in totals.iter().chain([&statement.total]) {
+            document.line(format!(
+                "{:<30}  {:>11}  {:>12}  {:>14}",
+                truncate(&total.label, 30),
+                total.collections,
+                total.weight.to_string(),
+                total.amount.to_string(),
+            ));
+        }
+    }
+}
+
+fn truncate(text: &str, length: usize) -> String {
+    text.chars().take(length).collect()
+}
+
+/// Wraps a rendered file in a response that browsers will download.
+pub fn file_response(file_name: String, content_type: &str, body: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", file_name),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
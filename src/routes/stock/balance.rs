@@ -0,0 +1,134 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    AppState,
+};
+
+use super::{
+    stock_scope, StockQuery, MOVEMENT_BALING, MOVEMENT_COLLECTION, MOVEMENT_SALE,
+    MOVEMENT_TRANSFER, MOVEMENT_WRITE_OFF,
+};
+
+/// The flow of one product through stock over the period. Quantities out of
+/// stock are positive.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductBalance {
+    pub business_id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub opening: BigDecimal,
+    pub collected: BigDecimal,
+    pub transferred_in: BigDecimal,
+    pub baled_in: BigDecimal,
+    pub sold: BigDecimal,
+    pub transferred_out: BigDecimal,
+    pub baled_out: BigDecimal,
+    pub written_off: BigDecimal,
+    pub closing: BigDecimal,
+}
+
+/// Compares what came into the business over the period with what left it.
+/// Intake is collections and transfers in, outflow is sales and transfers
+/// out, and the losses are written off stock and weight lost in baling. The
+/// difference is the change in stock.
+#[utoipa::path(
+    get,
+    path = "/stock/balance",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Stock",
+    security(("bearer_auth" = [])),
+)]
+pub async fn balance(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = stock_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let products = sqlx::query_as!(
+        ProductBalance,
+        r#"
+        SELECT
+            product.business_id,
+            product.id AS product_id,
+            product.name AS product_name,
+            COALESCE(SUM(quantity) FILTER (WHERE occurred_at < $2), 0) AS "opening!",
+            COALESCE(SUM(quantity) FILTER (WHERE in_period AND movement_type = $4), 0) AS "collected!",
+            COALESCE(SUM(quantity) FILTER (WHERE in_period AND movement_type = $6 AND quantity > 0), 0) AS "transferred_in!",
+            COALESCE(SUM(quantity) FILTER (WHERE in_period AND movement_type = $7 AND quantity > 0), 0) AS "baled_in!",
+            COALESCE(-SUM(quantity) FILTER (WHERE in_period AND movement_type = $5), 0) AS "sold!",
+            COALESCE(-SUM(quantity) FILTER (WHERE in_period AND movement_type = $6 AND quantity < 0), 0) AS "transferred_out!",
+            COALESCE(-SUM(quantity) FILTER (WHERE in_period AND movement_type = $7 AND quantity < 0), 0) AS "baled_out!",
+            COALESCE(-SUM(quantity) FILTER (WHERE in_period AND movement_type = $8), 0) AS "written_off!",
+            COALESCE(SUM(quantity) FILTER (WHERE $3::timestamp IS NULL OR occurred_at < $3), 0) AS "closing!"
+        FROM product
+        LEFT JOIN LATERAL (
+            SELECT
+                stock_movement.*,
+                ($2::timestamp IS NULL OR occurred_at >= $2) AND ($3::timestamp IS NULL OR occurred_at < $3) AS in_period
+            FROM stock_movement
+            WHERE stock_movement.product_id = product.id
+        ) AS movement ON TRUE
+        WHERE ($1::uuid IS NULL OR product.business_id = $1)
+        GROUP BY product.business_id, product.id, product.name
+        ORDER BY product.name
+        "#,
+        business_id,
+        query.start(),
+        query.end(),
+        MOVEMENT_COLLECTION,
+        MOVEMENT_SALE,
+        MOVEMENT_TRANSFER,
+        MOVEMENT_BALING,
+        MOVEMENT_WRITE_OFF
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let sum = |field: fn(&ProductBalance) -> &BigDecimal| {
+        products
+            .iter()
+            .map(field)
+            .fold(BigDecimal::zero(), |total, quantity| total + quantity)
+    };
+
+    let intake = sum(|product| &product.collected) + sum(|product| &product.transferred_in);
+    let outflow = sum(|product| &product.sold) + sum(|product| &product.transferred_out);
+    let baling_loss = sum(|product| &product.baled_out) - sum(|product| &product.baled_in);
+    let written_off = sum(|product| &product.written_off);
+    let stock_change = sum(|product| &product.closing) - sum(|product| &product.opening);
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "intake": intake,
+            "outflow": outflow,
+            "baling_loss": baling_loss,
+            "written_off": written_off,
+            "stock_change": stock_change,
+            "products": products
+        })),
+    ))
+}
@@ -0,0 +1,98 @@
+use axum::{http::StatusCode, Json};
+use bigdecimal::BigDecimal;
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::business_scope,
+};
+
+pub mod balance;
+pub mod movement;
+pub mod view;
+
+pub const MOVEMENT_COLLECTION: &str = "Collection";
+pub const MOVEMENT_SALE: &str = "Sale";
+pub const MOVEMENT_BALING: &str = "Baling";
+pub const MOVEMENT_TRANSFER: &str = "Transfer";
+pub const MOVEMENT_WRITE_OFF: &str = "Write Off";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StockQuery {
+    /// Ignored for business users, who only see their own stock.
+    pub business_id: Option<Uuid>,
+    pub product_id: Option<Uuid>,
+    pub movement_type: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+impl StockQuery {
+    /// The inclusive start of the requested period.
+    pub fn start(&self) -> Option<NaiveDateTime> {
+        self.start_date
+            .and_then(|start_date| start_date.and_hms_opt(0, 0, 0))
+    }
+
+    /// The exclusive end of the requested period, which is the start of the day
+    /// after `end_date`.
+    pub fn end(&self) -> Option<NaiveDateTime> {
+        self.end_date
+            .and_then(|end_date| end_date.checked_add_days(Days::new(1)))
+            .and_then(|end_date| end_date.and_hms_opt(0, 0, 0))
+    }
+}
+
+/// The business whose stock the user asked for. Business users always get
+/// their own business, staff any business or all of them.
+pub fn stock_scope(
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    business_id: Option<Uuid>,
+) -> Result<Option<Uuid>, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to access stock."
+            })),
+        ));
+    }
+
+    Ok(business_scope(authenticated_user, membership)?.or(business_id))
+}
+
+/// Locks the stock of a product until the transaction ends and returns the
+/// quantity on hand, so that stock taken out can be checked against it.
+pub async fn lock_stock(
+    transaction: &mut Transaction<'_, Postgres>,
+    product_id: Uuid,
+) -> Result<BigDecimal, sqlx::Error> {
+    sqlx::query!(
+        r#"
+        SELECT id FROM product WHERE id = $1 FOR UPDATE
+        "#,
+        product_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?;
+
+    sqlx::query_scalar!(
+        r#"
+        SELECT COALESCE(SUM(quantity), 0) AS "quantity!" FROM stock_movement WHERE product_id = $1
+        "#,
+        product_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+}
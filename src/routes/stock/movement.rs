@@ -0,0 +1,275 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, stock_movement::StockMovement, user::User},
+    AppState,
+};
+
+use super::{
+    lock_stock, stock_scope, MOVEMENT_BALING, MOVEMENT_SALE, MOVEMENT_TRANSFER, MOVEMENT_WRITE_OFF,
+};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct RecordMovementPayload {
+    pub business_id: Uuid,
+    /// The product taken out of stock.
+    pub product_id: Uuid,
+    /// Sale, Baling, Transfer or Write Off. Collections are recorded by
+    /// themselves.
+    pub movement_type: String,
+    /// The quantity taken out of stock.
+    pub quantity: BigDecimal,
+    /// The product going into stock. For baling a product of the same
+    /// business, like baled PET from loose PET. For a transfer the same
+    /// material at the receiving business.
+    pub to_product_id: Option<Uuid>,
+    /// The baled quantity, when baling loses weight. Defaults to `quantity`.
+    pub output_quantity: Option<BigDecimal>,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    /// Defaults to now.
+    pub occurred_at: Option<NaiveDateTime>,
+}
+
+/// Takes stock out by a sale or write-off, converts it by baling or moves it
+/// to another business by a transfer. Stock can not go below zero.
+#[utoipa::path(
+    post,
+    path = "/stock/movement",
+    request_body = RecordMovementPayload,
+    tag = "Stock",
+    security(("bearer_auth" = [])),
+)]
+pub async fn record(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<RecordMovementPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = stock_scope(&authenticated_user, &membership, Some(payload.business_id))?;
+
+    if business_id != Some(payload.business_id) {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You may only move the stock of your own business."
+            })),
+        ));
+    }
+
+    let bad_request = |reason: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        )
+    };
+
+    let movement_type = payload.movement_type.as_str();
+
+    if ![
+        MOVEMENT_SALE,
+        MOVEMENT_BALING,
+        MOVEMENT_TRANSFER,
+        MOVEMENT_WRITE_OFF,
+    ]
+    .contains(&movement_type)
+    {
+        return Err(bad_request(
+            "The movement type must be Sale, Baling, Transfer or Write Off.".to_string(),
+        ));
+    }
+
+    if payload.quantity <= BigDecimal::zero() {
+        return Err(bad_request("The quantity must be more than 0.".to_string()));
+    }
+
+    let links_product = movement_type == MOVEMENT_BALING || movement_type == MOVEMENT_TRANSFER;
+
+    if links_product != payload.to_product_id.is_some() {
+        return Err(bad_request(match links_product {
+            true => format!("A {} needs a to_product_id.", movement_type.to_lowercase()),
+            false => format!("A {} has no to_product_id.", movement_type.to_lowercase()),
+        }));
+    }
+
+    let output_quantity = match movement_type {
+        MOVEMENT_BALING => payload
+            .output_quantity
+            .clone()
+            .unwrap_or(payload.quantity.clone()),
+        _ => payload.quantity.clone(),
+    };
+
+    if output_quantity <= BigDecimal::zero() || output_quantity > payload.quantity {
+        return Err(bad_request(
+            "The output quantity must be more than 0 and at most the quantity.".to_string(),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let product_business_id = |product_id: Uuid| {
+        sqlx::query_scalar!(
+            r#"
+            SELECT business_id FROM product WHERE id = $1
+            "#,
+            product_id
+        )
+        .fetch_optional(&app_state.pool)
+    };
+
+    if product_business_id(payload.product_id)
+        .await
+        .map_err(database_error)?
+        != Some(payload.business_id)
+    {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": "Not Found",
+                "reason": "Product not found."
+            })),
+        ));
+    }
+
+    let to_business_id = match payload.to_product_id {
+        Some(to_product_id) => {
+            let to_business_id = product_business_id(to_product_id)
+                .await
+                .map_err(database_error)?
+                .ok_or((
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "error": "Not Found",
+                        "reason": "The to product was not found."
+                    })),
+                ))?;
+
+            if movement_type == MOVEMENT_BALING
+                && (to_business_id != payload.business_id || to_product_id == payload.product_id)
+            {
+                return Err(bad_request(
+                    "Baling must go to another product of the same business.".to_string(),
+                ));
+            }
+
+            if movement_type == MOVEMENT_TRANSFER && to_business_id == payload.business_id {
+                return Err(bad_request(
+                    "A transfer must go to a product of another business.".to_string(),
+                ));
+            }
+
+            Some((to_product_id, to_business_id))
+        }
+        None => None,
+    };
+
+    let reference = payload.reference.unwrap_or_default();
+    let notes = payload.notes.unwrap_or_default();
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let on_hand = lock_stock(&mut transaction, payload.product_id)
+        .await
+        .map_err(database_error)?;
+
+    if on_hand < payload.quantity {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(json!({
+                "error": "Conflict",
+                "reason": format!("Only {} is in stock.", on_hand),
+                "on_hand": on_hand
+            })),
+        ));
+    }
+
+    let outbound = sqlx::query_as!(
+        StockMovement,
+        r#"
+        INSERT INTO stock_movement (business_id, product_id, movement_type, quantity, reference, notes, created_by, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, COALESCE($8, CURRENT_TIMESTAMP::TIMESTAMP))
+        RETURNING *
+        "#,
+        payload.business_id,
+        payload.product_id,
+        movement_type,
+        -payload.quantity,
+        reference,
+        notes,
+        authenticated_user.id,
+        payload.occurred_at
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let mut movements = vec![outbound];
+
+    if let Some((to_product_id, to_business_id)) = to_business_id {
+        let inbound = sqlx::query_as!(
+            StockMovement,
+            r#"
+            INSERT INTO stock_movement (business_id, product_id, movement_type, quantity, linked_movement_id, reference, notes, created_by, occurred_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+            to_business_id,
+            to_product_id,
+            movement_type,
+            output_quantity,
+            movements[0].id,
+            reference,
+            notes,
+            authenticated_user.id,
+            movements[0].occurred_at
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        movements[0] = sqlx::query_as!(
+            StockMovement,
+            r#"
+            UPDATE stock_movement SET linked_movement_id = $2 WHERE id = $1 RETURNING *
+            "#,
+            movements[0].id,
+            inbound.id
+        )
+        .fetch_one(&mut *transaction)
+        .await
+        .map_err(database_error)?;
+
+        movements.push(inbound);
+    }
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "movements": movements
+        })),
+    ))
+}
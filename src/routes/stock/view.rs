@@ -0,0 +1,140 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, stock_movement::StockMovement, user::User},
+    AppState,
+};
+
+use super::{stock_scope, StockQuery};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductStock {
+    pub business_id: Uuid,
+    pub product_id: Uuid,
+    pub product_name: String,
+    pub quantity: BigDecimal,
+    pub last_movement_at: Option<NaiveDateTime>,
+}
+
+/// The quantity of every product currently in stock.
+#[utoipa::path(
+    get,
+    path = "/stock",
+    params(("business_id" = Option<String>, Query, description = "The businesses id, for staff.")),
+    tag = "Stock",
+    security(("bearer_auth" = [])),
+)]
+pub async fn stock(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = stock_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let stock = sqlx::query_as!(
+        ProductStock,
+        r#"
+        SELECT
+            product.business_id,
+            product.id AS product_id,
+            product.name AS product_name,
+            COALESCE(SUM(stock_movement.quantity), 0) AS "quantity!",
+            MAX(stock_movement.occurred_at) AS last_movement_at
+        FROM product
+        LEFT JOIN stock_movement ON stock_movement.product_id = product.id
+        WHERE ($1::uuid IS NULL OR product.business_id = $1)
+        GROUP BY product.business_id, product.id, product.name
+        ORDER BY product.name
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "stock": stock
+        })),
+    ))
+}
+
+/// The stock ledger, newest first.
+#[utoipa::path(
+    get,
+    path = "/stock/movement",
+    params(
+        ("business_id" = Option<String>, Query, description = "The businesses id, for staff."),
+        ("product_id" = Option<String>, Query, description = "Only movements of this product."),
+        ("movement_type" = Option<String>, Query, description = "One of Collection, Sale, Baling, Transfer or Write Off."),
+        ("start_date" = Option<String>, Query, description = "The first day of the period (YYYY-MM-DD)."),
+        ("end_date" = Option<String>, Query, description = "The last day of the period (YYYY-MM-DD)."),
+    ),
+    tag = "Stock",
+    security(("bearer_auth" = [])),
+)]
+pub async fn movements(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Query(query): extract::Query<StockQuery>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let business_id = stock_scope(&authenticated_user, &membership, query.business_id)?;
+
+    let movements = sqlx::query_as!(
+        StockMovement,
+        r#"
+        SELECT * FROM stock_movement
+        WHERE ($1::uuid IS NULL OR business_id = $1)
+            AND ($2::uuid IS NULL OR product_id = $2)
+            AND ($3::text IS NULL OR movement_type = $3)
+            AND ($4::timestamp IS NULL OR occurred_at >= $4)
+            AND ($5::timestamp IS NULL OR occurred_at < $5)
+        ORDER BY occurred_at DESC, created_at DESC
+        "#,
+        business_id,
+        query.product_id,
+        query.movement_type,
+        query.start(),
+        query.end()
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "movements": movements
+        })),
+    ))
+}
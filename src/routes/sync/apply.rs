@@ -0,0 +1,494 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        branch_price::BranchPrice, business_member::BusinessMember, collection::Collection,
+        collector::Collector, product::Product, sync_tombstone::SyncTombstone, user::User,
+    },
+    geo::validate_coordinates,
+    routes::{
+        branch::require_active_branch, collector::STATUS_APPROVED as COLLECTOR_APPROVED,
+        import::collection::import_business,
+    },
+    webhooks::{self, COLLECTION_CREATED},
+    AppState,
+};
+
+/// The most collections accepted in one sync, larger offline backlogs are
+/// uploaded over several syncs.
+const MAX_BATCH_SIZE: usize = 500;
+
+/// How far before the sync token changes are read again. A change is stamped
+/// when it is made but only seen once its transaction commits, so a change
+/// committed after the previous sync can carry an earlier time.
+const SYNC_OVERLAP_MINUTES: i64 = 5;
+
+pub const STATUS_APPLIED: &str = "Applied";
+pub const STATUS_DUPLICATE: &str = "Duplicate";
+pub const STATUS_CONFLICT: &str = "Conflict";
+pub const STATUS_REJECTED: &str = "Rejected";
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct SyncPayload {
+    /// The business being synced. Required for staff.
+    pub business_id: Option<Uuid>,
+    /// The branch the device captures at. Defaults to the branch of the user,
+    /// or the default branch of the business.
+    pub branch_id: Option<Uuid>,
+    /// The token returned by the previous sync. Leave empty to receive every
+    /// collector and product. Changes just before the token are sent again,
+    /// apply them by id.
+    pub sync_token: Option<String>,
+    /// Collections captured offline since the previous sync.
+    #[serde(default)]
+    pub collections: Vec<SyncCollectionPayload>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct SyncCollectionPayload {
+    /// Generated by the client, so that uploading it again is harmless.
+    pub id: Uuid,
+    pub collector_id: Uuid,
+    pub product_id: Uuid,
+    pub weight: BigDecimal,
+    /// When the collection was captured on the device.
+    pub created_at: NaiveDateTime,
+    /// Where the collection was captured, set together with the longitude.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncResult {
+    pub id: Uuid,
+    pub status: String,
+    pub reason: Option<String>,
+    /// The collection as stored on the server, also for duplicates and
+    /// conflicts.
+    pub collection: Option<Collection>,
+}
+
+impl SyncResult {
+    fn new(id: Uuid, status: &str, reason: Option<&str>, collection: Option<Collection>) -> Self {
+        Self {
+            id,
+            status: status.to_string(),
+            reason: reason.map(str::to_string),
+            collection,
+        }
+    }
+}
+
+/// Applies collections captured offline and returns the collectors, products
+/// and branch prices that changed since the previous sync.
+///
+/// Each collection is applied on its own, a rejected collection does not hold
+/// up the rest of the batch. A collection whose id already exists is reported
+/// as a duplicate when it matches the upload, and as a conflict otherwise.
+#[utoipa::path(
+    post,
+    path = "/sync",
+    request_body = SyncPayload,
+    tag = "Sync",
+    security(("bearer_auth" = [])),
+)]
+pub async fn sync(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<SyncPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to sync collections."
+            })),
+        ));
+    }
+
+    if payload.collections.len() > MAX_BATCH_SIZE {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(json!({
+                "error": "Payload Too Large",
+                "reason": format!("At most {} collections can be synced at once.", MAX_BATCH_SIZE)
+            })),
+        ));
+    }
+
+    let since = match &payload.sync_token {
+        Some(sync_token) => Some(
+            NaiveDateTime::parse_from_str(sync_token, "%Y-%m-%dT%H:%M:%S%.f").map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "error": "Bad Request",
+                        "reason": "Invalid sync token."
+                    })),
+                )
+            })? - Duration::minutes(SYNC_OVERLAP_MINUTES),
+        ),
+        None => None,
+    };
+
+    let business_id = import_business(&authenticated_user, &membership, payload.business_id)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let branch_id = payload.branch_id.or(membership
+        .as_ref()
+        .filter(|membership| membership.business_id == business_id)
+        .and_then(|membership| membership.branch_id));
+
+    if let Some(branch_id) = branch_id {
+        require_active_branch(&app_state, branch_id, business_id).await?;
+    }
+
+    let collector_ids: Vec<Uuid> = payload
+        .collections
+        .iter()
+        .map(|collection| collection.collector_id)
+        .collect();
+    let product_ids: Vec<Uuid> = payload
+        .collections
+        .iter()
+        .map(|collection| collection.product_id)
+        .collect();
+
+    let known_collectors: HashMap<Uuid, String> = sqlx::query!(
+        r#"
+        SELECT id, status FROM collector_profile WHERE id = ANY($1)
+        "#,
+        &collector_ids
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .into_iter()
+    .map(|collector| (collector.id, collector.status))
+    .collect();
+
+    let known_products: HashSet<Uuid> = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM product WHERE business_id = $1 AND id = ANY($2)
+        "#,
+        business_id,
+        &product_ids
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?
+    .into_iter()
+    .collect();
+
+    let mut results = Vec::with_capacity(payload.collections.len());
+
+    for upload in &payload.collections {
+        match known_collectors
+            .get(&upload.collector_id)
+            .map(String::as_str)
+        {
+            Some(COLLECTOR_APPROVED) => {}
+            Some(_) => {
+                results.push(SyncResult::new(
+                    upload.id,
+                    STATUS_REJECTED,
+                    Some("The collector is not approved."),
+                    None,
+                ));
+                continue;
+            }
+            None => {
+                results.push(SyncResult::new(
+                    upload.id,
+                    STATUS_REJECTED,
+                    Some("Collector not found."),
+                    None,
+                ));
+                continue;
+            }
+        }
+
+        if !known_products.contains(&upload.product_id) {
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_REJECTED,
+                Some("Product not found."),
+                None,
+            ));
+            continue;
+        }
+
+        if validate_coordinates(upload.latitude, upload.longitude).is_err() {
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_REJECTED,
+                Some("The coordinates are invalid."),
+                None,
+            ));
+            continue;
+        }
+
+        if upload.weight <= BigDecimal::from(0) {
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_REJECTED,
+                Some("The weight must be greater than zero."),
+                None,
+            ));
+            continue;
+        }
+
+        let collection = sqlx::query_as!(
+            Collection,
+            r#"
+            INSERT INTO collection (id, business_id, collector_id, product_id, weight, created_at, latitude, longitude, branch_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (id) DO NOTHING
+            RETURNING *
+            "#,
+            upload.id,
+            business_id,
+            upload.collector_id,
+            upload.product_id,
+            upload.weight,
+            upload.created_at,
+            upload.latitude,
+            upload.longitude,
+            branch_id
+        )
+        .fetch_optional(&app_state.pool)
+        .await
+        .map_err(database_error)?;
+
+        if let Some(collection) = collection {
+            webhooks::emit(
+                &app_state.pool,
+                COLLECTION_CREATED,
+                Some(collection.business_id),
+                json!(collection),
+            )
+            .await;
+
+            app_state
+                .collection_feed
+                .publish(&app_state.pool, COLLECTION_CREATED, &collection)
+                .await;
+
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_APPLIED,
+                None,
+                Some(collection),
+            ));
+            continue;
+        }
+
+        let existing = sqlx::query_as!(
+            Collection,
+            r#"
+            SELECT * FROM collection WHERE id = $1
+            "#,
+            upload.id
+        )
+        .fetch_one(&app_state.pool)
+        .await
+        .map_err(database_error)?;
+
+        if existing.business_id != business_id {
+            // Do not reveal collections of other businesses.
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_CONFLICT,
+                Some("The id is already in use."),
+                None,
+            ));
+        } else if existing.collector_id == upload.collector_id
+            && existing.product_id == upload.product_id
+            && existing.weight == upload.weight
+            && existing.created_at == Some(upload.created_at)
+        {
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_DUPLICATE,
+                None,
+                Some(existing),
+            ));
+        } else {
+            results.push(SyncResult::new(
+                upload.id,
+                STATUS_CONFLICT,
+                Some("The collection was changed on the server."),
+                Some(existing),
+            ));
+        }
+    }
+
+    // Taken before reading the changes. Changes in transactions that were
+    // still open are caught by the overlap on the next sync, as long as those
+    // transactions took less than SYNC_OVERLAP_MINUTES.
+    let sync_token = sqlx::query_scalar!(
+        r#"
+        SELECT clock_timestamp()::TIMESTAMP AS "now!"
+        "#
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    // Only collectors that registered at or sold to the business, new
+    // collectors are captured online first. A collector is sent again when
+    // another device links them to the business.
+    let collectors = sqlx::query_as!(
+        Collector,
+        r#"
+        SELECT * FROM collector_profile
+        WHERE (
+                review_business_id = $1
+                OR EXISTS (
+                    SELECT 1 FROM collection
+                    WHERE collection.collector_id = collector_profile.id AND collection.business_id = $1
+                )
+            )
+            AND (
+                $2::TIMESTAMP IS NULL
+                OR COALESCE(updated_at, created_at) > $2
+                OR EXISTS (
+                    SELECT 1 FROM collection
+                    WHERE collection.collector_id = collector_profile.id
+                        AND collection.business_id = $1
+                        AND collection.updated_at > $2
+                )
+            )
+        ORDER BY updated_at
+        "#,
+        business_id,
+        since
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let products = sqlx::query_as!(
+        Product,
+        r#"
+        SELECT * FROM product
+        WHERE business_id = $1
+            AND ($2::TIMESTAMP IS NULL OR COALESCE(updated_at, created_at) > $2)
+        ORDER BY updated_at
+        "#,
+        business_id,
+        since
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    let branch_prices = sqlx::query_as!(
+        BranchPrice,
+        r#"
+        SELECT branch_price.* FROM branch_price
+        INNER JOIN branch ON branch.id = branch_price.branch_id
+        WHERE branch.business_id = $1
+            AND ($2::TIMESTAMP IS NULL OR branch_price.updated_at > $2)
+        ORDER BY branch_price.updated_at
+        "#,
+        business_id,
+        since
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    // A first sync starts from a full copy, so there is nothing to delete.
+    let deleted = match since {
+        Some(since) => sqlx::query_as!(
+            SyncTombstone,
+            r#"
+            SELECT * FROM sync_tombstone
+            WHERE deleted_at > $1 AND (business_id IS NULL OR business_id = $2)
+            ORDER BY id
+            "#,
+            since,
+            business_id
+        )
+        .fetch_all(&app_state.pool)
+        .await
+        .map_err(database_error)?,
+        None => Vec::new(),
+    };
+
+    let deleted_ids = |entity: &str| -> Vec<Uuid> {
+        deleted
+            .iter()
+            .filter(|tombstone| tombstone.entity == entity)
+            .map(|tombstone| tombstone.record_id)
+            .collect()
+    };
+
+    let deleted_branch_prices: Vec<Value> = deleted
+        .iter()
+        .filter(|tombstone| tombstone.entity == "branch_price")
+        .map(|tombstone| {
+            json!({
+                "branch_id": tombstone.branch_id,
+                "product_id": tombstone.record_id
+            })
+        })
+        .collect();
+
+    let count = |status: &str| {
+        results
+            .iter()
+            .filter(|result| result.status == status)
+            .count()
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "sync_token": sync_token.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+            "summary": {
+                "applied": count(STATUS_APPLIED),
+                "duplicates": count(STATUS_DUPLICATE),
+                "conflicts": count(STATUS_CONFLICT),
+                "rejected": count(STATUS_REJECTED)
+            },
+            "results": results,
+            "changes": {
+                "collectors": collectors,
+                "products": products,
+                "branch_prices": branch_prices,
+                "deleted_collectors": deleted_ids("collector"),
+                "deleted_products": deleted_ids("product"),
+                "deleted_branch_prices": deleted_branch_prices
+            }
+        })),
+    ))
+}
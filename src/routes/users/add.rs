@@ -6,6 +6,8 @@ use utoipa::ToSchema;
 
 use crate::{authentication::roles::Role, data::entities::user::User, AppState};
 
+use super::require_can_add_user;
+
 #[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct AddUserPayload {
     pub email: String,
@@ -25,40 +27,7 @@ pub async fn user(
     extract::Extension(authenticated_user): extract::Extension<User>,
     extract::Json(payload): extract::Json<AddUserPayload>,
 ) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
-    let requirement_a = authenticated_user.role() != Role::Staff
-        && authenticated_user.role() != Role::SystemAdmin
-        && authenticated_user.role() != Role::Business;
-    let requirement_b =
-        payload.role == Role::SystemAdmin && authenticated_user.role() != Role::SystemAdmin;
-    let requirement_c =
-        payload.role != Role::Collector && authenticated_user.role() == Role::Business;
-
-    if requirement_a {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(
-                json!({ "error": "Unauthorized", "reason": "You do not have permission to add users." }),
-            ),
-        ));
-    }
-
-    if requirement_b {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(
-                json!({ "error": "Unauthorized", "reason": "You do not have permission to add users with role \"System Admin\"." }),
-            ),
-        ));
-    }
-
-    if requirement_c {
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            Json(
-                json!({ "error": "Unauthorized", "reason": "You only have permission to add users with role \"Collector\"." }),
-            ),
-        ));
-    }
+    require_can_add_user(&authenticated_user, &payload.role)?;
 
     // Find an existing user.
     let existing_user = sqlx::query_as!(
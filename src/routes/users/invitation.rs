@@ -0,0 +1,280 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use bcrypt::hash;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::{roles::Role, token::generate_token},
+    data::entities::user::User,
+    AppState,
+};
+
+use super::{require_can_add_user, send_activation};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct InviteUserPayload {
+    pub email: String,
+    pub role: Role,
+}
+
+/// A user that has not activated their account yet, with their latest
+/// activation link.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingUser {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Option<Uuid>,
+    pub invited_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub expired: bool,
+}
+
+/// Adds an inactive user and emails them a link to choose a password, which
+/// is redeemed at `/authentication/activate`.
+#[utoipa::path(
+    post,
+    path = "/users/invite",
+    request_body = InviteUserPayload,
+    tag = "Users",
+    security(("bearer_auth" = [])),
+)]
+pub async fn invite(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Json(payload): extract::Json<InviteUserPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_can_add_user(&authenticated_user, &payload.role)?;
+
+    let email = payload.email.trim().to_lowercase();
+
+    if !email.contains('@') {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": "A valid email is required."
+            })),
+        ));
+    }
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let existing_user = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM users WHERE TRIM(LOWER(email)) = $1) AS "exists!"
+        "#,
+        email
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(database_error)?;
+
+    if existing_user {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(
+                json!({ "error":"Conflict", "reason": "A user with that email already exists. Please try again with a different email." }),
+            ),
+        ));
+    }
+
+    // The account can not be logged into until the invitee chooses a password.
+    let hashed_password = hash(generate_token(), 4).map_err(|error| {
+        tracing::error!("🔥 Failed to hash new user password: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Unknown error occured. Please contact the api developer."
+            })),
+        )
+    })?;
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        INSERT INTO users (email, password, role, active) VALUES ($1, $2, $3, FALSE)
+        RETURNING *
+        "#,
+        email,
+        hashed_password,
+        payload.role.to_string()
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .map_err(database_error)?;
+
+    let activation =
+        send_activation(&app_state, &mut transaction, &user, &authenticated_user).await?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "user": user,
+            "activation": activation
+        })),
+    ))
+}
+
+/// Lists invited users that have not activated their account. Businesses only
+/// see the users they invited.
+#[utoipa::path(
+    get,
+    path = "/users/invitation",
+    tag = "Users",
+    security(("bearer_auth" = [])),
+)]
+pub async fn invitations(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_can_add_user(&authenticated_user, &Role::Collector)?;
+
+    let invited_by = (authenticated_user.role() == Role::Business).then_some(authenticated_user.id);
+
+    let users = sqlx::query_as!(
+        PendingUser,
+        r#"
+        SELECT * FROM (
+            SELECT DISTINCT ON (users.id)
+                users.id,
+                users.email,
+                users.role,
+                user_activation.invited_by,
+                user_activation.created_at AS invited_at,
+                user_activation.expires_at,
+                user_activation.expires_at <= CURRENT_TIMESTAMP AS "expired!"
+            FROM users
+            INNER JOIN user_activation ON user_activation.user_id = users.id
+            WHERE NOT users.active
+                AND NOT EXISTS (
+                    SELECT 1 FROM user_activation AS used
+                    WHERE used.user_id = users.id AND used.used_at IS NOT NULL
+                )
+            ORDER BY users.id, user_activation.created_at DESC
+        ) AS pending
+        WHERE $1::uuid IS NULL OR invited_by = $1
+        ORDER BY invited_at DESC
+        "#,
+        invited_by
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "users": users
+        })),
+    ))
+}
+
+/// Emails a new activation link to an invited user, for example when the
+/// first one expired. Earlier links stop working.
+#[utoipa::path(
+    post,
+    path = "/users/{user_id}/invite/resend",
+    params(("user_id" = String, Path, description = "The users id.")),
+    tag = "Users",
+    security(("bearer_auth" = [])),
+)]
+pub async fn resend(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Path(user_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    require_can_add_user(&authenticated_user, &Role::Collector)?;
+
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    let invited_by = (authenticated_user.role() == Role::Business).then_some(authenticated_user.id);
+
+    let mut transaction = app_state.pool.begin().await.map_err(database_error)?;
+
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        SELECT * FROM users
+        WHERE id = $1
+            AND NOT active
+            AND EXISTS (
+                SELECT 1 FROM user_activation
+                WHERE user_id = users.id AND ($2::uuid IS NULL OR invited_by = $2)
+            )
+            AND NOT EXISTS (
+                SELECT 1 FROM user_activation WHERE user_id = users.id AND used_at IS NOT NULL
+            )
+        FOR UPDATE
+        "#,
+        user_id,
+        invited_by
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .map_err(database_error)?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "No pending invitation found for this user."
+        })),
+    ))?;
+
+    require_can_add_user(&authenticated_user, &user.role())?;
+
+    let activation =
+        send_activation(&app_state, &mut transaction, &user, &authenticated_user).await?;
+
+    transaction.commit().await.map_err(database_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "user": user,
+            "activation": activation
+        })),
+    ))
+}
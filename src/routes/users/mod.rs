@@ -1,4 +1,144 @@
+use axum::{http::StatusCode, Json};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    authentication::{
+        roles::Role,
+        token::{generate_token, hash_token},
+    },
+    data::entities::{user::User, user_activation::UserActivation},
+    mailer::Email,
+    AppState,
+};
+
 pub mod add;
 pub mod delete;
+pub mod invitation;
 pub mod update;
 pub mod view;
+
+/// How long an activation link can be used for.
+pub const ACTIVATION_TTL_DAYS: i64 = 3;
+
+/// Staff and admins can add anyone but system admins, which only system admins
+/// can add. Businesses can only add collectors.
+pub fn require_can_add_user(
+    authenticated_user: &User,
+    role: &Role,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+    let requirement_b =
+        *role == Role::SystemAdmin && authenticated_user.role() != Role::SystemAdmin;
+    let requirement_c = *role != Role::Collector && authenticated_user.role() == Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to add users." }),
+            ),
+        ));
+    }
+
+    if requirement_b {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You do not have permission to add users with role \"System Admin\"." }),
+            ),
+        ));
+    }
+
+    if requirement_c {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(
+                json!({ "error": "Unauthorized", "reason": "You only have permission to add users with role \"Collector\"." }),
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Replaces the open activation links of a pending user with a new one and
+/// emails it. The email is sent before the transaction commits, so that a link
+/// that never went out is not kept.
+pub async fn send_activation(
+    app_state: &AppState,
+    transaction: &mut Transaction<'_, Postgres>,
+    user: &User,
+    invited_by: &User,
+) -> Result<UserActivation, (StatusCode, Json<Value>)> {
+    let database_error = |error: sqlx::Error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    };
+
+    sqlx::query!(
+        r#"
+        UPDATE user_activation SET revoked_at = CURRENT_TIMESTAMP
+        WHERE user_id = $1 AND used_at IS NULL AND revoked_at IS NULL
+        "#,
+        user.id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let token = generate_token();
+    let expires_at = (Utc::now() + Duration::days(ACTIVATION_TTL_DAYS)).naive_utc();
+
+    let activation = sqlx::query_as!(
+        UserActivation,
+        r#"
+        INSERT INTO user_activation (user_id, token_hash, invited_by, expires_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        user.id,
+        hash_token(&token),
+        invited_by.id,
+        expires_at
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(database_error)?;
+
+    let email = Email {
+        to: user.email.clone(),
+        subject: "Activate your 3reco account".to_string(),
+        body: format!(
+            "You have been invited to 3reco by {}.\n\nChoose a password to activate your account:\n{}/activate?token={}\n\nThe link expires on {} UTC.",
+            invited_by.email,
+            app_state.config.app_url,
+            token,
+            expires_at.format("%Y-%m-%d %H:%M")
+        ),
+    };
+
+    app_state.mailer.send(&email).await.map_err(|error| {
+        tracing::error!("🔥 Failed to send activation email: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to send the activation email."
+            })),
+        )
+    })?;
+
+    Ok(activation)
+}
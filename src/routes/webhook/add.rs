@@ -0,0 +1,118 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, user::User, webhook_subscription::WebhookSubscription,
+    },
+    idempotency::NoStore,
+    routes::business::{business_scope, require_business_manager},
+    webhooks::generate_secret,
+    AppState,
+};
+
+use super::validate_subscription;
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct AddWebhookPayload {
+    pub name: String,
+    pub url: String,
+    /// The event types to deliver, e.g. `collection.created`. All events when empty.
+    pub event_types: Option<Vec<String>>,
+    /// Only deliver events of this business. Always the own business for business users.
+    pub business_id: Option<Uuid>,
+}
+
+/// Creates a webhook subscription. The signing secret is only returned here.
+#[utoipa::path(
+    post,
+    path = "/webhook/add",
+    request_body = AddWebhookPayload,
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn webhook(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Json(payload): extract::Json<AddWebhookPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to add webhooks."
+            })),
+        ));
+    }
+
+    let event_types = payload.event_types.unwrap_or_default();
+
+    validate_subscription(&payload.url, &event_types).await?;
+
+    let business_id = match business_scope(&authenticated_user, &membership)? {
+        Some(business_id) => Some(business_id),
+        None => payload.business_id,
+    };
+
+    if let Some(business_id) = business_id {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "Only owners and managers can add webhooks.",
+        )?;
+    }
+
+    let secret = generate_secret();
+
+    let subscription = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+        INSERT INTO webhook_subscription (name, url, secret, event_types, business_id, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+        payload.name,
+        payload.url,
+        secret,
+        &event_types,
+        business_id,
+        authenticated_user.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::CREATED,
+        (
+            // The secret is only ever returned here.
+            extract::Extension(NoStore),
+            Json(json!({
+                "success": true,
+                "webhook": subscription,
+                "secret": secret
+            })),
+        ),
+    ))
+}
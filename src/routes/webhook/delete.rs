@@ -0,0 +1,65 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{business_member::BusinessMember, user::User},
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::find_subscription;
+
+#[utoipa::path(
+    delete,
+    path = "/webhook/{webhook_id}",
+    params(("webhook_id" = String, Path, description = "The webhooks id.")),
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn webhook(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(webhook_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let subscription =
+        find_subscription(&app_state, &authenticated_user, &membership, webhook_id).await?;
+
+    if let Some(business_id) = subscription.business_id {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "Only owners and managers can remove webhooks.",
+        )?;
+    }
+
+    sqlx::query!(
+        r#"
+        DELETE FROM webhook_subscription WHERE id = $1
+        "#,
+        subscription.id
+    )
+    .execute(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "webhook": subscription
+        })),
+    ))
+}
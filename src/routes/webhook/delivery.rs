@@ -0,0 +1,204 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        business_member::BusinessMember,
+        user::User,
+        webhook_delivery::{WebhookDelivery, WebhookDeliveryAttempt},
+    },
+    webhooks::worker::STATUS_PENDING,
+    AppState,
+};
+
+use super::find_subscription;
+
+/// The delivery log of a webhook, newest first.
+#[utoipa::path(
+    get,
+    path = "/webhook/{webhook_id}/deliveries",
+    params(("webhook_id" = String, Path, description = "The webhooks id.")),
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn deliveries(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(webhook_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let subscription =
+        find_subscription(&app_state, &authenticated_user, &membership, webhook_id).await?;
+
+    let deliveries = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        SELECT * FROM webhook_delivery WHERE subscription_id = $1 ORDER BY created_at DESC LIMIT 500
+        "#,
+        subscription.id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "deliveries": deliveries
+        })),
+    ))
+}
+
+async fn find_delivery(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    delivery_id: Uuid,
+) -> Result<WebhookDelivery, (StatusCode, Json<Value>)> {
+    let delivery = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        SELECT * FROM webhook_delivery WHERE id = $1
+        "#,
+        delivery_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    let delivery = delivery.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Delivery not found."
+        })),
+    ))?;
+
+    find_subscription(
+        app_state,
+        authenticated_user,
+        membership,
+        delivery.subscription_id,
+    )
+    .await?;
+
+    Ok(delivery)
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhook/delivery/{delivery_id}",
+    params(("delivery_id" = String, Path, description = "The deliveries id.")),
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn delivery(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(delivery_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let delivery = find_delivery(&app_state, &authenticated_user, &membership, delivery_id).await?;
+
+    let attempts = sqlx::query_as!(
+        WebhookDeliveryAttempt,
+        r#"
+        SELECT * FROM webhook_delivery_attempt WHERE delivery_id = $1 ORDER BY created_at
+        "#,
+        delivery.id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "delivery": delivery,
+            "attempts": attempts
+        })),
+    ))
+}
+
+/// Queues a delivery to be sent again straight away, also after it failed.
+#[utoipa::path(
+    post,
+    path = "/webhook/delivery/{delivery_id}/retry",
+    params(("delivery_id" = String, Path, description = "The deliveries id.")),
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn retry(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(delivery_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let delivery = find_delivery(&app_state, &authenticated_user, &membership, delivery_id).await?;
+
+    let delivery = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        UPDATE webhook_delivery
+        SET status = $1, attempts = 0, next_attempt_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $2
+        RETURNING *
+        "#,
+        STATUS_PENDING,
+        delivery.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "delivery": delivery
+        })),
+    ))
+}
@@ -0,0 +1,114 @@
+use axum::{http::StatusCode, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, user::User, webhook_subscription::WebhookSubscription,
+    },
+    network::resolve_public_url,
+    routes::business::business_scope,
+    webhooks::EVENT_TYPES,
+    AppState,
+};
+
+pub mod add;
+pub mod delete;
+pub mod delivery;
+pub mod update;
+pub mod view;
+
+/// Checks the url and event types of a subscription. The url must be https on
+/// a public address, the server posts to it.
+pub async fn validate_subscription(
+    url: &str,
+    event_types: &[String],
+) -> Result<(), (StatusCode, Json<Value>)> {
+    let bad_request = |reason: String| {
+        Err((
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "Bad Request",
+                "reason": reason
+            })),
+        ))
+    };
+
+    let url = match reqwest::Url::parse(url) {
+        Ok(url) if url.scheme() == "https" => url,
+        _ => return bad_request("The url must be a valid https url.".to_string()),
+    };
+
+    if let Err(reason) = resolve_public_url(&url).await {
+        return bad_request(reason);
+    }
+
+    if let Some(event_type) = event_types
+        .iter()
+        .find(|event_type| !EVENT_TYPES.contains(&event_type.as_str()))
+    {
+        return bad_request(format!(
+            "Unknown event type \"{}\", expected one of {}.",
+            event_type,
+            EVENT_TYPES.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds a subscription the user may manage. Business users may only manage
+/// the subscriptions of their own business.
+pub async fn find_subscription(
+    app_state: &AppState,
+    authenticated_user: &User,
+    membership: &Option<BusinessMember>,
+    subscription_id: Uuid,
+) -> Result<WebhookSubscription, (StatusCode, Json<Value>)> {
+    let requirement_a = authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business;
+
+    if requirement_a {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to manage webhooks."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(authenticated_user, membership)?;
+
+    let subscription = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+        SELECT * FROM webhook_subscription WHERE id = $1 AND ($2::uuid IS NULL OR business_id = $2)
+        "#,
+        subscription_id,
+        business_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    subscription.ok_or((
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "error": "Not Found",
+            "reason": "Webhook not found."
+        })),
+    ))
+}
@@ -0,0 +1,94 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{
+        business_member::BusinessMember, user::User, webhook_subscription::WebhookSubscription,
+    },
+    routes::business::require_business_manager,
+    AppState,
+};
+
+use super::{find_subscription, validate_subscription};
+
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
+pub struct UpdateWebhookPayload {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhook/{webhook_id}",
+    params(("webhook_id" = String, Path, description = "The webhooks id.")),
+    request_body = UpdateWebhookPayload,
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn webhook(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(webhook_id): extract::Path<Uuid>,
+    extract::Json(payload): extract::Json<UpdateWebhookPayload>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let subscription =
+        find_subscription(&app_state, &authenticated_user, &membership, webhook_id).await?;
+
+    if let Some(business_id) = subscription.business_id {
+        require_business_manager(
+            &authenticated_user,
+            &membership,
+            business_id,
+            "Only owners and managers can change webhooks.",
+        )?;
+    }
+
+    let name = payload.name.unwrap_or(subscription.name);
+    let url = payload.url.unwrap_or(subscription.url);
+    let event_types = payload.event_types.unwrap_or(subscription.event_types);
+    let active = payload.active.unwrap_or(subscription.active);
+
+    validate_subscription(&url, &event_types).await?;
+
+    let subscription = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+        UPDATE webhook_subscription
+        SET name = $1, url = $2, event_types = $3, active = $4, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $5
+        RETURNING *
+        "#,
+        name,
+        url,
+        &event_types,
+        active,
+        subscription.id
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "webhook": subscription
+        })),
+    ))
+}
@@ -0,0 +1,97 @@
+use axum::{extract, http::StatusCode, response::IntoResponse, Json};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    authentication::roles::Role,
+    data::entities::{
+        business_member::BusinessMember, user::User, webhook_subscription::WebhookSubscription,
+    },
+    routes::business::business_scope,
+    webhooks::EVENT_TYPES,
+    AppState,
+};
+
+use super::find_subscription;
+
+#[utoipa::path(
+    get,
+    path = "/webhook",
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn webhooks(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    if authenticated_user.role() != Role::Staff
+        && authenticated_user.role() != Role::SystemAdmin
+        && authenticated_user.role() != Role::Business
+    {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({
+                "error": "Unauthorized",
+                "reason": "You do not have permission to manage webhooks."
+            })),
+        ));
+    }
+
+    let business_id = business_scope(&authenticated_user, &membership)?;
+
+    let subscriptions = sqlx::query_as!(
+        WebhookSubscription,
+        r#"
+        SELECT * FROM webhook_subscription WHERE ($1::uuid IS NULL OR business_id = $1) ORDER BY created_at
+        "#,
+        business_id
+    )
+    .fetch_all(&app_state.pool)
+    .await
+    .map_err(|error| {
+        tracing::error!("🔥 Failed to query database: {}", error);
+
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Internal Server Error",
+                "reason": "Failed to query database."
+            })),
+        )
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "webhooks": subscriptions,
+            "event_types": EVENT_TYPES
+        })),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhook/{webhook_id}",
+    params(("webhook_id" = String, Path, description = "The webhooks id.")),
+    tag = "Webhook",
+    security(("bearer_auth" = [])),
+)]
+pub async fn webhook(
+    extract::State(app_state): extract::State<AppState>,
+    extract::Extension(authenticated_user): extract::Extension<User>,
+    extract::Extension(membership): extract::Extension<Option<BusinessMember>>,
+    extract::Path(webhook_id): extract::Path<Uuid>,
+) -> Result<(StatusCode, impl IntoResponse), (StatusCode, Json<Value>)> {
+    let subscription =
+        find_subscription(&app_state, &authenticated_user, &membership, webhook_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(json!({
+            "success": true,
+            "webhook": subscription
+        })),
+    ))
+}
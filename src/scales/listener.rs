@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use chrono::Utc;
+use rand::Rng;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader},
+    net::TcpStream,
+    time::sleep,
+};
+
+use crate::{data::entities::scale::Scale, network::resolve_public};
+
+use super::{
+    protocol::{format_frame, parse_frame},
+    ScaleReading, Scales, CONNECTION_TYPE_SIMULATED, CONNECTION_TYPE_TCP,
+};
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// Frames are a few dozen bytes, a longer line means the peer is not a scale.
+const MAX_FRAME_LENGTH: u64 = 256;
+
+/// Reads the frames of a scale until the listener is stopped, reconnecting
+/// with an increasing delay whenever the connection is lost.
+pub async fn listen(scale: Scale, scales: Scales) {
+    let mut delay = Duration::from_secs(1);
+
+    loop {
+        let result = match scale.connection_type.as_str() {
+            CONNECTION_TYPE_TCP => read_tcp(&scale, &scales).await,
+            CONNECTION_TYPE_SIMULATED => simulate(&scale, &scales).await,
+            connection_type => {
+                tracing::error!(
+                    "🔥 Scale {} has an unknown connection type \"{}\".",
+                    scale.id,
+                    connection_type
+                );
+                return;
+            }
+        };
+
+        if let Err(error) = result {
+            tracing::error!("🔥 Lost connection to scale {}: {}", scale.id, error);
+        }
+
+        sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+async fn handle_line(scale: &Scale, scales: &Scales, line: &str) {
+    if line.trim().is_empty() {
+        return;
+    }
+
+    match parse_frame(line) {
+        Ok(frame) => {
+            scales
+                .publish(ScaleReading {
+                    scale_id: scale.id,
+                    business_id: scale.business_id,
+                    weight: frame.weight,
+                    stable: frame.stable,
+                    overload: frame.overload,
+                    net: frame.net,
+                    received_at: Utc::now().naive_utc(),
+                })
+                .await
+        }
+        Err(error) => tracing::warn!("❗ Ignoring frame from scale {}: {}", scale.id, error),
+    }
+}
+
+async fn read_tcp(scale: &Scale, scales: &Scales) -> Result<(), Error> {
+    let host = scale.host.clone().unwrap_or_default();
+    let port = scale.port.unwrap_or_default();
+
+    // Resolved again on every connect, the host may have been changed to
+    // point into the internal network after the scale was added.
+    let addresses = resolve_public(&host, port as u16)
+        .await
+        .map_err(Error::msg)?;
+    let stream = TcpStream::connect(&addresses[..]).await?;
+
+    tracing::info!("✅ Connected to scale {} at {}:{}.", scale.id, host, port);
+
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+
+        let read = (&mut reader)
+            .take(MAX_FRAME_LENGTH + 1)
+            .read_until(b'\n', &mut line)
+            .await?;
+
+        if read == 0 {
+            return Err(Error::msg("The scale closed the connection."));
+        }
+
+        if line.len() as u64 > MAX_FRAME_LENGTH {
+            return Err(Error::msg(format!(
+                "The scale sent a frame longer than {} bytes.",
+                MAX_FRAME_LENGTH
+            )));
+        }
+
+        handle_line(scale, scales, &String::from_utf8_lossy(&line)).await;
+    }
+}
+
+/// Emits frames like a platform scale that is loaded, settles and is emptied
+/// again, so that the capture flow can be tested without hardware.
+async fn simulate(scale: &Scale, scales: &Scales) -> Result<(), Error> {
+    loop {
+        for _ in 0..6 {
+            handle_line(scale, scales, &format_frame(true, 0.0)).await;
+            sleep(Duration::from_millis(500)).await;
+        }
+
+        let target: f64 = rand::thread_rng().gen_range(0.5..150.0);
+
+        for step in 1..=4 {
+            let jitter: f64 = rand::thread_rng().gen_range(-2.0..2.0);
+            let weight = (target * step as f64 / 4.0 + jitter).max(0.0);
+
+            handle_line(scale, scales, &format_frame(false, weight)).await;
+            sleep(Duration::from_millis(500)).await;
+        }
+
+        let target = (target * 100.0).round() / 100.0;
+
+        for _ in 0..20 {
+            handle_line(scale, scales, &format_frame(true, target)).await;
+            sleep(Duration::from_millis(500)).await;
+        }
+
+        handle_line(scale, scales, &format_frame(false, target / 2.0)).await;
+        sleep(Duration::from_millis(500)).await;
+    }
+}
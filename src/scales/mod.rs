@@ -0,0 +1,143 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Error;
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Postgres};
+use tokio::{
+    sync::{broadcast, Mutex, RwLock},
+    task::JoinHandle,
+};
+use uuid::Uuid;
+
+use crate::data::entities::scale::Scale;
+
+pub mod listener;
+pub mod protocol;
+
+/// The scale is a TCP server, or a serial-to-TCP bridge, that streams frames.
+pub const CONNECTION_TYPE_TCP: &str = "Tcp";
+/// A scale that generates weighings by itself, for testing.
+pub const CONNECTION_TYPE_SIMULATED: &str = "Simulated";
+
+/// A reading is only used for a collection while it is this fresh.
+const MAX_READING_AGE_SECONDS: i64 = 5;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ScaleReading {
+    pub scale_id: Uuid,
+    pub business_id: Uuid,
+    /// The weight in kilograms.
+    pub weight: BigDecimal,
+    pub stable: bool,
+    pub overload: bool,
+    pub net: bool,
+    pub received_at: NaiveDateTime,
+}
+
+/// Listens to the active scales and keeps their latest readings.
+#[derive(Clone)]
+pub struct Scales {
+    readings: Arc<RwLock<HashMap<Uuid, ScaleReading>>>,
+    listeners: Arc<Mutex<HashMap<Uuid, JoinHandle<()>>>>,
+    sender: broadcast::Sender<ScaleReading>,
+}
+
+impl Scales {
+    /// Starts a listener for every active scale.
+    pub async fn init(pool: &Pool<Postgres>) -> Result<Self, Error> {
+        let (sender, _) = broadcast::channel(256);
+
+        let scales = Self {
+            readings: Arc::new(RwLock::new(HashMap::new())),
+            listeners: Arc::new(Mutex::new(HashMap::new())),
+            sender,
+        };
+
+        let active_scales = sqlx::query_as!(
+            Scale,
+            r#"
+                SELECT * FROM scale WHERE active = TRUE
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for scale in &active_scales {
+            scales.start(scale).await;
+        }
+
+        tracing::info!("✅ Listening to {} scales.", active_scales.len());
+
+        Ok(scales)
+    }
+
+    /// Starts listening to a scale, restarting the listener if it was already
+    /// running. Inactive scales are only stopped.
+    pub async fn start(&self, scale: &Scale) {
+        self.stop(scale.id).await;
+
+        if !scale.active {
+            return;
+        }
+
+        let handle = tokio::spawn(listener::listen(scale.clone(), self.clone()));
+
+        self.listeners.lock().await.insert(scale.id, handle);
+    }
+
+    pub async fn stop(&self, scale_id: Uuid) {
+        if let Some(handle) = self.listeners.lock().await.remove(&scale_id) {
+            handle.abort();
+        }
+
+        self.readings.write().await.remove(&scale_id);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ScaleReading> {
+        self.sender.subscribe()
+    }
+
+    pub async fn reading(&self, scale_id: Uuid) -> Option<ScaleReading> {
+        self.readings.read().await.get(&scale_id).cloned()
+    }
+
+    /// The weight currently on the scale, as long as it is stable, recent and
+    /// not an overload.
+    pub async fn stable_weight(&self, scale_id: Uuid) -> Result<BigDecimal, String> {
+        let reading = match self.reading(scale_id).await {
+            Some(reading) => reading,
+            None => return Err("The scale has not sent a weight yet.".to_string()),
+        };
+
+        if Utc::now().naive_utc() - reading.received_at > Duration::seconds(MAX_READING_AGE_SECONDS)
+        {
+            return Err("The scale has not sent a weight recently.".to_string());
+        }
+
+        if reading.overload {
+            return Err("The scale is overloaded.".to_string());
+        }
+
+        if !reading.stable {
+            return Err("The weight on the scale is not stable yet.".to_string());
+        }
+
+        if reading.weight <= BigDecimal::from(0) {
+            return Err("The scale is empty.".to_string());
+        }
+
+        Ok(reading.weight)
+    }
+
+    async fn publish(&self, reading: ScaleReading) {
+        self.readings
+            .write()
+            .await
+            .insert(reading.scale_id, reading.clone());
+
+        // Nobody may be watching the scale, which is fine.
+        let _ = self.sender.send(reading);
+    }
+}
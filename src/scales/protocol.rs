@@ -0,0 +1,157 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+
+/// A single weight frame as emitted by the scale, e.g. `ST,GS,+0012.34kg`.
+///
+/// The first field is the status (`ST` stable, `US` unstable, `OL` overload),
+/// the second the weighing mode (`GS` gross, `NT` net) and the last the signed
+/// weight followed by its unit. Some indicators add a device number between
+/// the mode and the weight, which is ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub stable: bool,
+    pub overload: bool,
+    pub net: bool,
+    /// The weight converted to kilograms.
+    pub weight: BigDecimal,
+}
+
+pub fn parse_frame(line: &str) -> Result<Frame, String> {
+    let fields: Vec<&str> = line.trim().split(',').map(|field| field.trim()).collect();
+
+    if fields.len() < 3 {
+        return Err(format!(
+            "Expected at least 3 fields in \"{}\".",
+            line.trim()
+        ));
+    }
+
+    let (stable, overload) = match fields[0] {
+        "ST" => (true, false),
+        "US" => (false, false),
+        "OL" => (false, true),
+        status => return Err(format!("Unknown status \"{}\".", status)),
+    };
+
+    let net = match fields[1] {
+        "GS" => false,
+        "NT" => true,
+        mode => return Err(format!("Unknown weighing mode \"{}\".", mode)),
+    };
+
+    let value = fields[fields.len() - 1];
+    let unit_start = value
+        .find(|character: char| character.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(unit_start);
+
+    let number = BigDecimal::from_str(number.trim().trim_start_matches('+'))
+        .map_err(|_| format!("Invalid weight \"{}\".", value))?;
+
+    let weight = match unit.trim().to_lowercase().as_str() {
+        "kg" | "" => number,
+        "g" => number / BigDecimal::from(1000),
+        "t" => number * BigDecimal::from(1000),
+        "lb" => number * BigDecimal::from_str("0.45359237").unwrap(),
+        unit => return Err(format!("Unknown unit \"{}\".", unit)),
+    };
+
+    Ok(Frame {
+        stable,
+        overload,
+        net,
+        weight,
+    })
+}
+
+/// Formats a gross weight in kilograms the way the scales emit it.
+pub fn format_frame(stable: bool, weight: f64) -> String {
+    format!(
+        "{},GS,{:+08.2}kg",
+        match stable {
+            true => "ST",
+            false => "US",
+        },
+        weight
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kg(weight: &str) -> BigDecimal {
+        BigDecimal::from_str(weight).unwrap()
+    }
+
+    #[test]
+    fn parses_stable_gross_frame() {
+        assert_eq!(
+            parse_frame("ST,GS,+0012.34kg"),
+            Ok(Frame {
+                stable: true,
+                overload: false,
+                net: false,
+                weight: kg("12.34"),
+            })
+        );
+    }
+
+    #[test]
+    fn parses_unstable_net_frame() {
+        let frame = parse_frame("US,NT,-0001.50kg").unwrap();
+
+        assert!(!frame.stable);
+        assert!(!frame.overload);
+        assert!(frame.net);
+        assert_eq!(frame.weight, kg("-1.50"));
+    }
+
+    #[test]
+    fn parses_overload_frame() {
+        let frame = parse_frame("OL,GS,+9999.99kg").unwrap();
+
+        assert!(!frame.stable);
+        assert!(frame.overload);
+    }
+
+    #[test]
+    fn ignores_device_number_and_whitespace() {
+        let frame = parse_frame("  ST, GS, 01, +0005.00 kg\r\n").unwrap();
+
+        assert!(frame.stable);
+        assert_eq!(frame.weight, kg("5.00"));
+    }
+
+    #[test]
+    fn converts_units_to_kilograms() {
+        assert_eq!(parse_frame("ST,GS,+1500g").unwrap().weight, kg("1.5"));
+        assert_eq!(parse_frame("ST,GS,+1.2t").unwrap().weight, kg("1200"));
+        assert_eq!(parse_frame("ST,GS,+10lb").unwrap().weight, kg("4.5359237"));
+        assert_eq!(parse_frame("ST,GS,+10KG").unwrap().weight, kg("10"));
+        assert_eq!(parse_frame("ST,GS,+0010.00").unwrap().weight, kg("10"));
+    }
+
+    #[test]
+    fn parses_formatted_frames() {
+        let frame = parse_frame(&format_frame(true, 42.5)).unwrap();
+
+        assert!(frame.stable);
+        assert!(!frame.net);
+        assert_eq!(frame.weight, kg("42.5"));
+        assert!(!parse_frame(&format_frame(false, 0.0)).unwrap().stable);
+    }
+
+    #[test]
+    fn rejects_malformed_frames() {
+        assert!(parse_frame("").is_err());
+        assert!(parse_frame("ST,GS").is_err());
+        assert!(parse_frame("XX,GS,+0012.34kg").is_err());
+        assert!(parse_frame("ST,XX,+0012.34kg").is_err());
+        assert!(parse_frame("ST,GS,kg").is_err());
+        assert!(parse_frame("ST,GS,+12.3.4kg").is_err());
+        assert!(parse_frame("ST,GS,+0012.34oz").is_err());
+        assert!(parse_frame("ST,GS,garbage").is_err());
+    }
+}
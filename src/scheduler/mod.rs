@@ -0,0 +1,177 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use anyhow::Error;
+use chrono::{FixedOffset, Utc};
+use cron::Schedule;
+use sqlx::{Pool, Postgres};
+use tokio::sync::Mutex;
+use tokio_cron_scheduler::{Job, JobScheduler};
+use uuid::Uuid;
+
+use crate::{config::Config, data::entities::report_job::ReportJob};
+
+pub mod report_type;
+pub mod reports;
+
+/// How long collection events are kept for dashboards to resume from.
+const COLLECTION_EVENT_RETENTION_DAYS: i32 = 7;
+
+/// When data that is only kept for a while is pruned, every night at 03:00.
+const MAINTENANCE_SCHEDULE: &str = "0 0 3 * * *";
+
+/// Runs persisted report jobs on their cron schedules.
+///
+/// The scheduler keeps track of which scheduler job belongs to which report
+/// job, so that a report job can be rescheduled whenever it is changed through
+/// the api.
+#[derive(Clone)]
+pub struct Scheduler {
+    job_scheduler: JobScheduler,
+    jobs: Arc<Mutex<HashMap<Uuid, Uuid>>>,
+    pool: Pool<Postgres>,
+    reports_directory: String,
+}
+
+impl Scheduler {
+    /// Creates the scheduler, schedules every active report job and starts
+    /// ticking.
+    pub async fn init(pool: Pool<Postgres>, config: &Config) -> Result<Self, Error> {
+        let job_scheduler = JobScheduler::new().await?;
+
+        let scheduler = Self {
+            job_scheduler,
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            pool: pool.clone(),
+            reports_directory: config.reports_directory.clone(),
+        };
+
+        let report_jobs = sqlx::query_as!(
+            ReportJob,
+            r#"
+                SELECT * FROM report_job WHERE active = TRUE
+            "#
+        )
+        .fetch_all(&pool)
+        .await?;
+
+        for report_job in &report_jobs {
+            if let Err(error) = scheduler.schedule(report_job).await {
+                tracing::error!(
+                    "🔥 Failed to schedule report job {}: {}",
+                    report_job.id,
+                    error
+                );
+            }
+        }
+
+        scheduler.schedule_maintenance().await?;
+
+        scheduler.job_scheduler.start().await?;
+
+        tracing::info!(
+            "✅ Scheduler started with {} report jobs.",
+            scheduler.jobs.lock().await.len()
+        );
+
+        Ok(scheduler)
+    }
+
+    /// Schedules a report job, replacing any previous schedule of the same
+    /// report job. Inactive report jobs are only unscheduled.
+    pub async fn schedule(&self, report_job: &ReportJob) -> Result<(), Error> {
+        self.unschedule(report_job.id).await?;
+
+        if !report_job.active {
+            return Ok(());
+        }
+
+        let schedule = parse_schedule(&report_job.cron_expression)?;
+        let timezone = parse_utc_offset(report_job.utc_offset_minutes)?;
+
+        let pool = self.pool.clone();
+        let reports_directory = self.reports_directory.clone();
+        let report_job_id = report_job.id;
+
+        let job = Job::new_async_tz(schedule, timezone, move |_, _| {
+            let pool = pool.clone();
+            let reports_directory = reports_directory.clone();
+
+            Box::pin(async move {
+                tracing::info!("❕ Running report job {}.", report_job_id);
+
+                match reports::run_report_job(&pool, &reports_directory, report_job_id).await {
+                    Ok(report_job_run) => tracing::info!(
+                        "✅ Report job {} finished with status {}.",
+                        report_job_id,
+                        report_job_run.status
+                    ),
+                    Err(error) => {
+                        tracing::error!("🔥 Failed to run report job {}: {}", report_job_id, error)
+                    }
+                }
+            })
+        })?;
+
+        let job_id = self.job_scheduler.add(job).await?;
+
+        self.jobs.lock().await.insert(report_job.id, job_id);
+
+        Ok(())
+    }
+
+    /// Schedules pruning collection events past their retention.
+    async fn schedule_maintenance(&self) -> Result<(), Error> {
+        let pool = self.pool.clone();
+
+        let job = Job::new_async_tz(parse_schedule(MAINTENANCE_SCHEDULE)?, Utc, move |_, _| {
+            let pool = pool.clone();
+
+            Box::pin(async move {
+                let pruned = sqlx::query!(
+                    r#"
+                        DELETE FROM collection_event
+                        WHERE created_at < CURRENT_TIMESTAMP - MAKE_INTERVAL(days => $1)
+                    "#,
+                    COLLECTION_EVENT_RETENTION_DAYS
+                )
+                .execute(&pool)
+                .await;
+
+                match pruned {
+                    Ok(pruned) => {
+                        tracing::info!("✅ Pruned {} collection events.", pruned.rows_affected())
+                    }
+                    Err(error) => {
+                        tracing::error!("🔥 Failed to prune collection events: {}", error)
+                    }
+                }
+            })
+        })?;
+
+        self.job_scheduler.add(job).await?;
+
+        Ok(())
+    }
+
+    /// Removes the schedule of a report job, if it has one.
+    pub async fn unschedule(&self, report_job_id: Uuid) -> Result<(), Error> {
+        let job_id = self.jobs.lock().await.remove(&report_job_id);
+
+        if let Some(job_id) = job_id {
+            self.job_scheduler.remove(&job_id).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a cron expression in the `sec min hour day-of-month month
+/// day-of-week` format, e.g. `0 0 6 * * Mon` for every Monday at 06:00.
+pub fn parse_schedule(cron_expression: &str) -> Result<Schedule, Error> {
+    Schedule::from_str(cron_expression).map_err(|error| Error::msg(error.to_string()))
+}
+
+pub fn parse_utc_offset(utc_offset_minutes: i32) -> Result<FixedOffset, Error> {
+    FixedOffset::east_opt(utc_offset_minutes * 60)
+        .ok_or_else(|| Error::msg("The UTC offset must be within 24 hours."))
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The reports that can be produced by a scheduled report job. Each report
+/// uses the same csv format as the matching export route.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+pub enum ReportType {
+    CollectionExport,
+    CollectorExport,
+    BusinessExport,
+    ProductExport,
+}
+
+impl fmt::Display for ReportType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportType::CollectionExport => write!(f, "Collection Export"),
+            ReportType::CollectorExport => write!(f, "Collector Export"),
+            ReportType::BusinessExport => write!(f, "Business Export"),
+            ReportType::ProductExport => write!(f, "Product Export"),
+        }
+    }
+}
+
+impl ReportType {
+    pub fn from_name(name: &str) -> Option<ReportType> {
+        match name {
+            "Collection Export" => Some(ReportType::CollectionExport),
+            "Collector Export" => Some(ReportType::CollectorExport),
+            "Business Export" => Some(ReportType::BusinessExport),
+            "Product Export" => Some(ReportType::ProductExport),
+            _ => None,
+        }
+    }
+
+    /// Used in the file names of the generated reports.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ReportType::CollectionExport => "collection-export",
+            ReportType::CollectorExport => "collector-export",
+            ReportType::BusinessExport => "business-export",
+            ReportType::ProductExport => "product-export",
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use anyhow::Error;
+use chrono::Utc;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+use crate::{
+    data::entities::{report_job::ReportJob, report_job_run::ReportJobRun},
+    routes::export::{
+        business::business_csv, collection::collection_csv, collector::collector_csv,
+        product::product_csv,
+    },
+};
+
+use super::report_type::ReportType;
+
+/// Runs a report job once, writing its output to the reports store and
+/// recording the run, whether it succeeded or failed.
+pub async fn run_report_job(
+    pool: &Pool<Postgres>,
+    reports_directory: &str,
+    report_job_id: Uuid,
+) -> Result<ReportJobRun, Error> {
+    let report_job = sqlx::query_as!(
+        ReportJob,
+        r#"
+            SELECT * FROM report_job WHERE id = $1
+        "#,
+        report_job_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let report_job_run = sqlx::query_as!(
+        ReportJobRun,
+        r#"
+            INSERT INTO report_job_run (report_job_id, period_start, period_end)
+            VALUES (
+                $1,
+                (SELECT MAX(period_end) FROM report_job_run WHERE report_job_id = $1 AND status = 'Succeeded'),
+                CURRENT_TIMESTAMP
+            )
+            RETURNING *
+        "#,
+        report_job.id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let report_job_run = match write_report(pool, reports_directory, &report_job, &report_job_run).await {
+        Ok((output_path, output_size)) => {
+            sqlx::query_as!(
+                ReportJobRun,
+                r#"
+                    UPDATE report_job_run
+                    SET status = 'Succeeded', output_path = $1, output_size = $2, finished_at = CURRENT_TIMESTAMP
+                    WHERE id = $3
+                    RETURNING *
+                "#,
+                output_path,
+                output_size,
+                report_job_run.id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+        Err(error) => {
+            tracing::error!("🔥 Report job {} failed: {}", report_job.id, error);
+
+            sqlx::query_as!(
+                ReportJobRun,
+                r#"
+                    UPDATE report_job_run
+                    SET status = 'Failed', error = $1, finished_at = CURRENT_TIMESTAMP
+                    WHERE id = $2
+                    RETURNING *
+                "#,
+                error.to_string(),
+                report_job_run.id
+            )
+            .fetch_one(pool)
+            .await?
+        }
+    };
+
+    sqlx::query!(
+        r#"
+            UPDATE report_job SET last_run_at = CURRENT_TIMESTAMP WHERE id = $1
+        "#,
+        report_job.id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(report_job_run)
+}
+
+/// Generates the report and stores it as
+/// `<reports directory>/<report job id>/<timestamp>-<report type>.csv`.
+/// Collection exports only cover the period of the run, the other reports are
+/// a snapshot of the current data.
+async fn write_report(
+    pool: &Pool<Postgres>,
+    reports_directory: &str,
+    report_job: &ReportJob,
+    report_job_run: &ReportJobRun,
+) -> Result<(String, i64), Error> {
+    let report_type = ReportType::from_name(&report_job.report_type).ok_or_else(|| {
+        Error::msg(format!(
+            "Unknown report type \"{}\".",
+            report_job.report_type
+        ))
+    })?;
+
+    let csv_string = match report_type {
+        ReportType::CollectionExport => {
+            collection_csv(
+                pool,
+                report_job.business_id,
+                report_job_run.period_start,
+                report_job_run.period_end,
+            )
+            .await?
+        }
+        ReportType::CollectorExport => collector_csv(pool).await?,
+        ReportType::BusinessExport => business_csv(pool, report_job.business_id).await?,
+        ReportType::ProductExport => product_csv(pool, report_job.business_id).await?,
+    };
+
+    let directory = Path::new(reports_directory).join(report_job.id.to_string());
+
+    tokio::fs::create_dir_all(&directory).await?;
+
+    let file_path = directory.join(format!(
+        "{}-{}.csv",
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        report_type.slug()
+    ));
+
+    tokio::fs::write(&file_path, csv_string.as_bytes()).await?;
+
+    Ok((
+        file_path.to_string_lossy().to_string(),
+        csv_string.len() as i64,
+    ))
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use anyhow::Error;
+use chrono::Utc;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct FileSms {
+    directory: PathBuf,
+}
+
+impl FileSms {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+
+    pub async fn send(&self, to: &str, message: &str) -> Result<(), Error> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+
+        let path = self.directory.join(format!(
+            "{}-{}.txt",
+            Utc::now().format("%Y%m%d%H%M%S"),
+            Uuid::new_v4()
+        ));
+
+        tokio::fs::write(&path, format!("To: {}\n\n{}\n", to, message)).await?;
+
+        tracing::info!("📱 Wrote text message to {} to {}", to, path.display());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::{anyhow, Error};
+use serde_json::json;
+
+use crate::config::Config;
+
+/// Posts `{ "to": ..., "message": ... }` to the gateway, with the token as a
+/// bearer token when there is one.
+#[derive(Clone)]
+pub struct HttpSms {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl HttpSms {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let url = config
+            .sms_gateway_url
+            .clone()
+            .ok_or_else(|| anyhow!("SMS_GATEWAY_URL is required for the http SMS backend."))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            url,
+            token: config.sms_gateway_token.clone(),
+        })
+    }
+
+    pub async fn send(&self, to: &str, message: &str) -> Result<(), Error> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "to": to, "message": message }));
+
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "The SMS gateway responded with {}.",
+                response.status()
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anyhow::{anyhow, Error};
+
+use crate::config::Config;
+
+use self::{file::FileSms, http::HttpSms};
+
+pub mod file;
+pub mod http;
+
+/// How text messages are sent. The file backend is the default and writes each
+/// message to the SMS directory, the HTTP backend posts them to an SMS
+/// gateway.
+#[derive(Clone)]
+pub enum Sms {
+    File(FileSms),
+    Http(HttpSms),
+}
+
+impl Sms {
+    pub fn init(config: &Config) -> Result<Self, Error> {
+        match config.sms_backend.as_str() {
+            "file" => Ok(Sms::File(FileSms::new(&config.sms_directory))),
+            "http" => Ok(Sms::Http(HttpSms::new(config)?)),
+            backend => Err(anyhow!("Unknown SMS backend \"{}\".", backend)),
+        }
+    }
+
+    pub async fn send(&self, to: &str, message: &str) -> Result<(), Error> {
+        match self {
+            Sms::File(sms) => sms.send(to, message).await,
+            Sms::Http(sms) => sms.send(to, message).await,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Error};
+
+#[derive(Clone)]
+pub struct LocalStorage {
+    directory: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, Error> {
+        if key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "..")
+        {
+            return Err(anyhow!("Invalid storage key \"{}\".", key));
+        }
+
+        Ok(self.directory.join(key))
+    }
+
+    pub async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), Error> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(path, bytes).await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        Ok(tokio::fs::read(self.path(key)?).await?)
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), Error> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    }
+}
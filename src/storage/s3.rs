@@ -0,0 +1,157 @@
+use anyhow::{anyhow, Error};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, Url};
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+/// A bucket on an S3-compatible server, addressed path-style so that it works
+/// with MinIO as well as AWS. Requests are signed with AWS Signature Version 4.
+#[derive(Clone)]
+pub struct S3Storage {
+    client: Client,
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+impl S3Storage {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let setting = |value: &Option<String>, name: &str| {
+            value
+                .clone()
+                .ok_or_else(|| anyhow!("{} is required for the s3 storage backend.", name))
+        };
+
+        let endpoint = Url::parse(&setting(&config.s3_endpoint, "S3_ENDPOINT")?)?;
+
+        Ok(Self {
+            client: Client::new(),
+            endpoint,
+            bucket: setting(&config.s3_bucket, "S3_BUCKET")?,
+            region: config.s3_region.clone(),
+            access_key: setting(&config.s3_access_key, "S3_ACCESS_KEY")?,
+            secret_key: setting(&config.s3_secret_key, "S3_SECRET_KEY")?,
+        })
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<reqwest::Response, Error> {
+        // Keys are generated by the server and only contain unreserved
+        // characters and slashes, so the path needs no further encoding.
+        let path = format!(
+            "{}/{}/{}",
+            self.endpoint.path().trim_end_matches('/'),
+            self.bucket,
+            key
+        );
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(anyhow!("S3_ENDPOINT has no host.")),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method.as_str(),
+            path,
+            host,
+            payload_hash,
+            amz_date,
+            payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = hmac(
+            &hmac(
+                &hmac(
+                    &hmac(format!("AWS4{}", self.secret_key).as_bytes(), &date),
+                    &self.region,
+                ),
+                "s3",
+            ),
+            "aws4_request",
+        );
+        let signature = hex(&hmac(&signing_key, &string_to_sign));
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header(
+                "Authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+                    self.access_key, scope, signature
+                ),
+            );
+
+        if let Some(content_type) = content_type {
+            request = request.header("Content-Type", content_type);
+        }
+
+        let response = request.body(body).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let reason = response.text().await.unwrap_or_default();
+
+            return Err(anyhow!("S3 responded with {}: {}", status, reason));
+        }
+
+        Ok(response)
+    }
+
+    pub async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), Error> {
+        self.send(Method::PUT, key, bytes, Some(content_type))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>, Error> {
+        let response = self.send(Method::GET, key, Vec::new(), None).await?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<(), Error> {
+        self.send(Method::DELETE, key, Vec::new(), None).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub mod worker;
+
+pub const COLLECTION_CREATED: &str = "collection.created";
+pub const COLLECTION_UPDATED: &str = "collection.updated";
+pub const COLLECTION_DELETED: &str = "collection.deleted";
+pub const COLLECTION_PAID: &str = "collection.paid";
+pub const COLLECTOR_REGISTERED: &str = "collector.registered";
+pub const BUSINESS_CREATED: &str = "business.created";
+pub const BUSINESS_UPDATED: &str = "business.updated";
+
+pub const EVENT_TYPES: [&str; 7] = [
+    COLLECTION_CREATED,
+    COLLECTION_UPDATED,
+    COLLECTION_DELETED,
+    COLLECTION_PAID,
+    COLLECTOR_REGISTERED,
+    BUSINESS_CREATED,
+    BUSINESS_UPDATED,
+];
+
+/// Queues a delivery of the event for every active subscription that wants
+/// it. The worker sends the deliveries, so emitting never waits on a partner.
+///
+/// Failing to queue the event is logged rather than returned, an event must
+/// never fail the request that caused it.
+pub async fn emit(pool: &Pool<Postgres>, event_type: &str, business_id: Option<Uuid>, data: Value) {
+    let event_id = Uuid::new_v4();
+
+    let payload = json!({
+        "id": event_id,
+        "type": event_type,
+        "created_at": Utc::now().naive_utc(),
+        "data": data
+    });
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO webhook_delivery (subscription_id, event_id, event_type, payload)
+        SELECT id, $1, $2::text, $3
+        FROM webhook_subscription
+        WHERE active = TRUE
+            AND (CARDINALITY(event_types) = 0 OR $2::text = ANY(event_types))
+            AND (business_id IS NULL OR business_id = $4)
+        "#,
+        event_id,
+        event_type,
+        payload,
+        business_id
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(error) = result {
+        tracing::error!("🔥 Failed to queue {} event: {}", event_type, error);
+    }
+}
+
+/// Signs the delivery as `sha256=<hex hmac>` of `<timestamp>.<body>`, so that
+/// receivers can reject both forged and replayed deliveries.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+
+    let signature: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    format!("sha256={}", signature)
+}
+
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::random();
+
+    let secret: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("whsec_{}", secret)
+}
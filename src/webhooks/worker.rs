@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use reqwest::{redirect::Policy, Client, Url};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    data::entities::{
+        webhook_delivery::WebhookDelivery, webhook_subscription::WebhookSubscription,
+    },
+    network::resolve_public_url,
+};
+
+use super::sign;
+
+pub const STATUS_PENDING: &str = "Pending";
+pub const STATUS_DELIVERED: &str = "Delivered";
+pub const STATUS_FAILED: &str = "Failed";
+
+/// A delivery is given up after this many attempts, about 17 hours after the
+/// event with the backoff below.
+const MAX_ATTEMPTS: i32 = 12;
+const BASE_RETRY_DELAY_SECONDS: i64 = 30;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const BATCH_SIZE: i64 = 20;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sends due deliveries until the server stops.
+pub fn start(pool: Pool<Postgres>) {
+    tokio::spawn(async move {
+        loop {
+            match deliver_due(&pool).await {
+                Ok(0) => tokio::time::sleep(POLL_INTERVAL).await,
+                Ok(_) => {}
+                Err(error) => {
+                    tracing::error!("🔥 Failed to send webhook deliveries: {}", error);
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    });
+}
+
+/// Claims a batch of due deliveries and attempts each of them once. Returns
+/// the number of deliveries attempted.
+///
+/// Claiming pushes the next attempt back, so a delivery that was claimed by a
+/// server that stopped halfway is picked up again later.
+async fn deliver_due(pool: &Pool<Postgres>) -> Result<usize, sqlx::Error> {
+    let deliveries = sqlx::query_as!(
+        WebhookDelivery,
+        r#"
+        UPDATE webhook_delivery
+        SET next_attempt_at = CURRENT_TIMESTAMP + INTERVAL '5 minutes'
+        WHERE id IN (
+            SELECT id FROM webhook_delivery
+            WHERE status = $1 AND next_attempt_at <= CURRENT_TIMESTAMP
+            ORDER BY next_attempt_at
+            LIMIT $2
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING *
+        "#,
+        STATUS_PENDING,
+        BATCH_SIZE
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for delivery in &deliveries {
+        let subscription = sqlx::query_as!(
+            WebhookSubscription,
+            r#"
+            SELECT * FROM webhook_subscription WHERE id = $1
+            "#,
+            delivery.subscription_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        let attempt = delivery.attempts + 1;
+        let started = Instant::now();
+        let (status_code, error) = send(&subscription, delivery).await;
+        let duration_ms = started.elapsed().as_millis() as i64;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_delivery_attempt (delivery_id, attempt, status_code, error, duration_ms)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            delivery.id,
+            attempt,
+            status_code,
+            error,
+            duration_ms
+        )
+        .execute(pool)
+        .await?;
+
+        let delivered = error.is_none();
+
+        let status = match (delivered, attempt >= MAX_ATTEMPTS) {
+            (true, _) => STATUS_DELIVERED,
+            (false, true) => STATUS_FAILED,
+            (false, false) => STATUS_PENDING,
+        };
+
+        let retry_delay = BASE_RETRY_DELAY_SECONDS * 2_i64.pow((attempt - 1).min(16) as u32);
+
+        sqlx::query!(
+            r#"
+            UPDATE webhook_delivery
+            SET status = $1, attempts = $2, next_attempt_at = CURRENT_TIMESTAMP + MAKE_INTERVAL(secs => $3), last_status_code = $4, last_error = $5,
+                delivered_at = CASE WHEN $6 THEN CURRENT_TIMESTAMP ELSE NULL END,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $7
+            "#,
+            status,
+            attempt,
+            retry_delay as f64,
+            status_code,
+            error,
+            delivered,
+            delivery.id
+        )
+        .execute(pool)
+        .await?;
+
+        if status == STATUS_FAILED {
+            tracing::error!(
+                "🔥 Gave up on webhook delivery {} after {} attempts.",
+                delivery.id,
+                attempt
+            );
+        }
+    }
+
+    Ok(deliveries.len())
+}
+
+/// Posts the delivery and returns the response status and, unless the
+/// receiver answered with a 2xx status, the reason it failed.
+///
+/// The host is checked again before every attempt and the request is sent to
+/// the checked addresses without following redirects, so that a subscription
+/// can not be pointed into the internal network after it was added.
+async fn send(
+    subscription: &WebhookSubscription,
+    delivery: &WebhookDelivery,
+) -> (Option<i32>, Option<String>) {
+    if !subscription.active {
+        return (None, Some("The subscription is inactive.".to_string()));
+    }
+
+    let url = match Url::parse(&subscription.url) {
+        Ok(url) if url.scheme() == "https" => url,
+        _ => return (None, Some("The url must be a valid https url.".to_string())),
+    };
+
+    let addresses = match resolve_public_url(&url).await {
+        Ok(addresses) => addresses,
+        Err(reason) => return (None, Some(reason)),
+    };
+
+    let client = match Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .redirect(Policy::none())
+        .resolve_to_addrs(url.host_str().unwrap_or_default(), &addresses)
+        .build()
+    {
+        Ok(client) => client,
+        Err(error) => return (None, Some(error.to_string())),
+    };
+
+    let body = delivery.payload.to_string();
+    let timestamp = Utc::now().timestamp();
+
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("X-Webhook-Id", delivery.id.to_string())
+        .header("X-Webhook-Event", &delivery.event_type)
+        .header("X-Webhook-Timestamp", timestamp.to_string())
+        .header(
+            "X-Webhook-Signature",
+            sign(&subscription.secret, timestamp, &body),
+        )
+        .body(body)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            (Some(response.status().as_u16() as i32), None)
+        }
+        Ok(response) => (
+            Some(response.status().as_u16() as i32),
+            Some(format!("The receiver answered with {}.", response.status())),
+        ),
+        Err(error) => (None, Some(error.to_string())),
+    }
+}